@@ -1,4 +1,6 @@
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// Supported datatypes for columns.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -6,6 +8,7 @@ pub enum ColumnDataType {
     Integer,
     Float,
     Text,
+    Decimal,
 }
 
 /// Implement the Display trait for ColumnDataType,
@@ -16,17 +19,21 @@ impl fmt::Display for ColumnDataType {
             ColumnDataType::Integer => write!(f, "Integer"),
             ColumnDataType::Float => write!(f, "Float"),
             ColumnDataType::Text => write!(f, "Text"),
+            ColumnDataType::Decimal => write!(f, "Decimal"),
         }
     }
 }
 
 /// Supported value types for columns.
 /// Distinct from datatype as this is actual data storage and that is more metadata-ish.
-#[derive(Debug, Clone, PartialEq)]
+/// A `Decimal` is stored as a mantissa and a scale (the number of fractional digits), so
+/// `19.99` is `Decimal(1999, 2)`; it is exact and keeps the digits it was written with.
+#[derive(Debug, Clone)]
 pub enum Value {
     Integer(i64),
     Float(f64),
     Text(String),
+    Decimal(i128, u32),
     Null,
 }
 
@@ -53,6 +60,12 @@ impl fmt::Display for Value {
                 value,
                 width = f.width().unwrap_or_default()
             ),
+            Value::Decimal(mantissa, scale) => write!(
+                f,
+                "{:>width$}",
+                format_decimal(*mantissa, *scale),
+                width = f.width().unwrap_or_default()
+            ),
             Value::Null => write!(
                 f,
                 "{:>width$}",
@@ -64,7 +77,172 @@ impl fmt::Display for Value {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Decimals are equal when they have the same numeric value, whatever their scale, so `1.50`
+/// equals `1.5`. Values of different variants are never equal.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Integer(left), Value::Integer(right)) => left == right,
+            (Value::Float(left), Value::Float(right)) => left == right,
+            (Value::Text(left), Value::Text(right)) => left == right,
+            (Value::Decimal(..), Value::Decimal(..)) => {
+                compare_decimals(self, other) == Some(Ordering::Equal)
+            }
+            (Value::Null, Value::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Values are used as keys when grouping rows, so they need to be hashable.
+/// Floats hash by their bit pattern, with `-0.0` folded into `0.0` so hashing agrees with `==`.
+/// Note that `NaN` is never equal to itself, so rows holding `NaN` each end up in their own group.
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Integer(value) => value.hash(state),
+            Value::Float(value) => {
+                let value = if *value == 0.0 { 0.0 } else { *value };
+                value.to_bits().hash(state)
+            }
+            Value::Text(value) => value.hash(state),
+            Value::Decimal(mantissa, scale) => {
+                // Hash the shortest form, so decimals that only differ in scale hash alike
+                let (mut mantissa, mut scale) = (*mantissa, *scale);
+                while scale > 0 && mantissa % 10 == 0 {
+                    mantissa /= 10;
+                    scale -= 1;
+                }
+                (mantissa, scale).hash(state)
+            }
+            Value::Null => {}
+        }
+    }
+}
+
+impl Value {
+    /// Parses a decimal literal such as `19.99` or `-0.5` into a `Value::Decimal`, keeping the
+    /// number of fractional digits as its scale. Returns `None` for anything else, including
+    /// exponents, and for literals that don't fit the mantissa.
+    pub fn parse_decimal(value: &str) -> Option<Value> {
+        let value = value.trim();
+        let (negative, digits) = match value.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, value.strip_prefix('+').unwrap_or(value)),
+        };
+        let (integer_part, fraction_part) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer_part.is_empty() && fraction_part.is_empty() {
+            return None;
+        }
+
+        let mut mantissa: i128 = 0;
+        for c in integer_part.chars().chain(fraction_part.chars()) {
+            let digit = c.to_digit(10)?;
+            mantissa = mantissa.checked_mul(10)?.checked_add(digit as i128)?;
+        }
+        let scale = u32::try_from(fraction_part.len()).ok()?;
+
+        Some(Value::Decimal(
+            if negative { -mantissa } else { mantissa },
+            scale,
+        ))
+    }
+
+    /// Orders any two values, including values of different variants, so that sorting never
+    /// has to give up on a pair. NULL comes before everything else, then values are ordered by
+    /// variant (integers, floats, decimals, text) and finally by content. Decimals compare by
+    /// numeric value, whatever their scale.
+    ///
+    /// `NaN` floats sort after every other float and are equal to each other here, although
+    /// `==` never considers them equal. `-0.0` and `0.0` are equal, like with `==`.
+    ///
+    /// The result is always `Some`, the `Option` only mirrors `partial_cmp`.
+    pub fn cmp_value(&self, other: &Value) -> Option<Ordering> {
+        let rank = |value: &Value| match value {
+            Value::Null => 0,
+            Value::Integer(_) => 1,
+            Value::Float(_) => 2,
+            Value::Decimal(..) => 3,
+            Value::Text(_) => 4,
+        };
+
+        let ordering = match (self, other) {
+            (Value::Integer(left), Value::Integer(right)) => left.cmp(right),
+            (Value::Float(left), Value::Float(right)) => match (left.is_nan(), right.is_nan()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => left.partial_cmp(right).unwrap_or(Ordering::Equal),
+            },
+            (Value::Decimal(..), Value::Decimal(..)) => {
+                compare_decimals(self, other).unwrap_or(Ordering::Equal)
+            }
+            (Value::Text(left), Value::Text(right)) => left.cmp(right),
+            _ => rank(self).cmp(&rank(other)),
+        };
+
+        Some(ordering)
+    }
+}
+
+/// Renders a decimal with exactly `scale` fractional digits.
+fn format_decimal(mantissa: i128, scale: u32) -> String {
+    let digits = mantissa.unsigned_abs().to_string();
+    let text = if scale == 0 {
+        digits
+    } else {
+        let scale = scale as usize;
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer_part, fraction_part) = digits.split_at(digits.len() - scale);
+        format!("{}.{}", integer_part, fraction_part)
+    };
+
+    if mantissa < 0 {
+        format!("-{}", text)
+    } else {
+        text
+    }
+}
+
+/// Brings the mantissas of two decimals to the larger of their scales, or returns `None` if
+/// that overflows.
+pub(crate) fn align_decimals(left: (i128, u32), right: (i128, u32)) -> Option<(i128, i128, u32)> {
+    let scale = left.1.max(right.1);
+    let rescale = |(mantissa, from): (i128, u32)| -> Option<i128> {
+        mantissa.checked_mul(10i128.checked_pow(scale - from)?)
+    };
+    Some((rescale(left)?, rescale(right)?, scale))
+}
+
+/// Orders two decimal values exactly, or returns `None` if either value is not a decimal.
+pub(crate) fn compare_decimals(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (
+            Value::Decimal(left_mantissa, left_scale),
+            Value::Decimal(right_mantissa, right_scale),
+        ) => {
+            match align_decimals(
+                (*left_mantissa, *left_scale),
+                (*right_mantissa, *right_scale),
+            ) {
+                Some((left, right, _)) => Some(left.cmp(&right)),
+                // Only values far beyond any realistic amount overflow, compare them as floats
+                None => {
+                    let as_f64 =
+                        |mantissa: i128, scale: u32| mantissa as f64 / 10f64.powi(scale as i32);
+                    as_f64(*left_mantissa, *left_scale)
+                        .partial_cmp(&as_f64(*right_mantissa, *right_scale))
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ForeignKeyInfo {
     pub reference_table: String,
     pub reference_column: String,
@@ -88,10 +266,15 @@ pub struct Column {
     pub data: Vec<Value>,
     pub is_primary_key: bool,
     pub foreign_key: Option<ForeignKeyInfo>,
+    pub not_null: bool,
+    pub unique: bool,
+    // Value of the column in inserts that omit it, see `Table::insert_with_columns`
+    pub default_value: Option<Value>,
 }
 
 impl Column {
-    /// Create a new column with the given name, datatype and default value.
+    /// Create a new column with the given name, datatype and default value. The default value
+    /// fills the column in inserts that leave it out; it doesn't add any data to the column.
     pub fn new(
         name: &str,
         data_type: ColumnDataType,
@@ -102,12 +285,85 @@ impl Column {
         Column {
             name: name.to_owned(),
             data_type,
-            data: match default_value {
-                Some(value) => vec![value],
-                None => Vec::new(),
-            },
+            data: Vec::new(),
             is_primary_key,
             foreign_key,
+            not_null: false,
+            unique: false,
+            default_value,
+        }
+    }
+
+    /// Start building a column with the given name and datatype, opting into constraints
+    /// one by one instead of passing every option positionally to `Column::new`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::{Column, ColumnDataType};
+    ///
+    /// let id = Column::builder("id", ColumnDataType::Integer).primary_key().build();
+    /// let email = Column::builder("email", ColumnDataType::Text).not_null().unique().build();
+    /// ```
+    pub fn builder(name: &str, data_type: ColumnDataType) -> ColumnBuilder {
+        ColumnBuilder {
+            column: Column::new(name, data_type, None, false, None),
+        }
+    }
+
+    /// Creates a column with the same name, datatype and constraints as this one, but no data.
+    pub(crate) fn clone_without_data(&self) -> Self {
+        Column {
+            name: self.name.clone(),
+            data_type: self.data_type,
+            data: Vec::new(),
+            is_primary_key: self.is_primary_key,
+            foreign_key: self.foreign_key.clone(),
+            not_null: self.not_null,
+            unique: self.unique,
+            default_value: self.default_value.clone(),
         }
     }
 }
+
+/// Builder for `Column`, created through `Column::builder`.
+#[derive(Debug, Clone)]
+pub struct ColumnBuilder {
+    column: Column,
+}
+
+impl ColumnBuilder {
+    /// Mark the column as the primary key of its table.
+    pub fn primary_key(mut self) -> Self {
+        self.column.is_primary_key = true;
+        self
+    }
+
+    /// Make the column reference the given column of another table.
+    pub fn foreign_key(mut self, foreign_key: ForeignKeyInfo) -> Self {
+        self.column.foreign_key = Some(foreign_key);
+        self
+    }
+
+    /// Reject NULL values in the column.
+    pub fn not_null(mut self) -> Self {
+        self.column.not_null = true;
+        self
+    }
+
+    /// Reject duplicate non-null values in the column.
+    pub fn unique(mut self) -> Self {
+        self.column.unique = true;
+        self
+    }
+
+    /// Fill the column with the given value in inserts that leave it out.
+    pub fn default_value(mut self, value: Value) -> Self {
+        self.column.default_value = Some(value);
+        self
+    }
+
+    pub fn build(self) -> Column {
+        self.column
+    }
+}
@@ -0,0 +1,586 @@
+use crate::column::{Column, ForeignKeyInfo, Value};
+use crate::database::db_errors::Error;
+use crate::database::wal::WalRecord;
+use crate::database::Database;
+use crate::table::helpers::column_fill_value;
+use crate::table::{table_errors, Table};
+
+impl Database {
+    /// Adds a new foreign key column to an existing table.
+    ///
+    /// The reference is validated the same way `create_table` does. Existing rows would have to
+    /// be back-filled with NULL, and since foreign key columns don't accept NULL values the
+    /// column can only be added while the table is still empty. Self-references are the
+    /// exception: they accept NULL, so existing rows are back-filled as root rows.
+    pub fn add_foreign_key_column(
+        &mut self,
+        table_name: &str,
+        column: Column,
+        mut fk_info: ForeignKeyInfo,
+    ) -> Result<(), Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        self.validate_name(&column.name)?;
+
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        if table.column_position(&column.name).is_ok() {
+            return Err(Error::TableError(table_errors::Error::ColumnAlreadyExists(
+                column.name,
+            )));
+        }
+
+        if column.is_primary_key && table.primary_key_column.is_some() {
+            return Err(Error::MultiplePrimaryKeys);
+        }
+
+        let own_column_names: Vec<String> = table
+            .columns
+            .iter()
+            .map(|c| c.name.clone())
+            .chain([column.name.clone()])
+            .collect();
+        self.resolve_foreign_key_names(table_name, &own_column_names, &mut fk_info);
+
+        // Self-referencing columns accept NULL, so existing rows are back-filled as root rows
+        let is_self_reference = fk_info.reference_table == table_name;
+        let row_count = table.row_count();
+        if row_count > 0 && !is_self_reference {
+            return Err(Error::NullForeignKey(column.name));
+        }
+
+        self.validate_foreign_key(&column, &fk_info)?;
+
+        if !is_self_reference {
+            if let Some(cycle) = self.find_foreign_key_cycle(table_name, &fk_info.reference_table)
+            {
+                return Err(Error::CircularForeignKeyReference(cycle));
+            }
+        }
+
+        if self.wal_enabled() {
+            self.append_to_wal(WalRecord::add_foreign_key_column(table_name, &column, &fk_info))?;
+        }
+
+        let mut column = column;
+        column.foreign_key = Some(fk_info.clone());
+        column.data = vec![Value::Null; row_count];
+
+        let table = self.tables.get_mut(table_name).unwrap(); // Safe to unwrap since we checked for the table's existence
+        if column.is_primary_key {
+            table.primary_key_column = Some(column.clone());
+        }
+        table.columns.push(column.clone());
+
+        if let Some(referenced_table) = self.tables.get_mut(&fk_info.reference_table) {
+            referenced_table
+                .referenced_as_foreign_key
+                .push((table_name.to_owned(), column.name));
+        }
+
+        Ok(())
+    }
+
+    /// Turns an existing column into a foreign key. The reference is validated the same way
+    /// `create_table` does, and every value already in the column has to be in the referenced
+    /// column. NULL values are only accepted in self-references, where they mark root rows.
+    /// Values missing from the referenced column are reported as `ForeignKeyViolations`, with
+    /// the index of every row holding one.
+    pub fn add_foreign_key(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+        reference_table: &str,
+        reference_column: &str,
+    ) -> Result<(), Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let column_idx = table.column_position(column_name)?;
+        let column = &table.columns[column_idx];
+
+        if column.foreign_key.is_some() {
+            return Err(Error::ForeignKeyAlreadyExists(
+                table_name.to_owned(),
+                column.name.clone(),
+            ));
+        }
+
+        let mut fk_info = ForeignKeyInfo::new(reference_table, reference_column);
+        let own_column_names: Vec<String> = table.columns.iter().map(|c| c.name.clone()).collect();
+        self.resolve_foreign_key_names(table_name, &own_column_names, &mut fk_info);
+        self.validate_foreign_key(column, &fk_info)?;
+
+        let is_self_reference = fk_info.reference_table == table_name;
+        if !is_self_reference {
+            if let Some(cycle) = self.find_foreign_key_cycle(table_name, &fk_info.reference_table)
+            {
+                return Err(Error::CircularForeignKeyReference(cycle));
+            }
+        }
+
+        let referenced_values = self.tables[&fk_info.reference_table]
+            .columns
+            .iter()
+            .find(|c| c.name == fk_info.reference_column)
+            .map_or(&[][..], |c| &c.data[..]);
+        let mut violating_rows = Vec::new();
+        for (row_idx, value) in column.data.iter().enumerate() {
+            if *value == Value::Null {
+                if !is_self_reference {
+                    return Err(Error::NullForeignKey(column.name.clone()));
+                }
+            } else if !referenced_values.contains(value) {
+                violating_rows.push(row_idx);
+            }
+        }
+        if !violating_rows.is_empty() {
+            return Err(Error::ForeignKeyViolations(
+                column.name.clone(),
+                fk_info.reference_table.clone(),
+                violating_rows,
+            ));
+        }
+
+        let column_name = column.name.clone();
+        if self.wal_enabled() {
+            self.append_to_wal(WalRecord::add_foreign_key(table_name, &column_name, &fk_info))?;
+        }
+
+        let table = self
+            .tables
+            .get_mut(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let column = &mut table.columns[column_idx];
+        column.foreign_key = Some(fk_info.clone());
+        if column.is_primary_key {
+            table.primary_key_column = Some(column.clone());
+        }
+
+        if let Some(referenced_table) = self.tables.get_mut(&fk_info.reference_table) {
+            referenced_table
+                .referenced_as_foreign_key
+                .push((table_name.to_owned(), column_name));
+        }
+
+        Ok(())
+    }
+
+    /// Removes the foreign key of a column. The column and its values stay, and the referenced
+    /// table stops tracking it, so deletes and primary key updates no longer cascade to it.
+    pub fn drop_foreign_key(&mut self, table_name: &str, column_name: &str) -> Result<(), Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let column_idx = table.column_position(column_name)?;
+        let column_name = table.columns[column_idx].name.clone();
+        let Some(fk_info) = table.columns[column_idx].foreign_key.clone() else {
+            return Err(Error::ForeignKeyNotFound(table_name.to_owned(), column_name));
+        };
+
+        if self.wal_enabled() {
+            self.append_to_wal(WalRecord::drop_foreign_key(table_name, &column_name))?;
+        }
+
+        let table = self
+            .tables
+            .get_mut(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let column = &mut table.columns[column_idx];
+        column.foreign_key = None;
+        if column.is_primary_key {
+            table.primary_key_column = Some(column.clone());
+        }
+
+        if let Some(referenced_table) = self.tables.get_mut(&fk_info.reference_table) {
+            referenced_table
+                .referenced_as_foreign_key
+                .retain(|(ref_table_name, ref_column_name)| {
+                    !(ref_table_name == table_name && *ref_column_name == column_name)
+                });
+        }
+
+        Ok(())
+    }
+
+    /// Adds a new column to an existing table, see `Table::add_column`. A foreign key on the
+    /// column is validated the same way `create_table` does, and the fill value of the existing
+    /// rows has to satisfy it.
+    pub fn add_column_to_table(
+        &mut self,
+        table_name: &str,
+        mut column: Column,
+        fill: Option<String>,
+    ) -> Result<(), Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        self.validate_name(&column.name)?;
+
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        let own_column_names: Vec<String> = table
+            .columns
+            .iter()
+            .map(|c| c.name.clone())
+            .chain([column.name.clone()])
+            .collect();
+        if let Some(fk_info) = &mut column.foreign_key {
+            self.resolve_foreign_key_names(table_name, &own_column_names, fk_info);
+        }
+
+        if let Some(fk_info) = &column.foreign_key {
+            self.validate_foreign_key(&column, fk_info)?;
+
+            if fk_info.reference_table != table_name {
+                if let Some(cycle) =
+                    self.find_foreign_key_cycle(table_name, &fk_info.reference_table)
+                {
+                    return Err(Error::CircularForeignKeyReference(cycle));
+                }
+            }
+
+            // Every existing row takes the fill value, so it must be a valid reference
+            if table.row_count() > 0 {
+                let fill_value = column_fill_value(&column, fill.clone())?;
+                self.check_foreign_key_reference(table_name, &column, &fill_value)?;
+            }
+        }
+
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::add_column(table_name, &column, fill.as_deref()));
+        let column_name = column.name.clone();
+        let fk_info = column.foreign_key.clone();
+
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.add_column(column, fill)?)
+        })?;
+
+        if let Some(fk_info) = fk_info {
+            if let Some(referenced_table) = self.tables.get_mut(&fk_info.reference_table) {
+                referenced_table
+                    .referenced_as_foreign_key
+                    .push((table_name.to_owned(), column_name));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops a column from a table, see `Table::drop_column`. A column referenced by foreign
+    /// keys can't be dropped. If the column itself is a foreign key, the referenced table stops
+    /// tracking it.
+    pub fn drop_column_from_table(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+    ) -> Result<(), Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let column = &table.columns[table.column_position(column_name)?];
+
+        // Foreign keys only reference primary keys, so they are what blocks dropping one
+        if column.is_primary_key && !table.referenced_as_foreign_key.is_empty() {
+            return Err(Error::ColumnReferencedBy(
+                table_name.to_owned(),
+                column.name.clone(),
+                referencing_columns(table),
+            ));
+        }
+
+        let column_name = column.name.clone();
+        let fk_info = column.foreign_key.clone();
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::drop_column(table_name, &column_name));
+
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.drop_column(&column_name)?)
+        })?;
+
+        if let Some(fk_info) = fk_info {
+            if let Some(referenced_table) = self.tables.get_mut(&fk_info.reference_table) {
+                referenced_table
+                    .referenced_as_foreign_key
+                    .retain(|(ref_table_name, ref_column_name)| {
+                        !(ref_table_name == table_name && *ref_column_name == column_name)
+                    });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the primary key constraint of a table, see `Table::drop_primary_key`. It can't be
+    /// removed while foreign keys, self-references included, reference the primary key.
+    pub fn drop_primary_key_on_table(&mut self, table_name: &str) -> Result<(), Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        if let Some(primary_key) = table.primary_key() {
+            if !table.referenced_as_foreign_key.is_empty() {
+                return Err(Error::ColumnReferencedBy(
+                    table_name.to_owned(),
+                    primary_key.name.clone(),
+                    referencing_columns(table),
+                ));
+            }
+        }
+
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::drop_primary_key(table_name));
+
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.drop_primary_key()?)
+        })
+    }
+
+    /// Makes an existing column of a table unique, see `Table::add_unique_constraint`.
+    pub fn add_unique_constraint_to_table(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+    ) -> Result<(), Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::add_unique_constraint(table_name, column_name));
+
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.add_unique_constraint(column_name)?)
+        })
+    }
+
+    /// Renames a column of a table, see `Table::rename_column`. Foreign keys referencing the
+    /// column follow the new name, and so does the entry of a renamed foreign key column in
+    /// the table it references.
+    pub fn rename_column_in_table(
+        &mut self,
+        table_name: &str,
+        old_name: &str,
+        new_name: &str,
+    ) -> Result<(), Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let old_name = table.columns[table.column_position(old_name)?].name.clone();
+        self.validate_name(new_name)?;
+
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::rename_column(table_name, &old_name, new_name));
+
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.rename_column(&old_name, new_name)?)
+        })?;
+
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let fk_info = table
+            .columns
+            .iter()
+            .find(|c| c.name == new_name)
+            .and_then(|c| c.foreign_key.clone());
+
+        // Foreign keys referencing the column, including self-references
+        for table in self.tables.values_mut() {
+            for column in table
+                .columns
+                .iter_mut()
+                .chain(table.primary_key_column.iter_mut())
+            {
+                if let Some(fk_info) = &mut column.foreign_key {
+                    if fk_info.reference_table == table_name && fk_info.reference_column == old_name
+                    {
+                        fk_info.reference_column = new_name.to_string();
+                    }
+                }
+            }
+        }
+
+        // The referenced table lists the foreign key columns pointing at it by name
+        if let Some(fk_info) = fk_info {
+            if let Some(referenced_table) = self.tables.get_mut(&fk_info.reference_table) {
+                for (ref_table_name, ref_column_name) in
+                    &mut referenced_table.referenced_as_foreign_key
+                {
+                    if ref_table_name == table_name && *ref_column_name == old_name {
+                        *ref_column_name = new_name.to_string();
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renames a table. Foreign keys referencing it follow the new name, and so do the entries
+    /// of its foreign key columns in the tables they reference, so cascades keep working.
+    pub fn rename_table(&mut self, old_name: &str, new_name: &str) -> Result<(), Error> {
+        let old_name: &str = &self.resolve_table_name_owned(old_name);
+        if !self.tables.contains_key(old_name) {
+            return Err(Error::TableNotFound(old_name.to_owned()));
+        }
+        if old_name == new_name {
+            return Ok(());
+        }
+        self.validate_name(new_name)?;
+
+        // Changing only the case of the name is allowed, even when names ignore case
+        let existing_name = self.resolve_table_name(new_name);
+        if existing_name != old_name && self.tables.contains_key(existing_name) {
+            return Err(Error::TableAlreadyExists(new_name.to_owned()));
+        }
+
+        if self.wal_enabled() {
+            self.append_to_wal(WalRecord::rename_table(old_name, new_name))?;
+        }
+
+        if let Some(mut table) = self.tables.remove(old_name) {
+            table.name = new_name.to_owned();
+            self.tables.insert(new_name.to_owned(), table);
+        }
+
+        // Including the self-references of the renamed table
+        for table in self.tables.values_mut() {
+            for column in table
+                .columns
+                .iter_mut()
+                .chain(table.primary_key_column.iter_mut())
+            {
+                if let Some(fk_info) = &mut column.foreign_key {
+                    if fk_info.reference_table == old_name {
+                        fk_info.reference_table = new_name.to_owned();
+                    }
+                }
+            }
+            for (ref_table_name, _) in &mut table.referenced_as_foreign_key {
+                if ref_table_name == old_name {
+                    *ref_table_name = new_name.to_owned();
+                }
+            }
+        }
+
+        for table_name in &mut self.deferred_foreign_key_tables {
+            if table_name == old_name {
+                *table_name = new_name.to_owned();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes a table and its records. Tables referencing it through foreign keys either make
+    /// the drop fail (`cascade == false`), or keep their columns and values but lose the foreign
+    /// keys pointing at it (`cascade == true`), as with `drop_foreign_key`. The referencing
+    /// tables themselves are never dropped.
+    pub fn drop_table(&mut self, table_name: &str, cascade: bool) -> Result<(), Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        // Self-references go away with the table itself
+        let referencing_columns: Vec<(String, String)> = table
+            .referenced_as_foreign_key
+            .iter()
+            .filter(|(ref_table_name, _)| ref_table_name != table_name)
+            .cloned()
+            .collect();
+
+        if !cascade && !referencing_columns.is_empty() {
+            let mut referencing_tables: Vec<String> = referencing_columns
+                .into_iter()
+                .map(|(ref_table_name, _)| ref_table_name)
+                .collect();
+            referencing_tables.sort();
+            referencing_tables.dedup();
+            return Err(Error::ReferencedByTables(table_name.to_owned(), referencing_tables));
+        }
+
+        if self.wal_enabled() {
+            self.append_to_wal(WalRecord::drop_table(table_name, cascade))?;
+        }
+
+        let Some(table) = self.tables.remove(table_name) else {
+            return Err(Error::TableNotFound(table_name.to_owned()));
+        };
+
+        for (ref_table_name, ref_column_name) in referencing_columns {
+            let Some(referencing_table) = self.tables.get_mut(&ref_table_name) else {
+                continue;
+            };
+            for column in referencing_table
+                .columns
+                .iter_mut()
+                .chain(referencing_table.primary_key_column.iter_mut())
+                .filter(|column| column.name == ref_column_name)
+            {
+                column.foreign_key = None;
+            }
+        }
+
+        // The tables this one referenced stop tracking its foreign key columns
+        for fk_info in table.columns.iter().filter_map(|c| c.foreign_key.as_ref()) {
+            if let Some(referenced_table) = self.tables.get_mut(&fk_info.reference_table) {
+                referenced_table
+                    .referenced_as_foreign_key
+                    .retain(|(ref_table_name, _)| ref_table_name != table_name);
+            }
+        }
+
+        self.deferred_foreign_key_tables
+            .retain(|deferred_table| deferred_table != table_name);
+
+        Ok(())
+    }
+}
+
+/// The foreign key columns referencing a table, as `table.column`.
+fn referencing_columns(table: &Table) -> Vec<String> {
+    table
+        .referenced_as_foreign_key
+        .iter()
+        .map(|(ref_table_name, ref_column_name)| format!("{}.{}", ref_table_name, ref_column_name))
+        .collect()
+}
@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+
+use crate::column::Value;
+use crate::database::db_errors::Error;
+use crate::database::{Database, IntegrityViolation};
+use crate::table::Table;
+
+impl Database {
+    /// Checks the foreign keys of every row inserted through `insert_into_table_deferred`.
+    ///
+    /// Every violation is reported, not just the first one. Rows are never removed by this check:
+    /// on failure the offending rows stay in their tables and remain pending, so they can be fixed
+    /// (or their parents inserted) and the check run again. Once it succeeds nothing is pending.
+    pub fn validate_constraints(&mut self) -> Result<(), Vec<Error>> {
+        let errors: Vec<Error> = self
+            .deferred_foreign_key_tables
+            .iter()
+            .flat_map(|table_name| self.foreign_key_errors(table_name, true))
+            .collect();
+
+        if errors.is_empty() {
+            self.deferred_foreign_key_tables.clear();
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Scans every foreign key column of every table and reports each non-null value that is
+    /// missing from its referenced column as a `ForeignKeyViolation`. This is a read-only audit,
+    /// e.g. after importing tables; an empty result means the database is consistent. It reports
+    /// the foreign key violations of `validate` as errors.
+    pub fn check_referential_integrity(&self) -> Vec<Error> {
+        self.validate()
+            .into_iter()
+            .filter_map(violation_error)
+            .collect()
+    }
+
+    /// Audits every foreign key link of the database, whatever way the rows got in: each non-null
+    /// foreign key value must be in its referenced column, and each table listed as referencing
+    /// another one (`referenced_as_foreign_key`) must have that foreign key. Violations are
+    /// reported table by table in name order; an empty result means the database is consistent.
+    pub fn validate(&self) -> Vec<IntegrityViolation> {
+        let mut violations = Vec::new();
+
+        for table in self.tables() {
+            violations.extend(self.foreign_key_violations(table, false));
+
+            for (referencing_table, referencing_column) in &table.referenced_as_foreign_key {
+                let references_table = self
+                    .tables
+                    .get(referencing_table)
+                    .and_then(|t| t.columns.iter().find(|c| &c.name == referencing_column))
+                    .and_then(|c| c.foreign_key.as_ref())
+                    .is_some_and(|fk_info| fk_info.reference_table == table.name);
+                if !references_table {
+                    violations.push(IntegrityViolation::UnmatchedReferencedBy {
+                        table: table.name.clone(),
+                        referencing_table: referencing_table.clone(),
+                        referencing_column: referencing_column.clone(),
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// Whether `validate` finds no violation.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_empty()
+    }
+
+    /// Collects the foreign key errors of a table, see `foreign_key_violations`.
+    fn foreign_key_errors(&self, table_name: &str, check_nulls: bool) -> Vec<Error> {
+        let Some(table) = self.tables.get(table_name) else {
+            return Vec::new();
+        };
+
+        self.foreign_key_violations(table, check_nulls)
+            .into_iter()
+            .filter_map(violation_error)
+            .collect()
+    }
+
+    /// Collects the foreign keys of a table whose referenced column is missing, and the values
+    /// of its foreign key columns missing from their referenced column. NULL values are reported
+    /// as missing when `check_nulls` is set, except in self-referencing columns where NULL marks
+    /// a root row.
+    fn foreign_key_violations(&self, table: &Table, check_nulls: bool) -> Vec<IntegrityViolation> {
+        let mut violations = Vec::new();
+
+        for column in &table.columns {
+            let Some(fk_info) = &column.foreign_key else {
+                continue;
+            };
+
+            let referenced_column = self
+                .tables
+                .get(&fk_info.reference_table)
+                .and_then(|referenced_table| {
+                    referenced_table
+                        .columns
+                        .iter()
+                        .find(|c| c.name == fk_info.reference_column)
+                });
+            let Some(referenced_column) = referenced_column else {
+                violations.push(IntegrityViolation::MissingReferencedColumn {
+                    table: table.name.clone(),
+                    column: column.name.clone(),
+                    reference_table: fk_info.reference_table.clone(),
+                    reference_column: fk_info.reference_column.clone(),
+                });
+                continue;
+            };
+
+            let referenced_values: HashSet<&Value> = referenced_column.data.iter().collect();
+            for (row_idx, value) in column.data.iter().enumerate() {
+                let missing = if *value == Value::Null {
+                    check_nulls && fk_info.reference_table != table.name
+                } else {
+                    !referenced_values.contains(value)
+                };
+                if missing {
+                    violations.push(IntegrityViolation::MissingReference {
+                        table: table.name.clone(),
+                        column: column.name.clone(),
+                        row_idx,
+                        value: value.clone(),
+                        reference_table: fk_info.reference_table.clone(),
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+/// The error reporting a foreign key violation, or `None` for the violations only `validate`
+/// reports.
+fn violation_error(violation: IntegrityViolation) -> Option<Error> {
+    match violation {
+        IntegrityViolation::MissingReference {
+            column,
+            value: Value::Null,
+            ..
+        } => Some(Error::NullForeignKey(column)),
+        IntegrityViolation::MissingReference {
+            column,
+            value,
+            reference_table,
+            ..
+        } => Some(Error::ForeignKeyViolation(
+            value.to_string(),
+            column,
+            reference_table,
+        )),
+        IntegrityViolation::MissingReferencedColumn {
+            reference_table,
+            reference_column,
+            ..
+        } => Some(Error::ReferencedColumnNotFound(reference_table, reference_column)),
+        IntegrityViolation::UnmatchedReferencedBy { .. } => None,
+    }
+}
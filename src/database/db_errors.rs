@@ -1,3 +1,4 @@
+use crate::column::ColumnDataType;
 use crate::table::table_errors;
 
 #[derive(Debug)]
@@ -11,11 +12,23 @@ pub enum Error {
     NullForeignKey(String),
     ForeignKeyViolation(String, String, String),
     MissingForeignKeyColumns(Vec<String>),
-    ParseError(usize, String),
     TableError(table_errors::Error),
     ReferencedColumnNotPrimaryKey(String, String),
     NoPrimaryKeyColumn(String),
     MissingPrimaryKeyValue,
+    CircularForeignKeyReference(Vec<String>),
+    WriteAheadLog(String),
+    BatchRecord(usize, Box<Error>), // record index in the batch, error
+    ReferencedByTables(String, Vec<String>), // table_name, referencing table names
+    MismatchedColumnTypes(String, String), // target column, source column
+    AmbiguousTableName(String), // table_name
+    InvalidStatementSyntax(usize, String), // character position, reason
+    ColumnReferencedBy(String, String, Vec<String>), // table_name, column_name, referencing columns
+    ForeignKeyAlreadyExists(String, String), // table_name, column_name
+    ForeignKeyNotFound(String, String), // table_name, column_name
+    ForeignKeyTypeMismatch(String, ColumnDataType, ColumnDataType), // column_name, expected, found
+    ForeignKeyViolations(String, String, Vec<usize>), // column_name, reference_table, row indices
+    AmbiguousJoinColumn(String, String, String), // column_name, left side, right side
 }
 
 impl std::fmt::Display for Error {
@@ -61,9 +74,6 @@ impl std::fmt::Display for Error {
                     columns.join(", ")
                 )
             }
-            Error::ParseError(index, value) => {
-                write!(f, "Failed to parse value '{}' at index {}", value, index)
-            }
             Error::TableError(err) => write!(f, "{}", err),
             Error::ReferencedColumnNotPrimaryKey(table_name, column_name) => {
                 write!(
@@ -78,6 +88,93 @@ impl std::fmt::Display for Error {
             Error::MissingPrimaryKeyValue => {
                 write!(f, "Primary key value not provided")
             }
+            Error::CircularForeignKeyReference(tables) => {
+                write!(
+                    f,
+                    "Circular foreign key reference: {}",
+                    tables.join(" -> ")
+                )
+            }
+            Error::ReferencedByTables(table_name, referencing_tables) => {
+                write!(
+                    f,
+                    "Table '{}' is referenced by: {}",
+                    table_name,
+                    referencing_tables.join(", ")
+                )
+            }
+            Error::MismatchedColumnTypes(target_column, source_column) => {
+                write!(
+                    f,
+                    "Column '{}' can't be set from column '{}' of another data type",
+                    target_column, source_column
+                )
+            }
+            Error::AmbiguousTableName(table_name) => {
+                write!(
+                    f,
+                    "The table name '{}' matches several tables that only differ by case",
+                    table_name
+                )
+            }
+            Error::ColumnReferencedBy(table_name, column_name, referencing_columns) => {
+                write!(
+                    f,
+                    "Column '{}' of table '{}' is referenced by the foreign keys: {}",
+                    column_name,
+                    table_name,
+                    referencing_columns.join(", ")
+                )
+            }
+            Error::ForeignKeyAlreadyExists(table_name, column_name) => {
+                write!(
+                    f,
+                    "Column '{}' of table '{}' is already a foreign key",
+                    column_name, table_name
+                )
+            }
+            Error::ForeignKeyNotFound(table_name, column_name) => {
+                write!(
+                    f,
+                    "Column '{}' of table '{}' is not a foreign key",
+                    column_name, table_name
+                )
+            }
+            Error::ForeignKeyTypeMismatch(column_name, expected, found) => {
+                write!(
+                    f,
+                    "Foreign key column '{}' is {}, but the column it references is {}",
+                    column_name, found, expected
+                )
+            }
+            Error::AmbiguousJoinColumn(column_name, left_side, right_side) => {
+                write!(
+                    f,
+                    "Column '{0}' is in both '{1}' and '{2}', qualify it as '{1}.{0}' or '{2}.{0}'",
+                    column_name, left_side, right_side
+                )
+            }
+            Error::ForeignKeyViolations(column_name, reference_table, row_indices) => {
+                let row_indices: Vec<String> =
+                    row_indices.iter().map(|row_idx| row_idx.to_string()).collect();
+                write!(
+                    f,
+                    "Foreign key violation: rows {} of column '{}' reference values missing \
+                     from table '{}'",
+                    row_indices.join(", "),
+                    column_name,
+                    reference_table
+                )
+            }
+            Error::InvalidStatementSyntax(position, reason) => {
+                write!(f, "Invalid statement at position {}: {}", position, reason)
+            }
+            Error::WriteAheadLog(msg) => {
+                write!(f, "Write-ahead log error: {}", msg)
+            }
+            Error::BatchRecord(row_idx, error) => {
+                write!(f, "Record {} of the batch: {}", row_idx, error)
+            }
         }
     }
 }
@@ -90,9 +187,15 @@ impl From<table_errors::Error> for Error {
             table_errors::Error::MismatchedColumnCount => {
                 Error::TableError(table_errors::Error::MismatchedColumnCount)
             }
-            table_errors::Error::ParseError(index, value) => {
-                Error::TableError(table_errors::Error::ParseError(index, value))
-            }
+            table_errors::Error::ParseError {
+                column_name,
+                value,
+                expected,
+            } => Error::TableError(table_errors::Error::ParseError {
+                column_name,
+                value,
+                expected,
+            }),
             table_errors::Error::NonExistingColumns(columns) => {
                 Error::TableError(table_errors::Error::NonExistingColumns(columns))
             }
@@ -120,9 +223,92 @@ impl From<table_errors::Error> for Error {
             table_errors::Error::CannotBatchUpdatePrimaryKey => {
                 Error::TableError(table_errors::Error::CannotBatchUpdatePrimaryKey)
             }
+            table_errors::Error::CannotUpdatePrimaryKey(column_name) => {
+                Error::TableError(table_errors::Error::CannotUpdatePrimaryKey(column_name))
+            }
+            table_errors::Error::CannotDropPrimaryKey(column_name) => {
+                Error::TableError(table_errors::Error::CannotDropPrimaryKey(column_name))
+            }
             table_errors::Error::PrimaryKeyNotProvided(column_name) => {
                 Error::TableError(table_errors::Error::PrimaryKeyNotProvided(column_name))
             }
+            table_errors::Error::NonNumericColumn(column_name) => {
+                Error::TableError(table_errors::Error::NonNumericColumn(column_name))
+            }
+            table_errors::Error::ColumnAlreadyExists(column_name) => {
+                Error::TableError(table_errors::Error::ColumnAlreadyExists(column_name))
+            }
+            table_errors::Error::InvalidConditionValue(column_name, value) => {
+                Error::TableError(table_errors::Error::InvalidConditionValue(column_name, value))
+            }
+            table_errors::Error::NullValue(column_name) => {
+                Error::TableError(table_errors::Error::NullValue(column_name))
+            }
+            table_errors::Error::DuplicateValue(column_name, value) => {
+                Error::TableError(table_errors::Error::DuplicateValue(column_name, value))
+            }
+            table_errors::Error::DuplicateOutputColumn(column_name) => {
+                Error::TableError(table_errors::Error::DuplicateOutputColumn(column_name))
+            }
+            table_errors::Error::AmbiguousColumnName(column_name) => {
+                Error::TableError(table_errors::Error::AmbiguousColumnName(column_name))
+            }
+            table_errors::Error::InvalidName(name, reason) => {
+                Error::TableError(table_errors::Error::InvalidName(name, reason))
+            }
+            table_errors::Error::DuplicateColumnInInsert(column_name) => {
+                Error::TableError(table_errors::Error::DuplicateColumnInInsert(column_name))
+            }
+            table_errors::Error::InvalidExpression(reason) => {
+                Error::TableError(table_errors::Error::InvalidExpression(reason))
+            }
+            table_errors::Error::InvalidConditionSyntax(position, reason) => {
+                Error::TableError(table_errors::Error::InvalidConditionSyntax(position, reason))
+            }
+            table_errors::Error::SchemaMismatch(differences) => {
+                Error::TableError(table_errors::Error::SchemaMismatch(differences))
+            }
+            table_errors::Error::NoColumns => {
+                Error::TableError(table_errors::Error::NoColumns)
+            }
+            table_errors::Error::TypeMismatch(column_name, expected) => {
+                Error::TableError(table_errors::Error::TypeMismatch(column_name, expected))
+            }
+            table_errors::Error::NoPrimaryKey => {
+                Error::TableError(table_errors::Error::NoPrimaryKey)
+            }
+            table_errors::Error::InconsistentColumnLengths(column_name, length, expected) => {
+                Error::TableError(table_errors::Error::InconsistentColumnLengths(
+                    column_name,
+                    length,
+                    expected,
+                ))
+            }
+            table_errors::Error::IncompatibleTypeConversion(column_name, new_type, value) => {
+                Error::TableError(table_errors::Error::IncompatibleTypeConversion(
+                    column_name,
+                    new_type,
+                    value,
+                ))
+            }
+            table_errors::Error::ForeignKeyTypeChange(column_name) => {
+                Error::TableError(table_errors::Error::ForeignKeyTypeChange(column_name))
+            }
+            table_errors::Error::InvalidColumnOrder(reason) => {
+                Error::TableError(table_errors::Error::InvalidColumnOrder(reason))
+            }
+            table_errors::Error::DuplicateValues(column_name, values) => {
+                Error::TableError(table_errors::Error::DuplicateValues(column_name, values))
+            }
+            table_errors::Error::NonFiniteFloat(column_name, value) => {
+                Error::TableError(table_errors::Error::NonFiniteFloat(column_name, value))
+            }
         }
     }
 }
+
+impl From<table_errors::BatchError> for Error {
+    fn from(error: table_errors::BatchError) -> Self {
+        Error::BatchRecord(error.row_idx, Box::new(error.error.into()))
+    }
+}
@@ -1,4 +1,5 @@
-use crate::database::Database;
+use crate::database::{AffectedRows, Database, DeletedRecords};
+use crate::database::wal::WalRecord;
 use crate::database::db_errors::Error;
 use crate::table::helpers::evaluate_nested_conditions;
 use crate::table::NestedCondition;
@@ -8,12 +9,62 @@ impl Database {
         &mut self,
         table_name: &str,
         nested_condition: NestedCondition,
-    ) -> Result<(), Error> {
+    ) -> Result<AffectedRows, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::delete(table_name, &nested_condition));
+
+        // Only the top-level delete is logged, replaying it redoes the cascade
+        self.logged(wal_record, |db| {
+            db.delete_with_nested_conditions_cascading(
+                table_name,
+                nested_condition,
+                &mut Vec::new(),
+            )
+        })
+    }
+
+    /// Deletes like `delete_with_nested_conditions_in_table`, but returns the deleted records of
+    /// every table, see `Table::delete_returning`. The table deleted from comes first, followed
+    /// by the tables the delete cascaded to, in the order they were reached.
+    pub fn delete_returning_from_table(
+        &mut self,
+        table_name: &str,
+        nested_condition: NestedCondition,
+    ) -> Result<DeletedRecords, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::delete(table_name, &nested_condition));
+
+        let mut deleted_records = Vec::new();
+        self.logged(wal_record, |db| {
+            db.delete_with_nested_conditions_cascading(
+                table_name,
+                nested_condition,
+                &mut deleted_records,
+            )
+        })?;
+
+        Ok(deleted_records)
+    }
+
+    /// Deletes the matching records and the records referencing them, adding the deleted
+    /// records of every table to `deleted_records`.
+    fn delete_with_nested_conditions_cascading(
+        &mut self,
+        table_name: &str,
+        nested_condition: NestedCondition,
+        deleted_records: &mut DeletedRecords,
+    ) -> Result<AffectedRows, Error> {
         let table = self
             .tables
             .get_mut(table_name)
             .ok_or(Error::TableNotFound(table_name.to_owned()))?;
 
+        let nested_condition = table.resolve_condition(&nested_condition)?;
+
         let primary_key_column_idx = table
             .columns
             .iter()
@@ -22,7 +73,7 @@ impl Database {
 
         let mut primary_key_values_to_delete = Vec::new();
 
-        for row_idx in 0..table.columns[0].data.len() {
+        for row_idx in 0..table.row_count() {
             if evaluate_nested_conditions(&nested_condition, &table.columns, row_idx)? {
                 let primary_key_value = table.columns[primary_key_column_idx]
                     .data
@@ -34,7 +85,15 @@ impl Database {
         }
 
         let table_foreign_key_data = table.referenced_as_foreign_key.clone();
-        table.delete_with_nested_conditions(&nested_condition)?;
+        let table_deleted_records = table.delete_returning(&nested_condition)?;
+        let mut affected_rows = AffectedRows {
+            rows: table_deleted_records.len(),
+            cascaded: 0,
+        };
+        match deleted_records.iter_mut().find(|(name, _)| name == table_name) {
+            Some((_, records)) => records.extend(table_deleted_records),
+            None => deleted_records.push((table_name.to_owned(), table_deleted_records)),
+        }
 
         for (ref_table_name, ref_column_name) in table_foreign_key_data {
             for primary_key_value in &primary_key_values_to_delete {
@@ -44,10 +103,81 @@ impl Database {
                     primary_key_value.to_string(),
                 );
 
-                self.delete_with_nested_conditions_in_table(&ref_table_name, ref_nested_condition)?;
+                affected_rows.cascaded += self
+                    .delete_with_nested_conditions_cascading(
+                        &ref_table_name,
+                        ref_nested_condition,
+                        deleted_records,
+                    )?
+                    .total();
+            }
+        }
+
+        Ok(affected_rows)
+    }
+
+    /// Deletes every row of a table. Tables referencing it through foreign keys either make the
+    /// truncate fail (`cascade == false`), or are truncated as well (`cascade == true`), along
+    /// with the tables referencing those.
+    pub fn truncate_table(
+        &mut self,
+        table_name: &str,
+        cascade: bool,
+    ) -> Result<AffectedRows, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        // Self-references are cleared with the table itself
+        let mut referencing_tables: Vec<String> = table
+            .referenced_as_foreign_key
+            .iter()
+            .map(|(ref_table_name, _)| ref_table_name.clone())
+            .filter(|ref_table_name| ref_table_name != table_name)
+            .collect();
+        referencing_tables.sort();
+        referencing_tables.dedup();
+
+        if !cascade && !referencing_tables.is_empty() {
+            return Err(Error::ReferencedByTables(table_name.to_owned(), referencing_tables));
+        }
+
+        // Collect every table reached through the references, each once
+        let mut tables_to_truncate = vec![table_name.to_owned()];
+        let mut idx = 0;
+        while idx < tables_to_truncate.len() {
+            let current = self
+                .tables
+                .get(&tables_to_truncate[idx])
+                .ok_or(Error::TableNotFound(tables_to_truncate[idx].clone()))?;
+            for (ref_table_name, _) in &current.referenced_as_foreign_key {
+                if !tables_to_truncate.contains(ref_table_name) {
+                    tables_to_truncate.push(ref_table_name.clone());
+                }
+            }
+            idx += 1;
+        }
+
+        if self.wal_enabled() {
+            self.append_to_wal(WalRecord::truncate(table_name, cascade))?;
+        }
+
+        let mut affected_rows = AffectedRows::default();
+        for (idx, name) in tables_to_truncate.iter().enumerate() {
+            if let Some(table) = self.tables.get_mut(name) {
+                let deleted = table.truncate();
+                if idx == 0 {
+                    affected_rows.rows = deleted;
+                } else {
+                    affected_rows.cascaded += deleted;
+                }
             }
         }
+        self.deferred_foreign_key_tables
+            .retain(|name| !tables_to_truncate.contains(name));
 
-        Ok(())
+        Ok(affected_rows)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,93 @@
+use crate::database::db_errors::Error;
+use crate::database::Database;
+use crate::table::Table;
+
+impl Database {
+    /// Returns the names of the tables referenced through foreign keys by the given table.
+    pub(crate) fn referenced_tables(&self, table_name: &str) -> Vec<String> {
+        let mut referenced: Vec<String> = Vec::new();
+        if let Some(table) = self.tables.get(table_name) {
+            for fk_info in table.columns.iter().filter_map(|c| c.foreign_key.as_ref()) {
+                if !referenced.contains(&fk_info.reference_table) {
+                    referenced.push(fk_info.reference_table.clone());
+                }
+            }
+        }
+        referenced
+    }
+
+    /// Returns every table after the tables it references through foreign keys, and in name
+    /// order otherwise. Self-references don't count. Fails if the foreign keys form a cycle,
+    /// which `create_table` prevents but direct changes to `tables` don't.
+    pub fn tables_in_dependency_order(&self) -> Result<Vec<&Table>, Error> {
+        let mut remaining = self.table_names();
+        let mut ordered = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            let is_ready = |table_name: &String| {
+                self.referenced_tables(table_name)
+                    .iter()
+                    .all(|referenced| referenced == table_name || !remaining.contains(referenced))
+            };
+            match remaining.iter().position(is_ready) {
+                Some(next_idx) => ordered.push(&self.tables[&remaining.remove(next_idx)]),
+                None => {
+                    // Every remaining table references another remaining one
+                    let from_table = &remaining[0];
+                    let cycle = self
+                        .referenced_tables(from_table)
+                        .iter()
+                        .filter(|referenced| *referenced != from_table)
+                        .find_map(|referenced| self.find_foreign_key_cycle(from_table, referenced))
+                        .unwrap_or(remaining);
+                    return Err(Error::CircularForeignKeyReference(cycle));
+                }
+            }
+        }
+        Ok(ordered)
+    }
+
+    /// Checks whether adding a foreign key from `from_table` to `to_table` would close a cycle
+    /// in the foreign key graph. If so, returns the cycle as a list of table names starting and
+    /// ending with `from_table`, e.g. `["a", "b", "a"]`.
+    pub(crate) fn find_foreign_key_cycle(
+        &self,
+        from_table: &str,
+        to_table: &str,
+    ) -> Option<Vec<String>> {
+        let mut path = vec![from_table.to_string()];
+        let mut visited: Vec<String> = Vec::new();
+
+        if self.find_path(to_table, from_table, &mut path, &mut visited) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Depth-first search for a foreign key path from `current` to `target`, appending the
+    /// visited table names to `path`.
+    fn find_path(
+        &self,
+        current: &str,
+        target: &str,
+        path: &mut Vec<String>,
+        visited: &mut Vec<String>,
+    ) -> bool {
+        path.push(current.to_string());
+        if current == target {
+            return true;
+        }
+
+        if !visited.iter().any(|t| t == current) {
+            visited.push(current.to_string());
+            for next in self.referenced_tables(current) {
+                if self.find_path(&next, target, path, visited) {
+                    return true;
+                }
+            }
+        }
+
+        path.pop();
+        false
+    }
+}
@@ -1,10 +1,21 @@
-use crate::column::{ColumnDataType, Value};
-use crate::database::Database;
+use crate::column::Value;
+use crate::database::{AffectedRows, Database};
+use crate::database::wal::WalRecord;
 use crate::database::db_errors::Error;
-use crate::table::table_errors;
+use crate::table::helpers::{check_insert_columns_unique, parse_column_value_or_null};
+use crate::table::{table_errors, InsertOutcome, NestedCondition};
+use std::collections::HashSet;
 
 impl Database {
-    pub fn insert_into_table(&mut self, table_name: &str, data: Vec<String>) -> Result<(), Error> {
+    pub fn insert_into_table(
+        &mut self,
+        table_name: &str,
+        data: Vec<String>,
+    ) -> Result<InsertOutcome, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::insert(table_name, &data));
         let copied_tables = self.tables.clone();
 
         let table = self
@@ -29,25 +40,13 @@ impl Database {
                         fk_info.reference_column.clone(),
                     ))?;
 
-                let value = if value_str.trim().to_lowercase() == "null" {
-                    Value::Null
-                } else {
-                    match column.data_type {
-                        ColumnDataType::Integer => Value::Integer(
-                            value_str
-                                .parse()
-                                .map_err(|_| Error::ParseError(column_idx, value_str.to_owned()))?,
-                        ),
-                        ColumnDataType::Float => Value::Float(
-                            value_str
-                                .parse()
-                                .map_err(|_| Error::ParseError(column_idx, value_str.to_owned()))?,
-                        ),
-                        ColumnDataType::Text => Value::Text(value_str.to_owned()),
-                    }
-                };
+                let value = parse_column_value_or_null(column, value_str.to_owned())?;
 
+                // A NULL self-reference marks a root row, e.g. an employee without a manager
                 if value == Value::Null {
+                    if fk_info.reference_table == table_name {
+                        continue;
+                    }
                     return Err(Error::NullForeignKey(column.name.clone()));
                 }
 
@@ -62,9 +61,13 @@ impl Database {
         }
 
         // If all foreign key constraints are satisfied, insert the data into the table
-        table.insert(data)?;
-
-        Ok(())
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.insert(data)?)
+        })
     }
 
     pub fn insert_with_columns_into_table(
@@ -72,13 +75,19 @@ impl Database {
         table_name: &str,
         column_names: Vec<String>,
         data: Vec<String>,
-    ) -> Result<(), Error> {
+    ) -> Result<InsertOutcome, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::insert_with_columns(table_name, &column_names, &data));
         let copied_tables = self.tables.clone();
 
         let table = self
             .tables
             .get_mut(table_name)
             .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let column_names = table.resolve_column_names(column_names)?;
+        check_insert_columns_unique(&column_names)?;
 
         // Check if all foreign key columns are included in the provided column_names
         let missing_foreign_key_columns: Vec<_> = table
@@ -118,29 +127,13 @@ impl Database {
                         fk_info.reference_column.clone(),
                     ))?;
 
-                let value = if value_str.trim().to_lowercase() == "null" {
-                    Value::Null
-                } else {
-                    match column.data_type {
-                        ColumnDataType::Integer => {
-                            Value::Integer(value_str.parse().map_err(|_| {
-                                Error::TableError(table_errors::Error::ParseError(
-                                    column_idx,
-                                    value_str.to_owned(),
-                                ))
-                            })?)
-                        }
-                        ColumnDataType::Float => Value::Float(value_str.parse().map_err(|_| {
-                            Error::TableError(table_errors::Error::ParseError(
-                                column_idx,
-                                value_str.to_owned(),
-                            ))
-                        })?),
-                        ColumnDataType::Text => Value::Text(value_str.to_owned()),
-                    }
-                };
+                let value = parse_column_value_or_null(column, value_str.to_owned())?;
 
+                // A NULL self-reference marks a root row, e.g. an employee without a manager
                 if value == Value::Null {
+                    if fk_info.reference_table == table_name {
+                        continue;
+                    }
                     return Err(Error::NullForeignKey(column.name.clone()));
                 }
 
@@ -155,8 +148,261 @@ impl Database {
         }
 
         // If all foreign key constraints are satisfied, insert the data into the table
-        table.insert_with_columns(column_names, data)?;
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.insert_with_columns(column_names, data)?)
+        })
+    }
+
+    /// Inserts a row without checking its foreign keys, so child rows can be loaded before the
+    /// rows they reference. The row stays in the table while its checks are deferred; call
+    /// `validate_constraints` once loading is complete to check every deferred foreign key.
+    /// Constraints local to the table (primary key, not-null, unique) are still checked.
+    pub fn insert_into_table_deferred(
+        &mut self,
+        table_name: &str,
+        data: Vec<String>,
+    ) -> Result<InsertOutcome, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::insert_deferred(table_name, &data));
+
+        let outcome = self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.insert(data)?)
+        })?;
+
+        if !self.deferred_foreign_key_tables.iter().any(|t| t == table_name) {
+            self.deferred_foreign_key_tables.push(table_name.to_owned());
+        }
+
+        Ok(outcome)
+    }
+
+    /// Inserts a row like `insert_into_table`, but skips it when its primary key or a value of a
+    /// unique column already exists. Returns whether the row was inserted; foreign key
+    /// violations are still errors.
+    pub fn insert_or_ignore_into_table(
+        &mut self,
+        table_name: &str,
+        data: Vec<String>,
+    ) -> Result<bool, Error> {
+        match self.insert_into_table(table_name, data) {
+            Ok(_) => Ok(true),
+            Err(Error::TableError(table_errors::Error::DuplicatePrimaryKey))
+            | Err(Error::TableError(table_errors::Error::DuplicateValue(..))) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Inserts a row, replacing the row with the same primary key if there is one, see
+    /// `Table::replace`. The foreign keys of the new values are checked like on insert. Rows
+    /// referencing the replaced row keep referencing it, and are updated if its primary key is
+    /// stored differently, e.g. a decimal key with another scale.
+    pub fn replace_into_table(
+        &mut self,
+        table_name: &str,
+        data: Vec<String>,
+    ) -> Result<AffectedRows, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::replace(table_name, &data));
+
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let primary_key_idx = table
+            .columns
+            .iter()
+            .position(|c| c.is_primary_key)
+            .ok_or(Error::NoPrimaryKeyColumn(table_name.to_owned()))?;
+
+        let record = table.parse_record(data.clone())?;
+        for (column, value_str) in table.columns.iter().zip(&data) {
+            self.check_foreign_key_value(table_name, column, value_str)?;
+        }
+        let new_primary_key_value = record[primary_key_idx].clone();
+        let old_primary_key_value = table.columns[primary_key_idx]
+            .data
+            .iter()
+            .find(|value| **value == new_primary_key_value)
+            .cloned();
+
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            table.replace(data)?;
+            let mut affected_rows = AffectedRows { rows: 1, cascaded: 0 };
+
+            if let Some(old_primary_key_value) = old_primary_key_value {
+                if old_primary_key_value.to_string() != new_primary_key_value.to_string() {
+                    for (ref_table_name, ref_column_name) in table.referenced_as_foreign_key.clone()
+                    {
+                        let condition = NestedCondition::Condition(
+                            ref_column_name.clone(),
+                            "=".to_string(),
+                            old_primary_key_value.to_string(),
+                        );
+                        affected_rows.cascaded += db
+                            .update_with_nested_conditions_cascading(
+                                &ref_table_name,
+                                (ref_column_name, new_primary_key_value.to_string()),
+                                condition,
+                            )?
+                            .total();
+                    }
+                }
+            }
+
+            Ok(affected_rows)
+        })
+    }
+
+    /// Inserts several rows at once, with the checks of `insert_into_table`. The foreign keys of
+    /// the whole batch are checked against a single snapshot of the referenced columns, and
+    /// either every row is inserted or none. Rows of a self-referencing table may reference
+    /// other rows of the same batch.
+    pub fn insert_many_into_table(
+        &mut self,
+        table_name: &str,
+        rows: Vec<Vec<String>>,
+    ) -> Result<usize, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::insert_many(table_name, &rows));
+
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        let records = rows
+            .into_iter()
+            .enumerate()
+            .map(|(row_idx, row)| {
+                table
+                    .parse_record(row)
+                    .map_err(|error| Error::BatchRecord(row_idx, Box::new(error.into())))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Check the foreign key constraints of the whole batch first
+        self.check_batch_foreign_keys(table_name, &records)?;
+
+        // If all foreign key constraints are satisfied, insert the batch into the table
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.insert_parsed_many(records)?)
+        })
+    }
+
+    /// Checks the foreign keys of a batch of parsed records for a table against a single
+    /// snapshot of the referenced columns. Self-references may point to records of the batch.
+    pub(crate) fn check_batch_foreign_keys(
+        &self,
+        table_name: &str,
+        records: &[Vec<Value>],
+    ) -> Result<(), Error> {
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        for (column_idx, column) in table.columns.iter().enumerate() {
+            let Some(fk_info) = &column.foreign_key else {
+                continue;
+            };
+
+            let referenced_table = self.tables.get(&fk_info.reference_table).ok_or(
+                Error::ReferencedTableNotFound(fk_info.reference_table.clone()),
+            )?;
+            let referenced_column_idx = referenced_table
+                .columns
+                .iter()
+                .position(|c| c.name == fk_info.reference_column)
+                .ok_or(Error::ReferencedColumnNotFound(
+                    fk_info.reference_table.clone(),
+                    fk_info.reference_column.clone(),
+                ))?;
+
+            let self_reference = fk_info.reference_table == table_name;
+            let mut referenced_values: HashSet<&Value> = referenced_table.columns
+                [referenced_column_idx]
+                .data
+                .iter()
+                .collect();
+            if self_reference {
+                referenced_values
+                    .extend(records.iter().map(|record| &record[referenced_column_idx]));
+            }
+
+            for (row_idx, record) in records.iter().enumerate() {
+                let value = &record[column_idx];
+                let error = if *value == Value::Null {
+                    // A NULL self-reference marks a root row, e.g. an employee without a manager
+                    (!self_reference).then(|| Error::NullForeignKey(column.name.clone()))
+                } else if !referenced_values.contains(value) {
+                    Some(Error::ForeignKeyViolation(
+                        value.to_string(),
+                        column.name.clone(),
+                        fk_info.reference_table.clone(),
+                    ))
+                } else {
+                    None
+                };
+
+                if let Some(error) = error {
+                    return Err(Error::BatchRecord(row_idx, Box::new(error)));
+                }
+            }
+        }
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Appends every row of the source table to the target table, see `Table::append`. The
+    /// foreign keys of the appended rows are checked like in `insert_many_into_table`, and
+    /// either every row is appended or none.
+    pub fn append_table(&mut self, target_table: &str, source_table: &str) -> Result<usize, Error> {
+        let target_table: &str = &self.resolve_table_name_owned(target_table);
+        let source_table: &str = &self.resolve_table_name_owned(source_table);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::append_table(target_table, source_table));
+
+        let target = self
+            .tables
+            .get(target_table)
+            .ok_or(Error::TableNotFound(target_table.to_owned()))?;
+        let source = self
+            .tables
+            .get(source_table)
+            .ok_or(Error::TableNotFound(source_table.to_owned()))?;
+        let records = target.records_to_append(source)?;
+
+        self.check_batch_foreign_keys(target_table, &records)?;
+
+        self.logged(wal_record, |db| {
+            let target = db
+                .tables
+                .get_mut(target_table)
+                .ok_or(Error::TableNotFound(target_table.to_owned()))?;
+            Ok(target.insert_parsed_many(records)?)
+        })
+    }
+}
@@ -1,17 +1,96 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::column::{Column, ColumnDataType, Value};
+use crate::column::{Column, ColumnDataType, ForeignKeyInfo, Value};
 use crate::database::db_errors::Error;
+use crate::database::wal::{WalFile, WalRecord};
+use crate::table::helpers::validate_name;
 use crate::table::{helpers::evaluate_nested_conditions, table_errors, NestedCondition, Table};
+use crate::table::MAX_NAME_LENGTH;
 
-mod db_errors;
+mod alter_funcs;
+mod constraint_funcs;
+pub(crate) mod db_errors;
+mod dependency_funcs;
 mod insert_funcs;
+mod schema_funcs;
+mod select_funcs;
 mod update_funcs;
 mod delete_funcs;
+mod wal;
+
+/// Rows changed by an update or delete: `rows` in the table it was called on, and `cascaded` in
+/// the tables referencing it, changed through foreign keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AffectedRows {
+    pub rows: usize,
+    pub cascaded: usize,
+}
+
+impl AffectedRows {
+    /// Every changed row, including the cascaded ones.
+    pub fn total(&self) -> usize {
+        self.rows + self.cascaded
+    }
+}
+
+/// Records removed by `Database::delete_returning_from_table`, with the name of the table each
+/// group of records was deleted from.
+pub type DeletedRecords = Vec<(String, Vec<Vec<Value>>)>;
+
+/// Outcome of `Database::update_from`: the rows updated from a matching source row, including
+/// cascades, and the target rows left untouched because no source row matched.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UpdateFromOutcome {
+    pub affected_rows: AffectedRows,
+    pub unmatched: usize,
+}
+
+/// A broken foreign key link found by `Database::validate`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrityViolation {
+    /// A non-null foreign key value missing from the referenced column.
+    MissingReference {
+        table: String,
+        column: String,
+        row_idx: usize,
+        value: Value,
+        reference_table: String,
+    },
+    /// A foreign key whose referenced table or column does not exist.
+    MissingReferencedColumn {
+        table: String,
+        column: String,
+        reference_table: String,
+        reference_column: String,
+    },
+    /// A table listed as referenced by a column that does not exist or does not reference it.
+    UnmatchedReferencedBy {
+        table: String,
+        referencing_table: String,
+        referencing_column: String,
+    },
+}
+
+/// Outcome of `Database::query`: the names of the selected columns and one row of values per
+/// joined record, in the same order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
 
 pub struct Database {
     pub name: String,
     pub tables: HashMap<String, Table>,
+    // Tables with rows whose foreign keys are unchecked, see `insert_into_table_deferred`
+    pub(crate) deferred_foreign_key_tables: Vec<String>,
+    // Write-ahead log every operation is appended to, see `enable_wal`
+    pub(crate) wal: Option<WalFile>,
+    // Longest table or column name accepted, see `set_max_name_length`
+    pub(crate) max_name_length: usize,
+    // Whether table and column names given by callers ignore case, see
+    // `set_case_insensitive_names`
+    pub(crate) case_insensitive_names: bool,
 }
 
 impl Database {
@@ -19,15 +98,113 @@ impl Database {
         Self {
             name,
             tables: HashMap::new(),
+            deferred_foreign_key_tables: Vec::new(),
+            wal: None,
+            max_name_length: MAX_NAME_LENGTH,
+            case_insensitive_names: false,
+        }
+    }
+
+    /// Creates an empty database whose table and column names ignore case, see
+    /// `set_case_insensitive_names`.
+    pub fn new_case_insensitive(name: String) -> Self {
+        Self {
+            case_insensitive_names: true,
+            ..Self::new(name)
         }
     }
 
-    pub fn create_table(&mut self, table_name: &str, columns: Vec<Column>) -> Result<(), Error> {
+    /// Makes the table names given to every method of the database, and the column names given
+    /// to every table, ignore case (see `Table::set_case_insensitive_names`). Tables and columns
+    /// keep their original names, which are the ones written to the write-ahead log. Enabling it
+    /// fails if two tables only differ by case, and while it is enabled `create_table` rejects
+    /// such names.
+    pub fn set_case_insensitive_names(&mut self, enabled: bool) -> Result<(), Error> {
+        if enabled {
+            let mut table_names: Vec<&String> = self.tables.keys().collect();
+            table_names.sort();
+            let mut lowercase_names = HashSet::new();
+            for table_name in table_names {
+                if !lowercase_names.insert(table_name.to_lowercase()) {
+                    return Err(Error::AmbiguousTableName(table_name.clone()));
+                }
+            }
+        }
+
+        if self.wal_enabled() {
+            self.append_to_wal(WalRecord::case_insensitive_names(enabled))?;
+        }
+
+        self.case_insensitive_names = enabled;
+        for table in self.tables.values_mut() {
+            table.set_case_insensitive_names(enabled);
+        }
+        Ok(())
+    }
+
+    /// Returns the name of the table a caller-given name refers to, ignoring case if enabled.
+    /// Unknown names are returned as they are.
+    pub(crate) fn resolve_table_name<'a>(&'a self, table_name: &'a str) -> &'a str {
+        if !self.case_insensitive_names || self.tables.contains_key(table_name) {
+            return table_name;
+        }
+
+        let lowercase_name = table_name.to_lowercase();
+        self.tables
+            .keys()
+            .find(|name| name.to_lowercase() == lowercase_name)
+            .map_or(table_name, |name| name.as_str())
+    }
+
+    /// Like `resolve_table_name`, but owned, so public methods can resolve a name once and keep
+    /// it while they change the database.
+    pub(crate) fn resolve_table_name_owned(&self, table_name: &str) -> String {
+        self.resolve_table_name(table_name).to_owned()
+    }
+
+    /// Sets the longest table or column name, in characters, accepted by `create_table`, the
+    /// functions adding columns and the rename functions. Limits above `MAX_NAME_LENGTH` have no
+    /// effect.
+    pub fn set_max_name_length(&mut self, max_name_length: usize) {
+        self.max_name_length = max_name_length;
+    }
+
+    /// Checks a new table or column name against the naming rules and the configured limit.
+    pub(crate) fn validate_name(&self, name: &str) -> Result<(), Error> {
+        Ok(validate_name(name, self.max_name_length)?)
+    }
+
+    pub fn create_table(
+        &mut self,
+        table_name: &str,
+        mut columns: Vec<Column>,
+    ) -> Result<(), Error> {
+        self.validate_name(table_name)?;
+        for column in &columns {
+            self.validate_name(&column.name)?;
+        }
+
         // Check if a table with the same name already exists
-        if self.tables.contains_key(table_name) {
+        if self.tables.contains_key(self.resolve_table_name(table_name)) {
             return Err(Error::TableAlreadyExists(table_name.to_string()));
         }
 
+        let mut column_names = HashSet::new();
+        for column in &columns {
+            let name = if self.case_insensitive_names {
+                column.name.to_lowercase()
+            } else {
+                column.name.clone()
+            };
+            if !column_names.insert(name) {
+                return Err(table_errors::Error::ColumnAlreadyExists(column.name.clone()).into());
+            }
+        }
+        let column_names: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
+        for fk_info in columns.iter_mut().filter_map(|c| c.foreign_key.as_mut()) {
+            self.resolve_foreign_key_names(table_name, &column_names, fk_info);
+        }
+
         let mut primary_key_column: Option<Column> = None;
 
         // Validate that only one column is marked as the primary key
@@ -41,63 +218,224 @@ impl Database {
 
             // Validate foreign key references
             if let Some(fk_info) = &column.foreign_key {
-                // Check if the referenced table exists in the database
-                if !self.tables.contains_key(&fk_info.reference_table) {
-                    return Err(Error::ReferencedTableNotFound(
-                        fk_info.reference_table.clone(),
-                    ));
+                // A self-reference can't be checked against `self.tables` since the table
+                // doesn't exist yet, and it can't form a cycle with other tables
+                if fk_info.reference_table == table_name {
+                    Self::validate_referenced_column(column, fk_info, &columns)?;
+                    continue;
                 }
 
-                // Check if the referenced column exists in the referenced table
-                let referenced_table = self.tables.get(&fk_info.reference_table).unwrap(); // Safe to unwrap since we checked for the table's existence
-                if !referenced_table
-                    .columns
-                    .iter()
-                    .any(|col| col.name == fk_info.reference_column)
-                {
-                    return Err(Error::ReferencedColumnNotFound(
-                        fk_info.reference_table.clone(),
-                        fk_info.reference_column.clone(),
-                    ));
-                }
+                self.validate_foreign_key(column, fk_info)?;
 
-                // Check if the referenced column is the primary key column in the referenced table
-                if !referenced_table
-                    .columns
-                    .iter()
-                    .any(|col| col.is_primary_key && col.name == fk_info.reference_column)
+                // Reject references that would make the cascading updates/deletes loop forever
+                if let Some(cycle) =
+                    self.find_foreign_key_cycle(table_name, &fk_info.reference_table)
                 {
-                    return Err(Error::ReferencedColumnNotPrimaryKey(
-                        fk_info.reference_table.clone(),
-                        fk_info.reference_column.clone(),
-                    ));
+                    return Err(Error::CircularForeignKeyReference(cycle));
                 }
             }
         }
 
-        let table = Table::new(table_name, columns).unwrap();
+        let mut table = Table::new(table_name, columns)?;
+        table.case_insensitive_names = self.case_insensitive_names;
+        if self.wal_enabled() {
+            self.append_to_wal(WalRecord::create_table(&table.name, &table.columns))?;
+        }
+
+        let mut self_references = Vec::new();
 
         for (column, fk_info) in table
             .columns
             .iter()
             .filter_map(|col| col.foreign_key.as_ref().map(|fk| (col, fk)))
         {
-            if let Some(referenced_table) = self.tables.get_mut(&fk_info.reference_table) {
+            if fk_info.reference_table == table.name {
+                self_references.push((table.name.clone(), column.name.clone()));
+            } else if let Some(referenced_table) = self.tables.get_mut(&fk_info.reference_table) {
                 referenced_table
                     .referenced_as_foreign_key
                     .push((table.name.clone(), column.name.clone()));
             }
         }
-
+        table.referenced_as_foreign_key.extend(self_references);
         self.tables.insert(table.name.clone(), table);
         Ok(())
     }
 
+    /// Creates an empty table with the columns of another one: their names, data types, primary
+    /// key flag, defaults and constraints, so records can move between both tables with
+    /// `insert_many_into_table` and `append_table`. Foreign keys are only copied when
+    /// `with_foreign_keys` is set, and are then validated like in `create_table`; a
+    /// self-reference of the source table becomes a self-reference of the new one.
+    pub fn create_table_like(
+        &mut self,
+        new_name: &str,
+        source_table: &str,
+        with_foreign_keys: bool,
+    ) -> Result<(), Error> {
+        let source = self
+            .get_table(source_table)
+            .ok_or(Error::TableNotFound(source_table.to_owned()))?;
+
+        let columns = source
+            .columns
+            .iter()
+            .map(|column| {
+                let mut column = column.clone_without_data();
+                if !with_foreign_keys {
+                    column.foreign_key = None;
+                } else if let Some(fk_info) = &mut column.foreign_key {
+                    if fk_info.reference_table == source.name {
+                        fk_info.reference_table = new_name.to_owned();
+                    }
+                }
+                column
+            })
+            .collect();
+
+        self.create_table(new_name, columns)
+    }
+
+    /// Copies a table under a new name, with its records if `include_data` is set. The copy is
+    /// created like in `create_table_like` with foreign keys: they stay active and reference the
+    /// same tables, except self-references, which reference the copy. Nothing references the
+    /// copy itself. The records are then added like in `append_table`, so if any of them breaks
+    /// a foreign key, e.g. a record inserted with `insert_into_table_deferred`, the copy is
+    /// dropped again and the error is returned.
+    pub fn copy_table(
+        &mut self,
+        source_table: &str,
+        new_name: &str,
+        include_data: bool,
+    ) -> Result<(), Error> {
+        self.create_table_like(new_name, source_table, true)?;
+        if !include_data {
+            return Ok(());
+        }
+
+        let source_table = self.resolve_table_name_owned(source_table);
+        if let Err(error) = self.append_table(new_name, &source_table) {
+            self.drop_table(new_name, false)?;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Rewrites a foreign key of a column of `table_name` to the exact names of the table and
+    /// column it references, for names that only differ by case and only while names ignore
+    /// case. `own_column_names` are the columns of `table_name`, for self-references.
+    /// References to unknown tables or columns are left for the validation to report.
+    pub(crate) fn resolve_foreign_key_names(
+        &self,
+        table_name: &str,
+        own_column_names: &[String],
+        fk_info: &mut ForeignKeyInfo,
+    ) {
+        if !self.case_insensitive_names {
+            return;
+        }
+
+        let referenced_names: Vec<&String> =
+            if fk_info.reference_table.to_lowercase() == table_name.to_lowercase() {
+                fk_info.reference_table = table_name.to_owned();
+                own_column_names.iter().collect()
+            } else if let Some(table) = self.get_table(&fk_info.reference_table) {
+                fk_info.reference_table = table.name.clone();
+                table.columns.iter().map(|c| &c.name).collect()
+            } else {
+                return;
+            };
+
+        let lowercase_column = fk_info.reference_column.to_lowercase();
+        if let Some(name) = referenced_names
+            .into_iter()
+            .find(|name| name.to_lowercase() == lowercase_column)
+        {
+            fk_info.reference_column = name.clone();
+        }
+    }
+
+    /// Checks that a foreign key points at an existing primary key column of an existing table,
+    /// with the data type of the foreign key column.
+    pub(crate) fn validate_foreign_key(
+        &self,
+        column: &Column,
+        fk_info: &ForeignKeyInfo,
+    ) -> Result<(), Error> {
+        // Check if the referenced table exists in the database
+        let referenced_table = self.tables.get(&fk_info.reference_table).ok_or(
+            Error::ReferencedTableNotFound(fk_info.reference_table.clone()),
+        )?;
+
+        Self::validate_referenced_column(column, fk_info, &referenced_table.columns)
+    }
+
+    /// Checks that a foreign key points at one of the given columns, which must be the primary
+    /// key and have the data type of the foreign key column. The columns are those of the
+    /// referenced table, or of the table being created for a self-reference.
+    fn validate_referenced_column(
+        column: &Column,
+        fk_info: &ForeignKeyInfo,
+        referenced_columns: &[Column],
+    ) -> Result<(), Error> {
+        // Check if the referenced column exists in the referenced table
+        let referenced_column = referenced_columns
+            .iter()
+            .find(|col| col.name == fk_info.reference_column)
+            .ok_or(Error::ReferencedColumnNotFound(
+                fk_info.reference_table.clone(),
+                fk_info.reference_column.clone(),
+            ))?;
+
+        // Check if the referenced column is the primary key column in the referenced table
+        if !referenced_column.is_primary_key {
+            return Err(Error::ReferencedColumnNotPrimaryKey(
+                fk_info.reference_table.clone(),
+                fk_info.reference_column.clone(),
+            ));
+        }
+
+        // Values of different types are never equal, so no value could satisfy the reference
+        if referenced_column.data_type != column.data_type {
+            return Err(Error::ForeignKeyTypeMismatch(
+                column.name.clone(),
+                referenced_column.data_type,
+                column.data_type,
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn get_table(&self, table_name: &str) -> Option<&Table> {
-        self.tables.get(table_name)
+        self.tables.get(self.resolve_table_name(table_name))
+    }
+
+    /// Every table of the database, ordered by name.
+    pub fn tables(&self) -> impl Iterator<Item = &Table> {
+        let mut tables: Vec<&Table> = self.tables.values().collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        tables.into_iter()
+    }
+
+    /// Returns a copy of the database to read from while this one keeps changing, e.g. to run
+    /// the queries of a report against the same data. The snapshot is a deep copy of every table
+    /// and its indexes: taking one costs time and memory in proportion to the data, but reads
+    /// and writes on either side stay as fast as before and never affect the other. The
+    /// snapshot has no write-ahead log, so changes made to it are not logged.
+    pub fn snapshot(&self) -> Database {
+        Database {
+            name: self.name.clone(),
+            tables: self.tables.clone(),
+            deferred_foreign_key_tables: self.deferred_foreign_key_tables.clone(),
+            wal: None,
+            max_name_length: self.max_name_length,
+            case_insensitive_names: self.case_insensitive_names,
+        }
     }
 
     pub fn get_table_mut(&mut self, table_name: &str) -> Option<&mut Table> {
-        self.tables.get_mut(table_name)
+        let table_name = self.resolve_table_name(table_name).to_string();
+        self.tables.get_mut(&table_name)
     }
 }
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::database::db_errors::Error;
+use crate::database::Database;
+use crate::table::schema::{SchemaDiff, TableSchema};
+
+impl Database {
+    /// Returns the schema of a single table, as printed by its `describe`.
+    pub fn schema_of(&self, table_name: &str) -> Result<TableSchema, Error> {
+        self.get_table(table_name)
+            .map(|table| table.schema())
+            .ok_or(Error::TableNotFound(table_name.to_owned()))
+    }
+
+    /// Returns the schema of every table, keyed by table name.
+    pub fn schemas(&self) -> HashMap<String, TableSchema> {
+        self.tables
+            .iter()
+            .map(|(table_name, table)| (table_name.clone(), table.schema()))
+            .collect()
+    }
+
+    /// Returns the `CREATE TABLE` statements of every table, see `Table::to_ddl`, one per line.
+    /// Tables come after the tables they reference, see `tables_in_dependency_order`, or in
+    /// name order if their foreign keys form a cycle.
+    pub fn to_ddl(&self) -> String {
+        let tables = self.tables_in_dependency_order().unwrap_or_else(|_| {
+            let table_names = self.table_names();
+            table_names.iter().map(|table_name| &self.tables[table_name]).collect()
+        });
+        tables
+            .iter()
+            .map(|table| table.to_ddl())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Compares the schema of every table with the table of the same name in another database,
+    /// see `TableSchema::diff`, and returns the tables that differ in name order. A table only
+    /// in one of the databases is compared with an empty schema, so all its columns show up as
+    /// added or removed.
+    pub fn diff_schemas(&self, other: &Database) -> Vec<(String, SchemaDiff)> {
+        let mut table_names = self.table_names();
+        table_names.extend(other.table_names());
+        table_names.sort();
+        table_names.dedup();
+
+        let schema = |db: &Database, table_name: &String| {
+            db.tables
+                .get(table_name)
+                .map(|table| table.schema())
+                .unwrap_or(TableSchema {
+                    name: table_name.clone(),
+                    columns: Vec::new(),
+                })
+        };
+
+        table_names
+            .into_iter()
+            .filter_map(|table_name| {
+                let diff = schema(self, &table_name).diff(&schema(other, &table_name));
+                (!diff.is_empty()).then_some((table_name, diff))
+            })
+            .collect()
+    }
+
+    /// Whether the database has a table with this name, ignoring case if enabled.
+    pub fn table_exists(&self, table_name: &str) -> bool {
+        self.get_table(table_name).is_some()
+    }
+
+    /// Returns the number of tables in the database.
+    pub fn table_count(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// Returns the summary printed by `describe`: the name of the database, then every table in
+    /// name order with its row and column counts.
+    pub fn render_description(&self) -> String {
+        let rows: Vec<[String; 3]> = self
+            .tables()
+            .map(|table| {
+                [
+                    table.name.clone(),
+                    table.row_count().to_string(),
+                    table.columns.len().to_string(),
+                ]
+            })
+            .collect();
+        let headers = ["table", "rows", "columns"];
+        let widths: Vec<usize> = (0..headers.len())
+            .map(|idx| {
+                rows.iter()
+                    .map(|row| row[idx].len())
+                    .chain([headers[idx].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut description = format!("Database: {}\n\n", self.name);
+        for row in std::iter::once(headers.map(String::from)).chain(rows) {
+            let _ = writeln!(
+                description,
+                "{:<name_width$} {:>rows_width$} {:>columns_width$}",
+                row[0],
+                row[1],
+                row[2],
+                name_width = widths[0],
+                rows_width = widths[1],
+                columns_width = widths[2],
+            );
+        }
+        description
+    }
+
+    /// Prints every table with its row and column counts, see `render_description`.
+    pub fn describe(&self) {
+        print!("{}", self.render_description());
+    }
+
+    /// Returns the name of every table, sorted by name.
+    pub fn table_names(&self) -> Vec<String> {
+        let mut table_names: Vec<String> = self.tables.keys().cloned().collect();
+        table_names.sort();
+        table_names
+    }
+
+    /// Returns every table name with the number of records in the table, sorted by name.
+    pub fn table_stats(&self) -> Vec<(String, usize)> {
+        self.table_names()
+            .into_iter()
+            .map(|table_name| {
+                let row_count = self.tables[&table_name].row_count();
+                (table_name, row_count)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+
+use crate::column::Value;
+use crate::database::db_errors::Error;
+use crate::database::{Database, QueryResult};
+use crate::table::condition_parser::ConditionParser;
+use crate::table::helpers::{satisfies_condition, validate_condition_value};
+use crate::table::operators::Operator;
+use crate::table::{table_errors, NestedCondition, Row, Table};
+
+impl Database {
+    pub fn row_count_of_table(&self, table_name: &str) -> Result<usize, Error> {
+        let table = self
+            .get_table(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        Ok(table.row_count())
+    }
+
+    pub fn count_in_table(
+        &self,
+        table_name: &str,
+        nested_condition: &NestedCondition,
+    ) -> Result<usize, Error> {
+        let table = self
+            .get_table(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        Ok(table.count_where(nested_condition)?)
+    }
+
+    pub fn exists_in_table(
+        &self,
+        table_name: &str,
+        nested_condition: &NestedCondition,
+    ) -> Result<bool, Error> {
+        let table = self
+            .get_table(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        Ok(table.exists(nested_condition)?)
+    }
+
+    pub fn value_counts_in_table(
+        &self,
+        table_name: &str,
+        column_name: &str,
+    ) -> Result<Vec<(Value, usize)>, Error> {
+        let table = self
+            .get_table(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        Ok(table.value_counts(column_name)?)
+    }
+
+    /// Returns the requested columns of the records in a table that satisfy an optional condition.
+    /// An empty column list returns every column.
+    pub fn select_from_table(
+        &self,
+        table_name: &str,
+        columns: Vec<String>,
+        nested_condition: Option<NestedCondition>,
+    ) -> Result<Vec<Vec<Value>>, Error> {
+        let table = self
+            .get_table(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        Ok(table.select(columns, nested_condition)?)
+    }
+
+    /// Like `select_from_table`, but returns the records as rows with lookup by column name.
+    pub fn select_rows_from_table(
+        &self,
+        table_name: &str,
+        columns: Vec<String>,
+        nested_condition: Option<NestedCondition>,
+    ) -> Result<Vec<Row>, Error> {
+        let table = self
+            .get_table(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        Ok(table.select_rows(columns, nested_condition)?)
+    }
+
+    /// Returns the first `n` records of a table when sorted by the given column.
+    pub fn top_n_in_table(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        n: usize,
+        descending: bool,
+    ) -> Result<Table, Error> {
+        let table = self
+            .get_table(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        Ok(table.top_n(column_name, n, descending)?)
+    }
+
+    /// Runs a `SELECT` statement against a single table and returns the selected values of the
+    /// matching records, e.g. `SELECT user_name, age FROM users WHERE age > 25`. The select list
+    /// is either `*` or column names, and the optional `WHERE` clause takes the syntax of
+    /// `NestedCondition::parse`. Keywords ignore case and a trailing `;` is allowed.
+    pub fn execute_select(&self, sql: &str) -> Result<Vec<Vec<Value>>, Error> {
+        let (table_name, columns, nested_condition) =
+            parse_select(sql).map_err(|err| match err {
+                table_errors::Error::InvalidConditionSyntax(position, reason) => {
+                    Error::InvalidStatementSyntax(position, reason)
+                }
+                err => err.into(),
+            })?;
+
+        let table = self
+            .get_table(&table_name)
+            .ok_or(Error::TableNotFound(table_name))?;
+
+        Ok(table.select(columns, nested_condition)?)
+    }
+
+    /// Joins two tables on equal values of a column of each, like an SQL inner join, and returns
+    /// the requested columns of the joined records that satisfy an optional condition. `on`
+    /// names the left and right join columns; NULL values and values of different data types
+    /// never match. The condition is checked on each pair of records as they are joined, so
+    /// only the selected records are ever copied.
+    ///
+    /// Columns in the condition and in `columns` are named as `table.column`, or just `column`
+    /// when only one of the tables has it. When a table is joined with itself, e.g. employees
+    /// with their managers, the sides are named `left` and `right` instead of by table. An
+    /// empty `columns` selects every column of the left table and then of the right one, the
+    /// shared names qualified the same way.
+    pub fn query(
+        &self,
+        left_table: &str,
+        right_table: &str,
+        on: (String, String),
+        condition: Option<NestedCondition>,
+        columns: Vec<String>,
+    ) -> Result<QueryResult, Error> {
+        let tables = [
+            self.get_table(left_table)
+                .ok_or(Error::TableNotFound(left_table.to_owned()))?,
+            self.get_table(right_table)
+                .ok_or(Error::TableNotFound(right_table.to_owned()))?,
+        ];
+        let left_join_idx = tables[0].column_position(&on.0)?;
+        let right_join_idx = tables[1].column_position(&on.1)?;
+        let condition = condition
+            .map(|condition| JoinCondition::resolve(&condition, &tables))
+            .transpose()?;
+
+        let (column_names, positions) = if columns.is_empty() {
+            let mut column_names = Vec::new();
+            let mut positions = Vec::new();
+            let side_names = join_side_names(&tables);
+            for (side, table) in tables.iter().enumerate() {
+                for (column_idx, column) in table.columns.iter().enumerate() {
+                    let shared = tables[1 - side].columns.iter().any(|c| c.name == column.name);
+                    column_names.push(if shared {
+                        format!("{}.{}", side_names[side], column.name)
+                    } else {
+                        column.name.clone()
+                    });
+                    positions.push((side, column_idx));
+                }
+            }
+            (column_names, positions)
+        } else {
+            let positions = columns
+                .iter()
+                .map(|column_name| resolve_join_column(column_name, &tables))
+                .collect::<Result<Vec<_>, _>>()?;
+            (columns, positions)
+        };
+
+        let mut right_rows: HashMap<&Value, Vec<usize>> = HashMap::new();
+        for (row_idx, value) in tables[1].columns[right_join_idx].data.iter().enumerate() {
+            if *value != Value::Null {
+                right_rows.entry(value).or_default().push(row_idx);
+            }
+        }
+
+        let mut rows = Vec::new();
+        for (left_row, value) in tables[0].columns[left_join_idx].data.iter().enumerate() {
+            let Some(right_matches) = right_rows.get(value) else {
+                continue;
+            };
+            for &right_row in right_matches {
+                let row_indices = [left_row, right_row];
+                if condition
+                    .as_ref()
+                    .is_some_and(|condition| !condition.evaluate(&tables, row_indices))
+                {
+                    continue;
+                }
+                rows.push(
+                    positions
+                        .iter()
+                        .map(|&(side, column_idx)| {
+                            tables[side].columns[column_idx].data[row_indices[side]].clone()
+                        })
+                        .collect(),
+                );
+            }
+        }
+
+        Ok(QueryResult {
+            columns: column_names,
+            rows,
+        })
+    }
+}
+
+/// A condition of `Database::query` with every column resolved to a side of the join (0 for
+/// the left table) and a position in that table, to be checked on pairs of joined records.
+enum JoinCondition {
+    Condition(usize, usize, Operator, String), // side, column index, operator, value
+    And(Box<JoinCondition>, Box<JoinCondition>),
+    Or(Box<JoinCondition>, Box<JoinCondition>),
+    Not(Box<JoinCondition>),
+}
+
+impl JoinCondition {
+    fn resolve(condition: &NestedCondition, tables: &[&Table; 2]) -> Result<Self, Error> {
+        let resolve = |inner: &NestedCondition| -> Result<Box<Self>, Error> {
+            Ok(Box::new(Self::resolve(inner, tables)?))
+        };
+
+        Ok(match condition {
+            NestedCondition::Condition(column_name, operator, value) => {
+                let (side, column_idx) = resolve_join_column(column_name, tables)?;
+                let operator = Operator::from_str(operator)
+                    .map_err(|_| table_errors::Error::InvalidOperator(operator.clone()))?;
+                let data_type = tables[side].columns[column_idx].data_type;
+                validate_condition_value(column_name, data_type, &operator, value)?;
+                JoinCondition::Condition(side, column_idx, operator, value.clone())
+            }
+            NestedCondition::And(left, right) => {
+                JoinCondition::And(resolve(left)?, resolve(right)?)
+            }
+            NestedCondition::Or(left, right) => {
+                JoinCondition::Or(resolve(left)?, resolve(right)?)
+            }
+            NestedCondition::Not(inner) => JoinCondition::Not(resolve(inner)?),
+        })
+    }
+
+    fn evaluate(&self, tables: &[&Table; 2], row_indices: [usize; 2]) -> bool {
+        match self {
+            JoinCondition::Condition(side, column_idx, operator, value) => {
+                let column = &tables[*side].columns[*column_idx];
+                let row_value = &column.data[row_indices[*side]];
+                satisfies_condition(row_value, column.data_type, value, operator)
+            }
+            JoinCondition::And(left, right) => {
+                left.evaluate(tables, row_indices) && right.evaluate(tables, row_indices)
+            }
+            JoinCondition::Or(left, right) => {
+                left.evaluate(tables, row_indices) || right.evaluate(tables, row_indices)
+            }
+            JoinCondition::Not(inner) => !inner.evaluate(tables, row_indices),
+        }
+    }
+}
+
+/// The names qualifying the columns of each side of a join in `Database::query`: the table
+/// names, or `left` and `right` when a table is joined with itself.
+fn join_side_names<'a>(tables: &[&'a Table; 2]) -> [&'a str; 2] {
+    if tables[0].name == tables[1].name {
+        ["left", "right"]
+    } else {
+        [&tables[0].name, &tables[1].name]
+    }
+}
+
+/// Finds the side of the join and the position of a column named in `Database::query`, either
+/// qualified as `side.column` (see `join_side_names`) or as `column` if only one of the sides
+/// has it.
+fn resolve_join_column(column_name: &str, tables: &[&Table; 2]) -> Result<(usize, usize), Error> {
+    let side_names = join_side_names(tables);
+    let find = |side_name: Option<&str>, name: &str| -> Vec<(usize, usize)> {
+        tables
+            .iter()
+            .enumerate()
+            .filter(|&(side, _)| side_name.is_none_or(|side_name| side_names[side] == side_name))
+            .filter_map(|(side, table)| Some((side, table.column_position(name).ok()?)))
+            .collect()
+    };
+
+    let mut matches = match column_name.split_once('.') {
+        Some((side_name, name)) => find(Some(side_name), name),
+        None => Vec::new(),
+    };
+    // A name with a dot may also be a plain column name
+    if matches.is_empty() {
+        matches = find(None, column_name);
+    }
+
+    match matches[..] {
+        [position] => Ok(position),
+        [] => Err(table_errors::Error::NonExistingColumn(column_name.to_owned()).into()),
+        _ => Err(Error::AmbiguousJoinColumn(
+            column_name.to_owned(),
+            side_names[0].to_owned(),
+            side_names[1].to_owned(),
+        )),
+    }
+}
+
+/// Splits a `SELECT` statement into the table name, the selected columns (empty for `*`) and the
+/// `WHERE` condition.
+fn parse_select(
+    sql: &str,
+) -> Result<(String, Vec<String>, Option<NestedCondition>), table_errors::Error> {
+    let mut parser = ConditionParser::new(sql);
+    if !parser.eat_keyword("SELECT") {
+        return Err(parser.error("expected SELECT"));
+    }
+
+    let mut columns = Vec::new();
+    if !parser.eat_symbol('*') {
+        loop {
+            columns.push(parser.parse_identifier("column name or *")?);
+            if !parser.eat_symbol(',') {
+                break;
+            }
+        }
+    }
+
+    if !parser.eat_keyword("FROM") {
+        return Err(parser.error("expected FROM"));
+    }
+    let table_name = parser.parse_identifier("table name")?;
+
+    let nested_condition = if parser.eat_keyword("WHERE") {
+        Some(parser.parse_or()?)
+    } else {
+        None
+    };
+
+    parser.eat_symbol(';');
+    if !parser.at_end() {
+        return Err(parser.error(if nested_condition.is_some() {
+            "expected AND, OR or the end of the statement"
+        } else {
+            "expected WHERE or the end of the statement"
+        }));
+    }
+
+    Ok((table_name, columns, nested_condition))
+}
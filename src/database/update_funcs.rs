@@ -1,7 +1,10 @@
-use crate::column::{ColumnDataType, Value};
-use crate::database::Database;
+use crate::column::{Column, Value};
+use crate::database::{AffectedRows, Database, UpdateFromOutcome};
+use crate::database::wal::WalRecord;
 use crate::database::db_errors::Error;
-use crate::table::{NestedCondition, table_errors};
+use crate::table::helpers::parse_column_value_or_null;
+use crate::table::NestedCondition;
+use std::collections::HashMap;
 
 impl Database {
     pub fn update_column_in_table(
@@ -10,199 +13,407 @@ impl Database {
         column_name: &str,
         new_value: &str,
     ) -> Result<(), Error> {
-        let copied_tables = self.tables.clone();
-
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
         let table = self
             .tables
-            .get_mut(table_name)
+            .get(table_name)
             .ok_or(Error::TableNotFound(table_name.to_owned()))?;
 
         // Check if the column is a foreign key column
-        let column =
-            table
-                .columns
-                .iter()
-                .find(|c| c.name == column_name)
-                .ok_or(Error::TableError(table_errors::Error::NonExistingColumn(
-                    column_name.to_string(),
-                )))?;
-
-        if let Some(fk_info) = &column.foreign_key {
-            let referenced_table = copied_tables.get(&fk_info.reference_table).cloned().ok_or(
-                Error::ReferencedTableNotFound(fk_info.reference_table.clone()),
-            )?;
-
-            let referenced_column = referenced_table
-                .columns
-                .iter()
-                .find(|c| c.name == fk_info.reference_column)
-                .ok_or(Error::ReferencedColumnNotFound(
-                    fk_info.reference_table.clone(),
-                    fk_info.reference_column.clone(),
-                ))?;
-
-            let value = if new_value.trim().to_lowercase() == "null" {
-                Value::Null
-            } else {
-                match column.data_type {
-                    ColumnDataType::Integer => new_value
-                        .parse::<i64>()
-                        .map(Value::Integer)
-                        .map_err(|_| Error::ParseError(0, new_value.to_string()))?,
-                    ColumnDataType::Float => new_value
-                        .parse::<f64>()
-                        .map(Value::Float)
-                        .map_err(|_| Error::ParseError(0, new_value.to_string()))?,
-                    ColumnDataType::Text => Value::Text(new_value.to_string()),
+        let column = &table.columns[table.column_position(column_name)?];
+        self.check_foreign_key_value(table_name, column, new_value)?;
+
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::update_column(table_name, column_name, new_value));
+
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            Ok(table.update_column(column_name, new_value)?)
+        })
+    }
+
+    pub fn update_with_nested_conditions_in_table(
+        &mut self,
+        table_name: &str,
+        update_input: (String, String),
+        nested_condition: NestedCondition,
+    ) -> Result<AffectedRows, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::update(table_name, &update_input, &nested_condition));
+
+        // Only the top-level update is logged, replaying it redoes the cascade
+        self.logged(wal_record, |db| {
+            db.update_with_nested_conditions_cascading(table_name, update_input, nested_condition)
+        })
+    }
+
+    /// Updates the row with the given primary key value, checking the foreign keys of the
+    /// assigned columns. If the primary key itself changes, the rows referencing the old value
+    /// are updated to the new one.
+    pub fn update_by_pk_in_table(
+        &mut self,
+        table_name: &str,
+        key: &str,
+        assignments: Vec<(String, String)>,
+    ) -> Result<AffectedRows, Error> {
+        let table_name: &str = &self.resolve_table_name_owned(table_name);
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+        let primary_key_idx = table
+            .columns
+            .iter()
+            .position(|c| c.is_primary_key)
+            .ok_or(Error::NoPrimaryKeyColumn(table_name.to_owned()))?;
+
+        let old_primary_key_value = match table.primary_key_row(key)? {
+            Some(row_idx) => {
+                for (column_name, new_value) in &assignments {
+                    if let Ok(column_idx) = table.column_position(column_name) {
+                        let column = &table.columns[column_idx];
+                        self.check_foreign_key_value(table_name, column, new_value)?;
+                    }
                 }
+                Some((row_idx, table.columns[primary_key_idx].data[row_idx].clone()))
+            }
+            None => None,
+        };
+
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::update_by_pk(table_name, key, &assignments));
+
+        // Only the top-level update is logged, replaying it redoes the cascade
+        self.logged(wal_record, |db| {
+            let table = db
+                .tables
+                .get_mut(table_name)
+                .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+            let mut affected_rows = AffectedRows {
+                rows: usize::from(table.update_by_pk(key, assignments)?),
+                cascaded: 0,
             };
 
-            if value == Value::Null {
-                return Err(Error::NullForeignKey(column.name.clone()));
+            // Only the updated row can have changed its primary key
+            if let Some((row_idx, old_primary_key_value)) = old_primary_key_value {
+                let new_primary_key_value = table.columns[primary_key_idx].data[row_idx].clone();
+                if new_primary_key_value != old_primary_key_value {
+                    affected_rows.cascaded += db.cascade_primary_key_changes(
+                        table_name,
+                        &[(old_primary_key_value, new_primary_key_value)],
+                    )?;
+                }
             }
 
-            if !referenced_column.data.contains(&value) {
-                return Err(Error::ForeignKeyViolation(
-                    value.to_string(),
-                    column.name.clone(),
-                    fk_info.reference_table.clone(),
+            Ok(affected_rows)
+        })
+    }
+
+    /// Sets columns of `target_table` from the matching rows of `source_table`, e.g. the city of
+    /// every address from a staging table. `join_on` names the target and source columns whose
+    /// values must be equal, and every assignment a `(target_column, source_column)` pair of
+    /// the same data type. A target row takes the values of the first matching source row;
+    /// rows without a match (including NULL join values) are left untouched.
+    ///
+    /// The new values are checked against the foreign keys of the target columns, and a
+    /// primary key update is rejected if it creates duplicates, and cascaded to the referencing
+    /// rows otherwise. Nothing is changed if a check fails.
+    pub fn update_from(
+        &mut self,
+        target_table: &str,
+        source_table: &str,
+        join_on: (String, String),
+        assignments: Vec<(String, String)>,
+    ) -> Result<UpdateFromOutcome, Error> {
+        let target_table: &str = &self.resolve_table_name_owned(target_table);
+        let source_table: &str = &self.resolve_table_name_owned(source_table);
+        let target = self
+            .tables
+            .get(target_table)
+            .ok_or(Error::TableNotFound(target_table.to_owned()))?;
+        let source = self
+            .tables
+            .get(source_table)
+            .ok_or(Error::TableNotFound(source_table.to_owned()))?;
+
+        let target_join_idx = target.column_position(&join_on.0)?;
+        let source_join_idx = source.column_position(&join_on.1)?;
+        let mut column_pairs = Vec::with_capacity(assignments.len());
+        for (target_column, source_column) in &assignments {
+            let target_idx = target.column_position(target_column)?;
+            let source_idx = source.column_position(source_column)?;
+            if target.columns[target_idx].data_type != source.columns[source_idx].data_type {
+                return Err(Error::MismatchedColumnTypes(
+                    target_column.clone(),
+                    source_column.clone(),
                 ));
             }
+            column_pairs.push((target_idx, source_idx));
         }
 
-        table.update_column(column_name, new_value)?;
+        // First source row for every join value
+        let mut source_rows: HashMap<&Value, usize> = HashMap::new();
+        for (row_idx, value) in source.columns[source_join_idx].data.iter().enumerate() {
+            if *value != Value::Null {
+                source_rows.entry(value).or_insert(row_idx);
+            }
+        }
 
-        Ok(())
+        let mut matched_rows = Vec::new();
+        for (row_idx, value) in target.columns[target_join_idx].data.iter().enumerate() {
+            if let Some(&source_row_idx) = source_rows.get(value) {
+                matched_rows.push((row_idx, source_row_idx));
+            }
+        }
+        let unmatched = target.row_count() - matched_rows.len();
+
+        let mut column_updates = Vec::with_capacity(column_pairs.len());
+        for (target_idx, source_idx) in column_pairs {
+            let column = &target.columns[target_idx];
+            let mut updates = Vec::with_capacity(matched_rows.len());
+            for &(row_idx, source_row_idx) in &matched_rows {
+                let value = source.columns[source_idx].data[source_row_idx].clone();
+                self.check_foreign_key_reference(target_table, column, &value)?;
+                updates.push((row_idx, value));
+            }
+            column_updates.push((target_idx, updates));
+        }
+        // Every assignment is checked before any is applied, so none fails halfway
+        for (target_idx, updates) in &column_updates {
+            target.check_assigned_values(*target_idx, updates)?;
+        }
+
+        let primary_key_idx = target.columns.iter().position(|c| c.is_primary_key);
+        let old_primary_key_values: Vec<Value> = match primary_key_idx {
+            Some(primary_key_idx) => matched_rows
+                .iter()
+                .map(|&(row_idx, _)| target.columns[primary_key_idx].data[row_idx].clone())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let wal_record = self
+            .wal_enabled()
+            .then(|| WalRecord::update_from(target_table, source_table, &join_on, &assignments));
+
+        self.logged(wal_record, |db| {
+            let target = db
+                .tables
+                .get_mut(target_table)
+                .ok_or(Error::TableNotFound(target_table.to_owned()))?;
+            for (target_idx, updates) in column_updates {
+                target.assign_values(target_idx, updates)?;
+            }
+
+            let mut outcome = UpdateFromOutcome {
+                affected_rows: AffectedRows {
+                    rows: matched_rows.len(),
+                    cascaded: 0,
+                },
+                unmatched,
+            };
+
+            if let Some(primary_key_idx) = primary_key_idx {
+                let changed_primary_keys: Vec<(Value, Value)> = matched_rows
+                    .iter()
+                    .zip(old_primary_key_values)
+                    .map(|(&(row_idx, _), old_value)| {
+                        (old_value, target.columns[primary_key_idx].data[row_idx].clone())
+                    })
+                    .filter(|(old_value, new_value)| old_value != new_value)
+                    .collect();
+                outcome.affected_rows.cascaded +=
+                    db.cascade_primary_key_changes(target_table, &changed_primary_keys)?;
+            }
+
+            Ok(outcome)
+        })
     }
 
-    pub fn update_with_nested_conditions_in_table(
+    pub(crate) fn update_with_nested_conditions_cascading(
         &mut self,
         table_name: &str,
         update_input: (String, String),
         nested_condition: NestedCondition,
-    ) -> Result<(), Error> {
-        let copied_tables = self.tables.clone();
+    ) -> Result<AffectedRows, Error> {
+        let table = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?;
+
+        let update_idx = table.column_position(&update_input.0)?;
+        let update_column = &table.columns[update_idx];
+
+        let is_primary_key_column = update_column.is_primary_key;
+
+        // Keep the primary key values to find the ones the update changes
+        let old_primary_key_values: Vec<Value> = if is_primary_key_column {
+            update_column.data.clone()
+        } else {
+            Vec::new()
+        };
+
+        self.check_foreign_key_value(table_name, update_column, &update_input.1)?;
 
         let table = self
             .tables
             .get_mut(table_name)
             .ok_or(Error::TableNotFound(table_name.to_owned()))?;
 
-        let update_column = table
-            .columns
-            .iter()
-            .find(|c| c.name == update_input.0)
-            .ok_or(Error::TableError(table_errors::Error::NonExistingColumn(
-                update_input.0.clone(),
-            )))?;
+        let mut affected_rows = AffectedRows {
+            rows: table.update_with_nested_conditions(update_input.clone(), nested_condition)?,
+            cascaded: 0,
+        };
 
-        let is_primary_key_column = update_column.is_primary_key;
+        // Compare the keys row by row, so nothing cascades when no row matched or a key was set
+        // to the value it already had
+        let changed_primary_keys: Vec<(Value, Value)> = if is_primary_key_column {
+            old_primary_key_values
+                .into_iter()
+                .zip(table.columns[update_idx].data.iter())
+                .filter(|(old_value, new_value)| old_value != *new_value)
+                .map(|(old_value, new_value)| (old_value, new_value.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
 
-        let mut old_primary_key_values: Vec<Value> = Vec::new();
+        affected_rows.cascaded +=
+            self.cascade_primary_key_changes(table_name, &changed_primary_keys)?;
 
-        if is_primary_key_column {
-            for pk_value in &update_column.data {
-                old_primary_key_values.push(pk_value.clone());
-            }
+        Ok(affected_rows)
+    }
+
+    /// Re-points the rows referencing changed primary key values of a table, given as
+    /// `(old_value, new_value)` pairs, and returns the number of updated rows including further
+    /// cascades. The referencing rows of every pair are found before any of them changes, so
+    /// chained changes like 1 -> 2 and 2 -> 3 move every row exactly once.
+    pub(crate) fn cascade_primary_key_changes(
+        &mut self,
+        table_name: &str,
+        changed_primary_keys: &[(Value, Value)],
+    ) -> Result<usize, Error> {
+        if changed_primary_keys.is_empty() {
+            return Ok(0);
         }
 
-        //dbg!(&old_primary_key_values);
+        let new_values: HashMap<&Value, &Value> = changed_primary_keys
+            .iter()
+            .map(|(old_value, new_value)| (old_value, new_value))
+            .collect();
+        let references = self
+            .tables
+            .get(table_name)
+            .ok_or(Error::TableNotFound(table_name.to_owned()))?
+            .referenced_as_foreign_key
+            .clone();
 
-        if let Some(fk_info) = &update_column.foreign_key {
-            let referenced_table = copied_tables.get(&fk_info.reference_table).cloned().ok_or(
-                Error::ReferencedTableNotFound(fk_info.reference_table.clone()),
-            )?;
+        let mut cascaded = 0;
+        for (ref_table_name, ref_column_name) in references {
+            let ref_table = self
+                .tables
+                .get(&ref_table_name)
+                .ok_or(Error::TableNotFound(ref_table_name.clone()))?;
+            let column_idx = ref_table.column_position(&ref_column_name)?;
+            let column = &ref_table.columns[column_idx];
 
-            let referenced_column = referenced_table
-                .columns
+            let updates: Vec<(usize, Value)> = column
+                .data
                 .iter()
-                .find(|c| c.name == fk_info.reference_column)
-                .ok_or(Error::ReferencedColumnNotFound(
-                    fk_info.reference_table.clone(),
-                    fk_info.reference_column.clone(),
-                ))?;
-
-            let new_value = if update_input.1.trim().to_lowercase() == "null" {
-                Value::Null
+                .enumerate()
+                .filter_map(|(row_idx, value)| {
+                    new_values
+                        .get(value)
+                        .map(|&new_value| (row_idx, new_value.clone()))
+                })
+                .collect();
+            if updates.is_empty() {
+                continue;
+            }
+
+            // A referencing column that is itself a primary key passes the change on
+            let nested_changes: Vec<(Value, Value)> = if column.is_primary_key {
+                updates
+                    .iter()
+                    .map(|(row_idx, new_value)| (column.data[*row_idx].clone(), new_value.clone()))
+                    .collect()
             } else {
-                match update_column.data_type {
-                    ColumnDataType::Integer => update_input
-                        .1
-                        .parse::<i64>()
-                        .map(Value::Integer)
-                        .map_err(|_| Error::ParseError(1, update_input.1.clone()))?,
-                    ColumnDataType::Float => update_input
-                        .1
-                        .parse::<f64>()
-                        .map(Value::Float)
-                        .map_err(|_| Error::ParseError(1, update_input.1.clone()))?,
-                    ColumnDataType::Text => Value::Text(update_input.1.clone()),
-                }
+                Vec::new()
             };
 
-            if new_value == Value::Null {
-                return Err(Error::NullForeignKey(update_column.name.clone()));
-            }
-
-            if !referenced_column.data.contains(&new_value) {
-                return Err(Error::ForeignKeyViolation(
-                    new_value.to_string(),
-                    update_column.name.clone(),
-                    fk_info.reference_table.clone(),
-                ));
-            }
+            cascaded += self
+                .tables
+                .get_mut(&ref_table_name)
+                .ok_or(Error::TableNotFound(ref_table_name.clone()))?
+                .assign_values(column_idx, updates)?;
+            cascaded += self.cascade_primary_key_changes(&ref_table_name, &nested_changes)?;
         }
 
-        let table_foreign_key_data = table.referenced_as_foreign_key.clone();
+        Ok(cascaded)
+    }
 
-        table.update_with_nested_conditions(update_input.clone(), nested_condition)?;
+    /// Checks that a new value of a column, given as a string, is allowed by the column's
+    /// foreign key, if it has one. NULL is only allowed for self-references.
+    pub(crate) fn check_foreign_key_value(
+        &self,
+        table_name: &str,
+        column: &Column,
+        new_value: &str,
+    ) -> Result<(), Error> {
+        if column.foreign_key.is_none() {
+            return Ok(());
+        }
 
-        let mut new_primary_key_values: Vec<Value> = Vec::new();
+        let value = parse_column_value_or_null(column, new_value.to_string())?;
+        self.check_foreign_key_reference(table_name, column, &value)
+    }
 
-        if is_primary_key_column {
-            for pk_value in &table
-                .columns
-                .iter()
-                .find(|c| c.name == update_input.0)
-                .unwrap()
-                .data
-            {
-                new_primary_key_values.push(pk_value.clone());
-            }
-        }
-        //dbg!(&new_primary_key_values);
+    /// Checks that a parsed value of a column is allowed by the column's foreign key, if it
+    /// has one.
+    pub(crate) fn check_foreign_key_reference(
+        &self,
+        table_name: &str,
+        column: &Column,
+        value: &Value,
+    ) -> Result<(), Error> {
+        let Some(fk_info) = &column.foreign_key else {
+            return Ok(());
+        };
 
-        let old_pk_value = old_primary_key_values
-            .iter()
-            .find(|value| !new_primary_key_values.contains(value))
-            .cloned();
+        let referenced_table = self
+            .tables
+            .get(&fk_info.reference_table)
+            .ok_or(Error::ReferencedTableNotFound(fk_info.reference_table.clone()))?;
 
-        let new_pk_value = new_primary_key_values
+        let referenced_column = referenced_table
+            .columns
             .iter()
-            .find(|value| !old_primary_key_values.contains(value))
-            .cloned();
-
-        //dbg!(&old_pk_value);
-        //dbg!(&new_pk_value);
-
-        if is_primary_key_column {
-            for (ref_table_name, ref_column_name) in table_foreign_key_data {
-                let condition = NestedCondition::Condition(
-                    ref_column_name.clone(),
-                    "=".to_string(),
-                    old_pk_value.clone().unwrap().to_string(),
-                );
-                self.update_with_nested_conditions_in_table(
-                    &ref_table_name,
-                    (
-                        ref_column_name.clone(),
-                        new_pk_value.clone().unwrap().to_string(),
-                    ),
-                    condition,
-                )?;
+            .find(|c| c.name == fk_info.reference_column)
+            .ok_or(Error::ReferencedColumnNotFound(
+                fk_info.reference_table.clone(),
+                fk_info.reference_column.clone(),
+            ))?;
+
+        if *value == Value::Null {
+            // A NULL self-reference marks a root row, e.g. an employee without a manager
+            if fk_info.reference_table != table_name {
+                return Err(Error::NullForeignKey(column.name.clone()));
             }
+        } else if !referenced_column.data.contains(value) {
+            return Err(Error::ForeignKeyViolation(
+                value.to_string(),
+                column.name.clone(),
+                fk_info.reference_table.clone(),
+            ));
         }
 
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,1053 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+
+use crate::column::{Column, ColumnDataType, ForeignKeyInfo, Value};
+use crate::database::db_errors::Error;
+use crate::database::Database;
+use crate::table::NestedCondition;
+
+/// A single operation recorded in the write-ahead log.
+///
+/// The log is line-delimited JSON, one record per line, e.g.
+/// `{"op":"insert","table":"users","values":["1","Alice"]}`. Values are stored as the strings
+/// the operation was called with, so replaying a record goes through the same parsing and
+/// constraint checks as the original call.
+pub(crate) struct WalRecord(Json);
+
+impl WalRecord {
+    fn new(op: &str, table_name: &str, fields: Vec<(&str, Json)>) -> Self {
+        let mut entries = vec![
+            ("op".to_string(), Json::String(op.to_string())),
+            ("table".to_string(), Json::String(table_name.to_string())),
+        ];
+        entries.extend(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value)),
+        );
+        WalRecord(Json::Object(entries))
+    }
+
+    /// The first record of a new log, naming the database it belongs to.
+    pub(crate) fn database(name: &str) -> Self {
+        WalRecord(Json::Object(vec![
+            ("op".to_string(), Json::String("database".to_string())),
+            ("name".to_string(), Json::String(name.to_string())),
+        ]))
+    }
+
+    /// A database-wide setting, so unlike the other records it names no table.
+    pub(crate) fn case_insensitive_names(enabled: bool) -> Self {
+        WalRecord(Json::Object(vec![
+            (
+                "op".to_string(),
+                Json::String("case_insensitive_names".to_string()),
+            ),
+            ("enabled".to_string(), Json::Bool(enabled)),
+        ]))
+    }
+
+    pub(crate) fn create_table(table_name: &str, columns: &[Column]) -> Self {
+        let columns = columns.iter().map(column_to_json).collect();
+        WalRecord::new(
+            "create_table",
+            table_name,
+            vec![("columns", Json::Array(columns))],
+        )
+    }
+
+    pub(crate) fn add_foreign_key_column(
+        table_name: &str,
+        column: &Column,
+        fk_info: &ForeignKeyInfo,
+    ) -> Self {
+        WalRecord::new(
+            "add_foreign_key_column",
+            table_name,
+            vec![
+                ("column", column_to_json(column)),
+                ("foreign_key", foreign_key_to_json(fk_info)),
+            ],
+        )
+    }
+
+    pub(crate) fn add_foreign_key(
+        table_name: &str,
+        column_name: &str,
+        fk_info: &ForeignKeyInfo,
+    ) -> Self {
+        WalRecord::new(
+            "add_foreign_key",
+            table_name,
+            vec![
+                ("column", Json::String(column_name.to_string())),
+                ("foreign_key", foreign_key_to_json(fk_info)),
+            ],
+        )
+    }
+
+    pub(crate) fn drop_foreign_key(table_name: &str, column_name: &str) -> Self {
+        WalRecord::new(
+            "drop_foreign_key",
+            table_name,
+            vec![("column", Json::String(column_name.to_string()))],
+        )
+    }
+
+    pub(crate) fn add_column(table_name: &str, column: &Column, fill: Option<&str>) -> Self {
+        WalRecord::new(
+            "add_column",
+            table_name,
+            vec![
+                ("column", column_to_json(column)),
+                (
+                    "fill",
+                    fill.map_or(Json::Null, |fill| Json::String(fill.to_string())),
+                ),
+            ],
+        )
+    }
+
+    pub(crate) fn drop_column(table_name: &str, column_name: &str) -> Self {
+        WalRecord::new(
+            "drop_column",
+            table_name,
+            vec![("column", Json::String(column_name.to_string()))],
+        )
+    }
+
+    pub(crate) fn drop_primary_key(table_name: &str) -> Self {
+        WalRecord::new("drop_primary_key", table_name, Vec::new())
+    }
+
+    pub(crate) fn add_unique_constraint(table_name: &str, column_name: &str) -> Self {
+        WalRecord::new(
+            "add_unique_constraint",
+            table_name,
+            vec![("column", Json::String(column_name.to_string()))],
+        )
+    }
+
+    pub(crate) fn rename_column(table_name: &str, old_name: &str, new_name: &str) -> Self {
+        WalRecord::new(
+            "rename_column",
+            table_name,
+            vec![
+                ("column", Json::String(old_name.to_string())),
+                ("new_name", Json::String(new_name.to_string())),
+            ],
+        )
+    }
+
+    pub(crate) fn rename_table(table_name: &str, new_name: &str) -> Self {
+        WalRecord::new(
+            "rename_table",
+            table_name,
+            vec![("new_name", Json::String(new_name.to_string()))],
+        )
+    }
+
+    pub(crate) fn drop_table(table_name: &str, cascade: bool) -> Self {
+        WalRecord::new("drop_table", table_name, vec![("cascade", Json::Bool(cascade))])
+    }
+
+    pub(crate) fn insert(table_name: &str, values: &[String]) -> Self {
+        WalRecord::new(
+            "insert",
+            table_name,
+            vec![("values", strings_to_json(values))],
+        )
+    }
+
+    pub(crate) fn insert_many(table_name: &str, rows: &[Vec<String>]) -> Self {
+        let rows = rows.iter().map(|row| strings_to_json(row)).collect();
+        WalRecord::new("insert_many", table_name, vec![("rows", Json::Array(rows))])
+    }
+
+    pub(crate) fn replace(table_name: &str, values: &[String]) -> Self {
+        WalRecord::new(
+            "replace",
+            table_name,
+            vec![("values", strings_to_json(values))],
+        )
+    }
+
+    pub(crate) fn insert_with_columns(
+        table_name: &str,
+        column_names: &[String],
+        values: &[String],
+    ) -> Self {
+        WalRecord::new(
+            "insert_with_columns",
+            table_name,
+            vec![
+                ("columns", strings_to_json(column_names)),
+                ("values", strings_to_json(values)),
+            ],
+        )
+    }
+
+    pub(crate) fn append_table(target_table: &str, source_table: &str) -> Self {
+        WalRecord::new(
+            "append_table",
+            target_table,
+            vec![("source", Json::String(source_table.to_string()))],
+        )
+    }
+
+    pub(crate) fn insert_deferred(table_name: &str, values: &[String]) -> Self {
+        WalRecord::new(
+            "insert_deferred",
+            table_name,
+            vec![("values", strings_to_json(values))],
+        )
+    }
+
+    pub(crate) fn update_column(table_name: &str, column_name: &str, new_value: &str) -> Self {
+        WalRecord::new(
+            "update_column",
+            table_name,
+            vec![
+                ("column", Json::String(column_name.to_string())),
+                ("value", Json::String(new_value.to_string())),
+            ],
+        )
+    }
+
+    pub(crate) fn update(
+        table_name: &str,
+        update_input: &(String, String),
+        nested_condition: &NestedCondition,
+    ) -> Self {
+        WalRecord::new(
+            "update",
+            table_name,
+            vec![
+                ("column", Json::String(update_input.0.clone())),
+                ("value", Json::String(update_input.1.clone())),
+                ("condition", condition_to_json(nested_condition)),
+            ],
+        )
+    }
+
+    pub(crate) fn update_by_pk(
+        table_name: &str,
+        key: &str,
+        assignments: &[(String, String)],
+    ) -> Self {
+        let (column_names, values): (Vec<String>, Vec<String>) =
+            assignments.iter().cloned().unzip();
+        WalRecord::new(
+            "update_by_pk",
+            table_name,
+            vec![
+                ("key", Json::String(key.to_string())),
+                ("columns", strings_to_json(&column_names)),
+                ("values", strings_to_json(&values)),
+            ],
+        )
+    }
+
+    pub(crate) fn update_from(
+        target_table: &str,
+        source_table: &str,
+        join_on: &(String, String),
+        assignments: &[(String, String)],
+    ) -> Self {
+        let (target_columns, source_columns): (Vec<String>, Vec<String>) =
+            assignments.iter().cloned().unzip();
+        WalRecord::new(
+            "update_from",
+            target_table,
+            vec![
+                ("source", Json::String(source_table.to_string())),
+                ("target_join", Json::String(join_on.0.clone())),
+                ("source_join", Json::String(join_on.1.clone())),
+                ("target_columns", strings_to_json(&target_columns)),
+                ("source_columns", strings_to_json(&source_columns)),
+            ],
+        )
+    }
+
+    pub(crate) fn delete(table_name: &str, nested_condition: &NestedCondition) -> Self {
+        WalRecord::new(
+            "delete",
+            table_name,
+            vec![("condition", condition_to_json(nested_condition))],
+        )
+    }
+
+    pub(crate) fn truncate(table_name: &str, cascade: bool) -> Self {
+        WalRecord::new("truncate", table_name, vec![("cascade", Json::Bool(cascade))])
+    }
+}
+
+/// The open write-ahead log of a database, see `Database::enable_wal`.
+pub(crate) struct WalFile {
+    path: String,
+    file: File,
+}
+
+impl WalFile {
+    /// Appends a record and syncs it to disk, returning the length of the log before it. A
+    /// record that can't be written completely is cut off again, so no torn line is left
+    /// behind for the following records.
+    fn append(&self, record: &WalRecord) -> Result<u64, Error> {
+        let len = self
+            .file
+            .metadata()
+            .map_err(|e| Error::WriteAheadLog(format!("Failed to read '{}': {}", self.path, e)))?
+            .len();
+
+        let line = format!("{}\n", record.0);
+        (&self.file)
+            .write_all(line.as_bytes())
+            .and_then(|_| self.file.sync_data())
+            .map_err(|e| {
+                let _ = self.file.set_len(len);
+                Error::WriteAheadLog(format!("Failed to write to '{}': {}", self.path, e))
+            })?;
+        Ok(len)
+    }
+
+    /// Cuts the log back to the given length, taking back the records appended since.
+    fn truncate(&self, len: u64) -> Result<(), Error> {
+        self.file
+            .set_len(len)
+            .and_then(|_| self.file.sync_data())
+            .map_err(|e| Error::WriteAheadLog(format!("Failed to write to '{}': {}", self.path, e)))
+    }
+}
+
+impl Database {
+    /// Starts logging every insert, update, delete and schema change to the write-ahead log at
+    /// `path`, appending to it if it already exists. Only operations performed after this call
+    /// are logged, so it should be enabled on an empty database for `replay_wal` to rebuild the
+    /// whole state.
+    ///
+    /// Each record is written and synced to disk before its operation changes anything, and
+    /// taken back out of the log if the operation fails. Only top-level calls are logged:
+    /// cascading updates and deletes are redone when the record is replayed. If a record can't
+    /// be written the operation is not applied and `Error::WriteAheadLog` is returned.
+    pub fn enable_wal(&mut self, path: &str) -> Result<(), Error> {
+        let wal = WalFile {
+            path: path.to_string(),
+            file: open_wal(path)?,
+        };
+
+        // A new log starts with the database name, so replaying it restores the name as well
+        let is_empty = wal
+            .file
+            .metadata()
+            .map_err(|e| Error::WriteAheadLog(format!("Failed to read '{}': {}", path, e)))?
+            .len()
+            == 0;
+        if is_empty {
+            wal.append(&WalRecord::database(&self.name))?;
+        }
+        self.wal = Some(wal);
+
+        // So is the name setting of a database created with `new_case_insensitive`
+        if is_empty && self.case_insensitive_names {
+            self.append_to_wal(WalRecord::case_insensitive_names(true))?;
+        }
+        Ok(())
+    }
+
+    /// Stops logging operations. The log file is left as it is.
+    pub fn disable_wal(&mut self) {
+        self.wal = None;
+    }
+
+    /// Whether operations are currently being logged, used to skip building records otherwise.
+    pub(crate) fn wal_enabled(&self) -> bool {
+        self.wal.is_some()
+    }
+
+    /// Appends a record to the write-ahead log, if one is enabled. Called before the operation
+    /// it records changes anything, once nothing can make the operation fail anymore.
+    pub(crate) fn append_to_wal(&self, record: WalRecord) -> Result<(), Error> {
+        if let Some(wal) = &self.wal {
+            wal.append(&record)?;
+        }
+        Ok(())
+    }
+
+    /// Runs an operation that may still fail after its record is appended to the write-ahead
+    /// log, taking the record back out of the log if it does.
+    pub(crate) fn logged<T>(
+        &mut self,
+        record: Option<WalRecord>,
+        operation: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let wal_len = match (&self.wal, &record) {
+            (Some(wal), Some(record)) => Some(wal.append(record)?),
+            _ => None,
+        };
+
+        let result = operation(self);
+        if let (Err(_), Some(wal), Some(len)) = (&result, &self.wal, wal_len) {
+            wal.truncate(len)?;
+        }
+        result
+    }
+
+    /// Rebuilds a database by replaying the write-ahead log at `path` onto an empty database.
+    /// The returned database doesn't log to `path`; call `enable_wal` to keep appending to it.
+    pub fn replay_wal(path: &str) -> Result<Database, Error> {
+        let mut db = Database::new(path.to_string());
+        db.replay_wal_records(path, true)?;
+        Ok(db)
+    }
+
+    /// Replays the write-ahead log at `path` onto this database, e.g. a base restored from an
+    /// export taken when the log was enabled. The replayed operations are not logged again.
+    pub fn replay_wal_onto(&mut self, path: &str) -> Result<(), Error> {
+        let wal = self.wal.take();
+        let result = self.replay_wal_records(path, false);
+        self.wal = wal;
+        result
+    }
+
+    fn replay_wal_records(&mut self, path: &str, restore_name: bool) -> Result<(), Error> {
+        let file = File::open(path)
+            .map_err(|e| Error::WriteAheadLog(format!("Failed to open '{}': {}", path, e)))?;
+        let lines = BufReader::new(file)
+            .lines()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error::WriteAheadLog(format!("Failed to read '{}': {}", path, e)))?;
+
+        for (line_idx, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record = match Json::parse(line) {
+                Ok(record) => record,
+                // A torn final line is what a crash in the middle of a write leaves behind
+                Err(_) if line_idx == lines.len() - 1 => break,
+                Err(msg) => {
+                    return Err(Error::WriteAheadLog(format!(
+                        "line {}: {}",
+                        line_idx + 1,
+                        msg
+                    )));
+                }
+            };
+
+            self.apply_wal_record(&record, restore_name)
+                .map_err(|e| match e {
+                    Error::WriteAheadLog(msg) => {
+                        Error::WriteAheadLog(format!("line {}: {}", line_idx + 1, msg))
+                    }
+                    e => e,
+                })?;
+        }
+
+        Ok(())
+    }
+
+    fn apply_wal_record(&mut self, record: &Json, restore_name: bool) -> Result<(), Error> {
+        let op = record.str_field("op")?;
+        if op == "database" {
+            if restore_name {
+                self.name = record.str_field("name")?.to_string();
+            }
+            return Ok(());
+        }
+        if op == "case_insensitive_names" {
+            return self.set_case_insensitive_names(record.bool_field("enabled")?);
+        }
+
+        let table_name = record.str_field("table")?;
+        match op {
+            "create_table" => {
+                let columns = record
+                    .array_field("columns")?
+                    .iter()
+                    .map(column_from_json)
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.create_table(table_name, columns)
+            }
+            "add_foreign_key_column" => {
+                let column = column_from_json(record.field("column")?)?;
+                let fk_info = foreign_key_from_json(record.field("foreign_key")?)?;
+                self.add_foreign_key_column(table_name, column, fk_info)
+            }
+            "add_foreign_key" => {
+                let fk_info = foreign_key_from_json(record.field("foreign_key")?)?;
+                self.add_foreign_key(
+                    table_name,
+                    record.str_field("column")?,
+                    &fk_info.reference_table,
+                    &fk_info.reference_column,
+                )
+            }
+            "drop_foreign_key" => self.drop_foreign_key(table_name, record.str_field("column")?),
+            "add_column" => {
+                let column = column_from_json(record.field("column")?)?;
+                let fill = match record.field("fill")? {
+                    Json::Null => None,
+                    _ => Some(record.str_field("fill")?.to_string()),
+                };
+                self.add_column_to_table(table_name, column, fill)
+            }
+            "drop_column" => self.drop_column_from_table(table_name, record.str_field("column")?),
+            "drop_primary_key" => self.drop_primary_key_on_table(table_name),
+            "add_unique_constraint" => {
+                self.add_unique_constraint_to_table(table_name, record.str_field("column")?)
+            }
+            "rename_column" => self.rename_column_in_table(
+                table_name,
+                record.str_field("column")?,
+                record.str_field("new_name")?,
+            ),
+            "rename_table" => self.rename_table(table_name, record.str_field("new_name")?),
+            "drop_table" => self.drop_table(table_name, record.bool_field("cascade")?),
+            "insert" => self
+                .insert_into_table(table_name, record.strings_field("values")?)
+                .map(|_| ()),
+            "insert_many" => self
+                .insert_many_into_table(table_name, record.string_rows_field("rows")?)
+                .map(|_| ()),
+            "replace" => self
+                .replace_into_table(table_name, record.strings_field("values")?)
+                .map(|_| ()),
+            "insert_with_columns" => self
+                .insert_with_columns_into_table(
+                    table_name,
+                    record.strings_field("columns")?,
+                    record.strings_field("values")?,
+                )
+                .map(|_| ()),
+            "append_table" => self
+                .append_table(table_name, record.str_field("source")?)
+                .map(|_| ()),
+            "insert_deferred" => self
+                .insert_into_table_deferred(table_name, record.strings_field("values")?)
+                .map(|_| ()),
+            "update_column" => self.update_column_in_table(
+                table_name,
+                record.str_field("column")?,
+                record.str_field("value")?,
+            ),
+            "update" => self
+                .update_with_nested_conditions_in_table(
+                    table_name,
+                    (
+                        record.str_field("column")?.to_string(),
+                        record.str_field("value")?.to_string(),
+                    ),
+                    condition_from_json(record.field("condition")?)?,
+                )
+                .map(|_| ()),
+            "update_by_pk" => self
+                .update_by_pk_in_table(
+                    table_name,
+                    record.str_field("key")?,
+                    record
+                        .strings_field("columns")?
+                        .into_iter()
+                        .zip(record.strings_field("values")?)
+                        .collect(),
+                )
+                .map(|_| ()),
+            "update_from" => self
+                .update_from(
+                    table_name,
+                    record.str_field("source")?,
+                    (
+                        record.str_field("target_join")?.to_string(),
+                        record.str_field("source_join")?.to_string(),
+                    ),
+                    record
+                        .strings_field("target_columns")?
+                        .into_iter()
+                        .zip(record.strings_field("source_columns")?)
+                        .collect(),
+                )
+                .map(|_| ()),
+            "delete" => self
+                .delete_with_nested_conditions_in_table(
+                    table_name,
+                    condition_from_json(record.field("condition")?)?,
+                )
+                .map(|_| ()),
+            "truncate" => self
+                .truncate_table(table_name, record.bool_field("cascade")?)
+                .map(|_| ()),
+            _ => Err(Error::WriteAheadLog(format!("Unknown operation '{}'", op))),
+        }
+    }
+}
+
+fn open_wal(path: &str) -> Result<File, Error> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| Error::WriteAheadLog(format!("Failed to open '{}': {}", path, e)))
+}
+
+fn strings_to_json(values: &[String]) -> Json {
+    Json::Array(values.iter().cloned().map(Json::String).collect())
+}
+
+fn foreign_key_to_json(fk_info: &ForeignKeyInfo) -> Json {
+    Json::Object(vec![
+        (
+            "table".to_string(),
+            Json::String(fk_info.reference_table.clone()),
+        ),
+        (
+            "column".to_string(),
+            Json::String(fk_info.reference_column.clone()),
+        ),
+    ])
+}
+
+fn foreign_key_from_json(json: &Json) -> Result<ForeignKeyInfo, Error> {
+    Ok(ForeignKeyInfo::new(
+        json.str_field("table")?,
+        json.str_field("column")?,
+    ))
+}
+
+fn column_to_json(column: &Column) -> Json {
+    Json::Object(vec![
+        ("name".to_string(), Json::String(column.name.clone())),
+        (
+            "type".to_string(),
+            Json::String(column.data_type.to_string()),
+        ),
+        ("primary_key".to_string(), Json::Bool(column.is_primary_key)),
+        ("not_null".to_string(), Json::Bool(column.not_null)),
+        ("unique".to_string(), Json::Bool(column.unique)),
+        (
+            "default".to_string(),
+            column.default_value.as_ref().map_or(Json::Null, value_to_json),
+        ),
+        (
+            "foreign_key".to_string(),
+            column
+                .foreign_key
+                .as_ref()
+                .map_or(Json::Null, foreign_key_to_json),
+        ),
+    ])
+}
+
+fn column_from_json(json: &Json) -> Result<Column, Error> {
+    let data_type = match json.str_field("type")? {
+        "Integer" => ColumnDataType::Integer,
+        "Float" => ColumnDataType::Float,
+        "Text" => ColumnDataType::Text,
+        "Decimal" => ColumnDataType::Decimal,
+        other => {
+            return Err(Error::WriteAheadLog(format!(
+                "Invalid data type '{}'",
+                other
+            )))
+        }
+    };
+
+    let mut column = Column::builder(json.str_field("name")?, data_type);
+    if json.bool_field("primary_key")? {
+        column = column.primary_key();
+    }
+    if json.bool_field("not_null")? {
+        column = column.not_null();
+    }
+    if json.bool_field("unique")? {
+        column = column.unique();
+    }
+    match json.field("foreign_key")? {
+        Json::Null => {}
+        fk_json => column = column.foreign_key(foreign_key_from_json(fk_json)?),
+    }
+    // Logs written before defaults were recorded have no "default" field
+    match json.field("default") {
+        Err(_) | Ok(Json::Null) => {}
+        Ok(default_json) => {
+            column = column.default_value(value_from_json(default_json, data_type)?)
+        }
+    }
+
+    Ok(column.build())
+}
+
+/// Stores a non-null value as a string that parses back exactly, unlike its `Display` form
+/// which rounds floats.
+fn value_to_json(value: &Value) -> Json {
+    match value {
+        Value::Float(value) => Json::String(value.to_string()),
+        Value::Null => Json::Null,
+        value => Json::String(value.to_string()),
+    }
+}
+
+fn value_from_json(json: &Json, data_type: ColumnDataType) -> Result<Value, Error> {
+    let invalid = || Error::WriteAheadLog(format!("Invalid {} value {}", data_type, json));
+    let Json::String(value) = json else {
+        return Err(invalid());
+    };
+
+    match data_type {
+        ColumnDataType::Integer => value.parse().map(Value::Integer).map_err(|_| invalid()),
+        ColumnDataType::Float => value.parse().map(Value::Float).map_err(|_| invalid()),
+        ColumnDataType::Text => Ok(Value::Text(value.clone())),
+        ColumnDataType::Decimal => Value::parse_decimal(value).ok_or_else(invalid),
+    }
+}
+
+fn condition_to_json(condition: &NestedCondition) -> Json {
+    match condition {
+        NestedCondition::Condition(column_name, operator, value) => Json::Object(vec![(
+            "condition".to_string(),
+            strings_to_json(&[column_name.clone(), operator.clone(), value.clone()]),
+        )]),
+        NestedCondition::And(left, right) => Json::Object(vec![(
+            "and".to_string(),
+            Json::Array(vec![condition_to_json(left), condition_to_json(right)]),
+        )]),
+        NestedCondition::Or(left, right) => Json::Object(vec![(
+            "or".to_string(),
+            Json::Array(vec![condition_to_json(left), condition_to_json(right)]),
+        )]),
+        NestedCondition::Not(inner) => {
+            Json::Object(vec![("not".to_string(), condition_to_json(inner))])
+        }
+    }
+}
+
+fn condition_from_json(json: &Json) -> Result<NestedCondition, Error> {
+    if let Ok(parts) = json.strings_field("condition") {
+        if let [column_name, operator, value] = parts.as_slice() {
+            return Ok(NestedCondition::Condition(
+                column_name.clone(),
+                operator.clone(),
+                value.clone(),
+            ));
+        }
+    }
+
+    for (key, combine) in [
+        ("and", NestedCondition::And as fn(_, _) -> _),
+        ("or", NestedCondition::Or as fn(_, _) -> _),
+    ] {
+        if let Ok([left, right]) = json.array_field(key).map(|parts| parts.as_slice()) {
+            return Ok(combine(
+                Box::new(condition_from_json(left)?),
+                Box::new(condition_from_json(right)?),
+            ));
+        }
+    }
+
+    if let Ok(inner) = json.field("not") {
+        return Ok(NestedCondition::Not(Box::new(condition_from_json(inner)?)));
+    }
+
+    Err(Error::WriteAheadLog(format!("Invalid condition {}", json)))
+}
+
+/// The subset of JSON needed by the log: every value is written as a string, so numbers are
+/// not supported.
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn parse(input: &str) -> Result<Json, String> {
+        let mut parser = JsonParser {
+            chars: input.chars().collect(),
+            pos: 0,
+        };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.chars.len() {
+            return Err(format!("Unexpected trailing data at {}", parser.pos));
+        }
+        Ok(value)
+    }
+
+    fn field(&self, key: &str) -> Result<&Json, Error> {
+        match self {
+            Json::Object(entries) => entries
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value)
+                .ok_or(Error::WriteAheadLog(format!("Missing field '{}'", key))),
+            _ => Err(Error::WriteAheadLog(format!(
+                "Expected an object with '{}'",
+                key
+            ))),
+        }
+    }
+
+    fn str_field(&self, key: &str) -> Result<&str, Error> {
+        match self.field(key)? {
+            Json::String(value) => Ok(value),
+            _ => Err(Error::WriteAheadLog(format!(
+                "Field '{}' is not a string",
+                key
+            ))),
+        }
+    }
+
+    fn bool_field(&self, key: &str) -> Result<bool, Error> {
+        match self.field(key)? {
+            Json::Bool(value) => Ok(*value),
+            _ => Err(Error::WriteAheadLog(format!(
+                "Field '{}' is not a boolean",
+                key
+            ))),
+        }
+    }
+
+    fn array_field(&self, key: &str) -> Result<&Vec<Json>, Error> {
+        match self.field(key)? {
+            Json::Array(values) => Ok(values),
+            _ => Err(Error::WriteAheadLog(format!(
+                "Field '{}' is not an array",
+                key
+            ))),
+        }
+    }
+
+    fn string_rows_field(&self, key: &str) -> Result<Vec<Vec<String>>, Error> {
+        self.array_field(key)?
+            .iter()
+            .map(|row| match row {
+                Json::Array(values) => values
+                    .iter()
+                    .map(|value| match value {
+                        Json::String(value) => Ok(value.clone()),
+                        _ => Err(Error::WriteAheadLog(format!(
+                            "Field '{}' is not an array of string arrays",
+                            key
+                        ))),
+                    })
+                    .collect(),
+                _ => Err(Error::WriteAheadLog(format!(
+                    "Field '{}' is not an array of string arrays",
+                    key
+                ))),
+            })
+            .collect()
+    }
+
+    fn strings_field(&self, key: &str) -> Result<Vec<String>, Error> {
+        self.array_field(key)?
+            .iter()
+            .map(|value| match value {
+                Json::String(value) => Ok(value.clone()),
+                _ => Err(Error::WriteAheadLog(format!(
+                    "Field '{}' is not an array of strings",
+                    key
+                ))),
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::String(value) => write_json_string(f, value),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(entries) => {
+                write!(f, "{{")?;
+                for (idx, (key, value)) in entries.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_json_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_json_string(f: &mut std::fmt::Formatter<'_>, value: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+struct JsonParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl JsonParser {
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos) {
+            Some(&c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(&c) => Err(format!(
+                "Expected '{}' at {}, found '{}'",
+                expected, self.pos, c
+            )),
+            None => Err(format!("Expected '{}' at end of input", expected)),
+        }
+    }
+
+    fn parse_keyword(&mut self, keyword: &str, value: Json) -> Result<Json, String> {
+        for expected in keyword.chars() {
+            if self.chars.get(self.pos) != Some(&expected) {
+                return Err(format!("Invalid literal at {}", self.pos));
+            }
+            self.pos += 1;
+        }
+        Ok(value)
+    }
+
+    fn parse_value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos) {
+            Some('n') => self.parse_keyword("null", Json::Null),
+            Some('t') => self.parse_keyword("true", Json::Bool(true)),
+            Some('f') => self.parse_keyword("false", Json::Bool(false)),
+            Some('"') => self.parse_string().map(Json::String),
+            Some('[') => {
+                self.pos += 1;
+                let mut values = Vec::new();
+                self.skip_whitespace();
+                if self.chars.get(self.pos) == Some(&']') {
+                    self.pos += 1;
+                    return Ok(Json::Array(values));
+                }
+                loop {
+                    values.push(self.parse_value()?);
+                    self.skip_whitespace();
+                    match self.chars.get(self.pos) {
+                        Some(',') => self.pos += 1,
+                        Some(']') => {
+                            self.pos += 1;
+                            return Ok(Json::Array(values));
+                        }
+                        _ => return Err(format!("Expected ',' or ']' at {}", self.pos)),
+                    }
+                }
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                self.skip_whitespace();
+                if self.chars.get(self.pos) == Some(&'}') {
+                    self.pos += 1;
+                    return Ok(Json::Object(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.parse_string()?;
+                    self.expect(':')?;
+                    entries.push((key, self.parse_value()?));
+                    self.skip_whitespace();
+                    match self.chars.get(self.pos) {
+                        Some(',') => self.pos += 1,
+                        Some('}') => {
+                            self.pos += 1;
+                            return Ok(Json::Object(entries));
+                        }
+                        _ => return Err(format!("Expected ',' or '}}' at {}", self.pos)),
+                    }
+                }
+            }
+            Some(c) => Err(format!("Unexpected '{}' at {}", c, self.pos)),
+            None => Err("Unexpected end of input".to_string()),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            let c = *self
+                .chars
+                .get(self.pos)
+                .ok_or("Unterminated string".to_string())?;
+            self.pos += 1;
+            match c {
+                '"' => return Ok(value),
+                '\\' => {
+                    let escaped = *self
+                        .chars
+                        .get(self.pos)
+                        .ok_or("Unterminated escape".to_string())?;
+                    self.pos += 1;
+                    match escaped {
+                        '"' => value.push('"'),
+                        '\\' => value.push('\\'),
+                        '/' => value.push('/'),
+                        'n' => value.push('\n'),
+                        'r' => value.push('\r'),
+                        't' => value.push('\t'),
+                        'b' => value.push('\u{8}'),
+                        'f' => value.push('\u{c}'),
+                        'u' => value.push(self.parse_unicode_escape()?),
+                        other => return Err(format!("Invalid escape '\\{}'", other)),
+                    }
+                }
+                c => value.push(c),
+            }
+        }
+    }
+
+    /// Parses the hex digits of a `\u` escape, combining surrogate pairs.
+    fn parse_unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.parse_hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            self.expect('\\')?;
+            self.expect('u')?;
+            let low = self.parse_hex4()?;
+            0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or(format!("Invalid unicode escape at {}", self.pos))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, String> {
+        let digits: String = self.chars.iter().skip(self.pos).take(4).collect();
+        self.pos += 4;
+        u32::from_str_radix(&digits, 16).map_err(|_| format!("Invalid unicode escape '{}'", digits))
+    }
+}
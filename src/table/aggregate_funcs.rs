@@ -0,0 +1,551 @@
+use crate::column::{align_decimals, Column, ColumnDataType, Value};
+use crate::table::helpers::{evaluate_nested_conditions, validate_nested_conditions};
+use crate::table::{AggregateFn, ColumnSummary, Error, NestedCondition, Table};
+use std::collections::{HashMap, HashSet};
+
+impl Table {
+    /// Counts the number of records that satisfy the provided nested condition structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - A reference to the `NestedCondition` enum representing the nested condition structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of records satisfying the condition (0 for an empty table).
+    /// * `Err(Error)` - An error if the condition is invalid.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
+    /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let condition = NestedCondition::Condition("age".to_string(), ">".to_string(), "30".to_string());
+    /// let older_than_30 = table.count_where(&condition).unwrap();
+    /// ```
+    pub fn count_where(&self, condition: &NestedCondition) -> Result<usize, Error> {
+        let condition = &*self.resolve_condition(condition)?;
+        validate_nested_conditions(condition, &self.columns)?;
+
+        let mut count = 0;
+        for row_idx in 0..self.row_count() {
+            if evaluate_nested_conditions(condition, &self.columns, row_idx)? {
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Checks whether at least one record satisfies the provided nested condition structure.
+    /// Stops at the first matching record, unlike `count_where` which scans the whole table.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - A reference to the `NestedCondition` enum representing the nested condition structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if any record satisfies the condition, `false` otherwise.
+    /// * `Err(Error)` - An error if the condition is invalid, even when the table is empty.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
+    /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let condition = NestedCondition::Condition("user_name".to_string(), "=".to_string(), "Bob".to_string());
+    /// let has_bob = table.exists(&condition).unwrap();
+    /// ```
+    pub fn exists(&self, condition: &NestedCondition) -> Result<bool, Error> {
+        let condition = &*self.resolve_condition(condition)?;
+        validate_nested_conditions(condition, &self.columns)?;
+
+        for row_idx in 0..self.row_count() {
+            if evaluate_nested_conditions(condition, &self.columns, row_idx)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Groups the table rows by the distinct values of the provided columns and computes
+    /// the requested aggregates for every group.
+    ///
+    /// The returned table has one row per distinct group key (in order of first appearance) and
+    /// contains the group columns followed by one column per aggregate. Rows whose group key
+    /// contains NULL form their own group. When no group columns are given, the whole table is
+    /// a single group.
+    ///
+    /// Group columns keep their name. Aggregate columns are named `<function>_<column>` with the
+    /// function in lowercase (`count`, `sum`, `avg`, `min` or `max`), e.g. `avg_score` for
+    /// `("score", AggregateFn::Avg)`. Groups can therefore be filtered on an aggregate, like a
+    /// SQL `HAVING` clause, by calling `filter` on the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_columns` - A vector of strings representing the names of the columns to group by.
+    /// * `aggregates` - A vector of `(column_name, AggregateFn)` pairs to compute for every group.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - A new table holding the grouped results.
+    /// * `Err(Error)` - An error if a column does not exist or an aggregate is not applicable.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If a group or aggregate column does not exist in the table.
+    /// * `Error::NonNumericColumn` - If `Sum` or `Avg` is requested on a Text column.
+    /// * `Error::DuplicateOutputColumn` - If two output columns end up with the same name.
+    /// * `Error::InvalidExpression` - If an Integer or Decimal `Sum` overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{AggregateFn, NestedCondition, Table};
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// // Average score per age
+    /// let grouped = table
+    ///     .group_by(vec!["age".to_string()], vec![("score".to_string(), AggregateFn::Avg)])
+    ///     .unwrap();
+    /// grouped.show();
+    ///
+    /// // Only the ages whose average score exceeds 80
+    /// let condition = NestedCondition::Condition("avg_score".to_string(), ">".to_string(), "80".to_string());
+    /// grouped.filter(&condition).unwrap().show();
+    /// ```
+    pub fn group_by(
+        &self,
+        group_columns: Vec<String>,
+        aggregates: Vec<(String, AggregateFn)>,
+    ) -> Result<Table, Error> {
+        let group_indices = group_columns
+            .iter()
+            .map(|name| self.column_position(name))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let aggregate_indices = aggregates
+            .iter()
+            .map(|(name, aggregate_fn)| {
+                let column_idx = self.column_position(name)?;
+                if matches!(aggregate_fn, AggregateFn::Sum | AggregateFn::Avg)
+                    && self.columns[column_idx].data_type == ColumnDataType::Text
+                {
+                    return Err(Error::NonNumericColumn(name.clone()));
+                }
+                Ok(column_idx)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Output columns are looked up by name, e.g. when filtering on an aggregate
+        let mut output_names = HashSet::new();
+        let aggregate_names = aggregates
+            .iter()
+            .map(|(name, aggregate_fn)| aggregate_column_name(name, aggregate_fn));
+        for output_name in group_columns.iter().cloned().chain(aggregate_names) {
+            if !output_names.insert(output_name.clone()) {
+                return Err(Error::DuplicateOutputColumn(output_name));
+            }
+        }
+
+        // Collect the row indices of every group, keeping groups in order of first appearance
+        let mut groups: Vec<(Vec<Value>, Vec<usize>)> = Vec::new();
+        let mut group_positions: HashMap<Vec<Value>, usize> = HashMap::new();
+
+        for row_idx in 0..self.row_count() {
+            let key: Vec<Value> = group_indices
+                .iter()
+                .map(|&column_idx| {
+                    self.columns[column_idx]
+                        .data
+                        .get(row_idx)
+                        .cloned()
+                        .unwrap_or(Value::Null)
+                })
+                .collect();
+
+            match group_positions.get(&key) {
+                Some(&group_idx) => groups[group_idx].1.push(row_idx),
+                None => {
+                    group_positions.insert(key.clone(), groups.len());
+                    groups.push((key, vec![row_idx]));
+                }
+            }
+        }
+
+        if group_indices.is_empty() && groups.is_empty() {
+            groups.push((Vec::new(), Vec::new()));
+        }
+
+        // Group columns keep their source name and type, aggregates get a derived name
+        let mut columns: Vec<Column> = group_indices
+            .iter()
+            .map(|&column_idx| {
+                let column = &self.columns[column_idx];
+                Column::new(&column.name, column.data_type, None, false, None)
+            })
+            .collect();
+
+        for ((name, aggregate_fn), &column_idx) in aggregates.iter().zip(aggregate_indices.iter()) {
+            let source_type = self.columns[column_idx].data_type;
+            let data_type = match aggregate_fn {
+                AggregateFn::Count => ColumnDataType::Integer,
+                AggregateFn::Avg => ColumnDataType::Float,
+                AggregateFn::Sum | AggregateFn::Min | AggregateFn::Max => source_type,
+            };
+            columns.push(Column::new(
+                &aggregate_column_name(name, aggregate_fn),
+                data_type,
+                None,
+                false,
+                None,
+            ));
+        }
+
+        for (key, row_indices) in groups {
+            for (column_idx, value) in key.into_iter().enumerate() {
+                columns[column_idx].data.push(value);
+            }
+
+            for (aggregate_idx, ((name, aggregate_fn), &source_idx)) in
+                aggregates.iter().zip(aggregate_indices.iter()).enumerate()
+            {
+                let values: Vec<&Value> = row_indices
+                    .iter()
+                    .filter_map(|&row_idx| self.columns[source_idx].data.get(row_idx))
+                    .filter(|value| **value != Value::Null)
+                    .collect();
+
+                columns[group_indices.len() + aggregate_idx]
+                    .data
+                    .push(compute_aggregate(&values, aggregate_fn, name)?);
+            }
+        }
+
+        Table::new(&self.name, columns)
+    }
+
+    /// Counts how often every distinct value occurs in a column.
+    ///
+    /// NULL values are counted as their own bucket. The result is sorted by descending count,
+    /// with ties kept in order of first appearance.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - A string slice representing the name of the column to count values in.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(Value, usize)>)` - The distinct values paired with their number of occurrences.
+    /// * `Err(Error)` - An error if the column does not exist in the table.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::NonExistingColumn` - If the provided column name does not exist in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// for (value, count) in table.value_counts("age").unwrap() {
+    ///     println!("{}: {}", value, count);
+    /// }
+    /// ```
+    pub fn value_counts(&self, column_name: &str) -> Result<Vec<(Value, usize)>, Error> {
+        let column = &self.columns[self.column_position(column_name)?];
+
+        let mut counts: Vec<(Value, usize)> = Vec::new();
+        let mut positions: HashMap<&Value, usize> = HashMap::new();
+
+        for value in &column.data {
+            match positions.get(value) {
+                Some(&idx) => counts[idx].1 += 1,
+                None => {
+                    positions.insert(value, counts.len());
+                    counts.push((value.clone(), 1));
+                }
+            }
+        }
+
+        // Stable sort keeps ties in order of first appearance
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        Ok(counts)
+    }
+
+    /// Prints the value frequencies of a column as a two-column table (`column_name` and `count`),
+    /// using the same layout as `show`.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::NonExistingColumn` - If the provided column name does not exist in the table.
+    pub fn show_value_counts(&self, column_name: &str) -> Result<(), Error> {
+        let counts = self.value_counts(column_name)?;
+        let data_type = self.columns[self.column_position(column_name)?].data_type;
+
+        let mut value_column = Column::new(column_name, data_type, None, false, None);
+        let mut count_column = Column::new("count", ColumnDataType::Integer, None, false, None);
+        for (value, count) in counts {
+            value_column.data.push(value);
+            count_column.data.push(Value::Integer(count as i64));
+        }
+
+        Table::new(&self.name, vec![value_column, count_column])?.show();
+
+        Ok(())
+    }
+
+    /// Profiles a column in one pass: how many values it holds, how many are NULL or distinct,
+    /// and its minimum, maximum and mean.
+    ///
+    /// NULL values are ignored by every statistic except `null_count`. Text columns get their
+    /// minimum and maximum in lexicographic order and no mean.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - A string slice representing the name of the column to profile.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ColumnSummary)` - The statistics of the column.
+    /// * `Err(Error)` - An error if the column does not exist in the table.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::NonExistingColumn` - If the provided column name does not exist in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let summary = table.column_summary("age").unwrap();
+    /// println!("{} values, {} distinct", summary.count, summary.distinct_count);
+    /// ```
+    pub fn column_summary(&self, column_name: &str) -> Result<ColumnSummary, Error> {
+        let column = &self.columns[self.column_position(column_name)?];
+
+        let values: Vec<&Value> = column
+            .data
+            .iter()
+            .filter(|value| **value != Value::Null)
+            .collect();
+        let distinct_count = values.iter().collect::<HashSet<_>>().len();
+
+        let statistic = |aggregate_fn: AggregateFn| {
+            (!values.is_empty())
+                .then(|| compute_aggregate(&values, &aggregate_fn, &column.name))
+                .transpose()
+        };
+        let mean = if column.data_type == ColumnDataType::Text {
+            None
+        } else {
+            statistic(AggregateFn::Avg)?
+        };
+
+        Ok(ColumnSummary {
+            count: values.len(),
+            null_count: column.data.len() - values.len(),
+            distinct_count,
+            min: statistic(AggregateFn::Min)?,
+            max: statistic(AggregateFn::Max)?,
+            mean,
+        })
+    }
+
+    /// Returns a copy of the table with an extra column holding the running total of a numeric
+    /// column, in row order. NULL values add nothing to the total but still get the total so far.
+    ///
+    /// The new column is added at the end, with the data type of the source column. Decimals
+    /// are summed exactly, at the largest scale seen so far.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_col` - The name of the column to sum up.
+    /// * `new_col_name` - The name of the column holding the running totals.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - A new table with the records of this table and the running totals.
+    /// * `Err(Error)` - An error if the source column is not numeric or the new column can't be added.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If the source column does not exist in the table.
+    /// * `Error::NonNumericColumn` - If the source column is a Text column.
+    /// * `Error::InvalidExpression` - If the running total overflows.
+    /// * `Error::InvalidName` - If the new column name is invalid, see `Table::new`.
+    /// * `Error::ColumnAlreadyExists` - If the table already has a column with the new name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let sales = Table::new("sales", /* ... */);
+    /// sales.with_cumulative("amount", "running_amount").unwrap().show();
+    /// ```
+    pub fn with_cumulative(&self, source_col: &str, new_col_name: &str) -> Result<Table, Error> {
+        let source = &self.columns[self.column_position(source_col)?];
+        let mut running_total = match source.data_type {
+            ColumnDataType::Integer => Value::Integer(0),
+            ColumnDataType::Float => Value::Float(0.0),
+            ColumnDataType::Decimal => Value::Decimal(0, 0),
+            ColumnDataType::Text => return Err(Error::NonNumericColumn(source.name.clone())),
+        };
+
+        let totals = (0..self.row_count())
+            .map(|row_idx| {
+                let value = source.data.get(row_idx).unwrap_or(&Value::Null);
+                running_total = add_to_total(&running_total, value).ok_or_else(|| {
+                    Error::InvalidExpression(format!(
+                        "arithmetic overflow in column '{}'",
+                        source.name
+                    ))
+                })?;
+                Ok(running_total.clone())
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut result = self.clone();
+        let column = Column::new(new_col_name, source.data_type, None, false, None);
+        result.add_column(column, None)?;
+        if let Some(column) = result.columns.last_mut() {
+            column.data = totals;
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the position of the column with the given name, see `resolve_column_name`.
+    pub(crate) fn column_position(&self, column_name: &str) -> Result<usize, Error> {
+        let column_name = self.resolve_column_name(column_name)?;
+        self.columns
+            .iter()
+            .position(|c| c.name == column_name)
+            .ok_or(Error::NonExistingColumn(column_name.to_string()))
+    }
+}
+
+/// Name of the column holding an aggregate in the output of `group_by`, e.g. `avg_score`.
+fn aggregate_column_name(column_name: &str, aggregate_fn: &AggregateFn) -> String {
+    let prefix = match aggregate_fn {
+        AggregateFn::Count => "count",
+        AggregateFn::Sum => "sum",
+        AggregateFn::Avg => "avg",
+        AggregateFn::Min => "min",
+        AggregateFn::Max => "max",
+    };
+    format!("{}_{}", prefix, column_name)
+}
+
+/// Computes an aggregate over the non-null values of a group of the named column.
+/// Everything but `Count` yields NULL when the group has no non-null values, and an Integer or
+/// Decimal `Sum` that overflows is an `InvalidExpression` error.
+fn compute_aggregate(
+    values: &[&Value],
+    aggregate_fn: &AggregateFn,
+    column_name: &str,
+) -> Result<Value, Error> {
+    if values.is_empty() {
+        return Ok(match aggregate_fn {
+            AggregateFn::Count => Value::Integer(0),
+            _ => Value::Null,
+        });
+    }
+
+    let overflow =
+        || Error::InvalidExpression(format!("arithmetic overflow in column '{}'", column_name));
+
+    Ok(match aggregate_fn {
+        AggregateFn::Count => Value::Integer(values.len() as i64),
+        AggregateFn::Sum => {
+            // Decimals are summed exactly, at the largest scale of the group
+            let zero = match values[0] {
+                Value::Integer(_) => Value::Integer(0),
+                Value::Decimal(..) => Value::Decimal(0, 0),
+                _ => return Ok(Value::Float(values.iter().filter_map(|v| as_f64(v)).sum())),
+            };
+            values
+                .iter()
+                .try_fold(zero, |total, value| add_to_total(&total, value))
+                .ok_or_else(overflow)?
+        }
+        AggregateFn::Avg => {
+            let sum: f64 = values.iter().filter_map(|value| as_f64(value)).sum();
+            Value::Float(sum / values.len() as f64)
+        }
+        AggregateFn::Min | AggregateFn::Max => {
+            let wanted = if *aggregate_fn == AggregateFn::Min {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Greater
+            };
+            let mut best = values[0];
+            for value in &values[1..] {
+                if value.cmp_value(best) == Some(wanted) {
+                    best = value;
+                }
+            }
+            best.clone()
+        }
+    })
+}
+
+/// Adds a value to a running total of the same type, or `None` on overflow. NULL adds nothing.
+fn add_to_total(total: &Value, value: &Value) -> Option<Value> {
+    match (total, value) {
+        (Value::Integer(total), Value::Integer(value)) => {
+            total.checked_add(*value).map(Value::Integer)
+        }
+        (Value::Float(total), Value::Float(value)) => Some(total + value)
+            .filter(|total| total.is_finite())
+            .map(Value::Float),
+        (Value::Decimal(total, total_scale), Value::Decimal(mantissa, scale)) => {
+            let (total, mantissa, scale) =
+                align_decimals((*total, *total_scale), (*mantissa, *scale))?;
+            Some(Value::Decimal(total.checked_add(mantissa)?, scale))
+        }
+        (total, _) => Some(total.clone()),
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(value) => Some(*value as f64),
+        Value::Float(value) => Some(*value),
+        Value::Decimal(mantissa, scale) => Some(*mantissa as f64 / 10f64.powi(*scale as i32)),
+        _ => None,
+    }
+}
@@ -0,0 +1,273 @@
+use crate::table::{Error, NestedCondition};
+
+impl NestedCondition {
+    /// Parses a condition written as a string expression, e.g.
+    /// `age > 25 AND (score >= 85 OR name = 'Charlie')`.
+    ///
+    /// Comparisons are written `column operator literal` with the operators `=`, `!=`, `<`, `>`,
+    /// `<=` and `>=`, or `column IS NULL` / `column IS NOT NULL`. Literals are numbers or text in
+    /// single quotes (a quote inside the text is doubled, `'O''Brien'`). Column names that are
+    /// not plain words, or that collide with a keyword, go in double quotes. Comparisons combine
+    /// with `NOT`, `AND` and `OR`, binding in that order, and parentheses. Keywords ignore case.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - A string slice holding the condition expression.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NestedCondition)` - The condition tree described by the expression.
+    /// * `Err(Error)` - An error if the expression is malformed.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::InvalidConditionSyntax` - With the character position of the problem and what
+    ///   was expected there. Columns and values are not checked against any table here.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let condition = NestedCondition::parse("age > 25 AND NOT user_name = 'Bob'").unwrap();
+    /// table.filter(&condition).unwrap().show();
+    /// ```
+    pub fn parse(expr: &str) -> Result<NestedCondition, Error> {
+        let mut parser = ConditionParser::new(expr);
+
+        let condition = parser.parse_or()?;
+        if !parser.at_end() {
+            return Err(parser.error("expected AND, OR or the end of the condition"));
+        }
+
+        Ok(condition)
+    }
+}
+
+const KEYWORDS: [&str; 8] = ["AND", "OR", "NOT", "IS", "NULL", "SELECT", "FROM", "WHERE"];
+
+/// Recursive-descent parser over the characters of a condition expression, one method per
+/// precedence level. Statements embedding a condition, like `Database::execute_select`, drive
+/// it through the crate-visible methods so that positions are reported against the statement.
+pub(crate) struct ConditionParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl ConditionParser {
+    pub(crate) fn new(expr: &str) -> Self {
+        ConditionParser {
+            chars: expr.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    pub(crate) fn error(&self, reason: &str) -> Error {
+        Error::InvalidConditionSyntax(self.pos, reason.to_string())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Whether only whitespace is left.
+    pub(crate) fn at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.pos == self.chars.len()
+    }
+
+    /// Consumes `symbol` if it is the next character.
+    pub(crate) fn eat_symbol(&mut self, symbol: char) -> bool {
+        self.skip_whitespace();
+        let matches = self.chars.get(self.pos) == Some(&symbol);
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+
+    /// Consumes `keyword` if it comes next as a whole word, ignoring case.
+    pub(crate) fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.skip_whitespace();
+        let end = self.pos + keyword.len();
+        let matches = self.chars.len() >= end
+            && self.chars[self.pos..end]
+                .iter()
+                .zip(keyword.chars())
+                .all(|(c, expected)| c.eq_ignore_ascii_case(&expected))
+            && !self.chars.get(end).is_some_and(|&c| is_word_char(c));
+        if matches {
+            self.pos = end;
+        }
+        matches
+    }
+
+    pub(crate) fn parse_or(&mut self) -> Result<NestedCondition, Error> {
+        let mut condition = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            let right = self.parse_and()?;
+            condition = NestedCondition::Or(Box::new(condition), Box::new(right));
+        }
+        Ok(condition)
+    }
+
+    fn parse_and(&mut self) -> Result<NestedCondition, Error> {
+        let mut condition = self.parse_not()?;
+        while self.eat_keyword("AND") {
+            let right = self.parse_not()?;
+            condition = NestedCondition::And(Box::new(condition), Box::new(right));
+        }
+        Ok(condition)
+    }
+
+    fn parse_not(&mut self) -> Result<NestedCondition, Error> {
+        if self.eat_keyword("NOT") {
+            return Ok(NestedCondition::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<NestedCondition, Error> {
+        self.skip_whitespace();
+        if self.chars.get(self.pos) != Some(&'(') {
+            return self.parse_comparison();
+        }
+
+        self.pos += 1;
+        let condition = self.parse_or()?;
+        self.skip_whitespace();
+        if self.chars.get(self.pos) != Some(&')') {
+            return Err(self.error("expected ')'"));
+        }
+        self.pos += 1;
+        Ok(condition)
+    }
+
+    fn parse_comparison(&mut self) -> Result<NestedCondition, Error> {
+        let column_name = self.parse_identifier("column name")?;
+
+        if self.eat_keyword("IS") {
+            let operator = if self.eat_keyword("NOT") {
+                "IS NOT NULL"
+            } else {
+                "IS NULL"
+            };
+            if !self.eat_keyword("NULL") {
+                return Err(self.error("expected NULL"));
+            }
+            return Ok(NestedCondition::Condition(
+                column_name,
+                operator.to_string(),
+                String::new(),
+            ));
+        }
+
+        let operator = self.parse_operator()?;
+        let value = self.parse_literal()?;
+        Ok(NestedCondition::Condition(column_name, operator, value))
+    }
+
+    /// Reads a plain word that is not a keyword, or any name in double quotes. `kind` names what
+    /// is expected, e.g. `column name`, for the error messages.
+    pub(crate) fn parse_identifier(&mut self, kind: &str) -> Result<String, Error> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos) {
+            Some('"') => self.parse_quoted('"', &format!("unterminated {}", kind)),
+            Some(&c) if c.is_alphabetic() || c == '_' => {
+                let start = self.pos;
+                while self.chars.get(self.pos).is_some_and(|&c| is_word_char(c)) {
+                    self.pos += 1;
+                }
+                let word: String = self.chars[start..self.pos].iter().collect();
+                if KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(&word)) {
+                    self.pos = start;
+                    return Err(
+                        self.error(&format!("expected a {}, quote it to use a keyword", kind))
+                    );
+                }
+                Ok(word)
+            }
+            _ => Err(self.error(&format!("expected a {}", kind))),
+        }
+    }
+
+    fn parse_operator(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+        for operator in ["<=", ">=", "!=", "=", "<", ">"] {
+            let end = self.pos + operator.len();
+            if self.chars.len() >= end
+                && self.chars[self.pos..end]
+                    .iter()
+                    .copied()
+                    .eq(operator.chars())
+            {
+                self.pos = end;
+                return Ok(operator.to_string());
+            }
+        }
+        Err(self.error("expected an operator (=, !=, <, >, <=, >= or IS)"))
+    }
+
+    fn parse_literal(&mut self) -> Result<String, Error> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos) {
+            Some('\'') => self.parse_quoted('\'', "unterminated text"),
+            Some(&c) if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                let start = self.pos;
+                self.pos += 1;
+                while self
+                    .chars
+                    .get(self.pos)
+                    .is_some_and(|&c| c.is_ascii_digit() || c == '.')
+                {
+                    self.pos += 1;
+                }
+                let number: String = self.chars[start..self.pos].iter().collect();
+                let followed_by_word = self.chars.get(self.pos).is_some_and(|&c| is_word_char(c));
+                if number.parse::<f64>().is_err() || followed_by_word {
+                    self.pos = start;
+                    return Err(self.error("invalid number"));
+                }
+                Ok(number)
+            }
+            _ => Err(self.error("expected a number or a text in single quotes")),
+        }
+    }
+
+    /// Reads a string delimited by `quote`, where a doubled quote stands for the quote itself.
+    fn parse_quoted(&mut self, quote: char, unterminated: &str) -> Result<String, Error> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            match self.chars.get(self.pos) {
+                Some(&c) if c == quote => {
+                    if self.chars.get(self.pos + 1) == Some(&quote) {
+                        text.push(quote);
+                        self.pos += 2;
+                    } else {
+                        self.pos += 1;
+                        return Ok(text);
+                    }
+                }
+                Some(&c) => {
+                    text.push(c);
+                    self.pos += 1;
+                }
+                None => {
+                    self.pos = start;
+                    return Err(self.error(unterminated));
+                }
+            }
+        }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
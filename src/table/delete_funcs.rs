@@ -1,30 +1,94 @@
+use crate::column::Value;
 use crate::table::helpers::evaluate_nested_conditions;
 use crate::table::{Error, NestedCondition, Table};
 
 impl Table {
+    /// Deletes every record matching the nested condition and returns how many were deleted.
     pub fn delete_with_nested_conditions(
         &mut self,
         nested_condition: &NestedCondition,
-    ) -> Result<(), Error> {
+    ) -> Result<usize, Error> {
+        Ok(self.delete_returning(nested_condition)?.len())
+    }
+
+    /// Deletes every record matching the nested condition and returns the deleted records, in
+    /// table order, with one value per column. They can be inserted back to undo the delete.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
+    /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+    /// * `Error::InvalidConditionValue` - If a condition value can't be compared with its column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// let condition = NestedCondition::parse("age < 18").unwrap();
+    /// let deleted_records = table.delete_returning(&condition).unwrap();
+    /// ```
+    pub fn delete_returning(
+        &mut self,
+        nested_condition: &NestedCondition,
+    ) -> Result<Vec<Vec<Value>>, Error> {
+        let nested_condition = &*self.resolve_condition(nested_condition)?;
         let mut rows_to_remove = Vec::new();
 
-        for row_idx in 0..self.columns[0].data.len() {
+        for row_idx in 0..self.row_count() {
             if evaluate_nested_conditions(nested_condition, &self.columns, row_idx)? {
                 rows_to_remove.push(row_idx);
             }
         }
 
-        // dbg!(&rows_to_remove);
-
+        let mut deleted_records: Vec<Vec<Value>> = rows_to_remove
+            .iter()
+            .map(|_| Vec::with_capacity(self.columns.len()))
+            .collect();
         for col in &mut self.columns {
-            let mut i = 0usize;
-            col.data.retain(|_| {
-                let keep = !rows_to_remove.contains(&i);
-                i += 1;
-                keep
-            });
+            let mut removed = rows_to_remove
+                .iter()
+                .zip(deleted_records.iter_mut())
+                .peekable();
+            let data = std::mem::take(&mut col.data);
+            for (row_idx, value) in data.into_iter().enumerate() {
+                match removed.next_if(|(&removed_idx, _)| removed_idx == row_idx) {
+                    Some((_, deleted_record)) => deleted_record.push(value),
+                    None => col.data.push(value),
+                }
+            }
+            // Columns shorter than the table are NULL in the records they lack
+            for (_, deleted_record) in removed {
+                deleted_record.push(Value::Null);
+            }
+        }
+        self.rebuild_indexes();
+
+        Ok(deleted_records)
+    }
+
+    /// Deletes every record of the table, keeping its columns, constraints and indexes, and
+    /// returns how many records were deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::import_table("users.csv", "csv").unwrap();
+    /// table.truncate();
+    /// assert_eq!(table.row_count(), 0);
+    /// ```
+    pub fn truncate(&mut self) -> usize {
+        let deleted = self.row_count();
+        for column in &mut self.columns {
+            column.data.clear();
         }
+        self.rebuild_indexes();
 
-        Ok(())
+        deleted
     }
 }
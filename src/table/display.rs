@@ -0,0 +1,219 @@
+use crate::column::ColumnDataType;
+use crate::table::{ShowOptions, Table};
+use std::fmt;
+
+/// Renders the table data as a grid: the column names, a separator line, and one line per
+/// record. Every column is as wide as its name or its longest value, whichever is longer, and
+/// missing values are rendered as blank cells. This is the layout printed by `show`.
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.column_widths();
+
+        // Write the column names
+        for (column, width) in self.columns.iter().zip(&widths) {
+            write!(f, "{:>width$} ", column.name, width = width)?;
+        }
+        writeln!(f)?;
+
+        // Write a separator line
+        write!(f, "{}", "-".repeat(separator_len(&widths)))?;
+
+        // Write the data rows
+        for row_idx in 0..self.row_count() {
+            writeln!(f)?;
+            for (column, width) in self.columns.iter().zip(&widths) {
+                match column.data.get(row_idx) {
+                    Some(value) => write!(f, "{:<width$} ", value, width = width)?,
+                    None => write!(f, "{} ", " ".repeat(*width))?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Length of the separator line under cells of the given widths, each followed by a space.
+pub(crate) fn separator_len(widths: &[usize]) -> usize {
+    (widths.iter().sum::<usize>() + widths.len()).saturating_sub(1)
+}
+
+impl Table {
+    /// Width of every column when rendered: the length of its name or of its longest value as
+    /// rendered by `Value`'s Display, whichever is longer.
+    pub(crate) fn column_widths(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .map(|column| {
+                column
+                    .data
+                    .iter()
+                    .map(|value| value.to_string().chars().count())
+                    .chain(std::iter::once(column.name.chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    /// Renders the structure of the table, as printed by `describe`: the table name, the column
+    /// names, a separator line, the data types, the primary key information and, if any, the
+    /// not-null constraints, unique constraints and foreign keys (as `-> table.column`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let description = table.render_description();
+    /// assert!(description.starts_with("Table: users"));
+    /// ```
+    pub fn render_description(&self) -> String {
+        self.schema().to_string()
+    }
+
+    /// Prints the table like `show`, but limited by the given options. Column widths are
+    /// computed from the printed values as well as the column names, so long values no longer
+    /// break the alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{ShowOptions, Table};
+    ///
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// table.show_opts(ShowOptions {
+    ///     max_rows: Some(20),
+    ///     max_cell_width: Some(30),
+    ///     show_row_numbers: true,
+    /// });
+    /// ```
+    pub fn show_opts(&self, options: ShowOptions) {
+        println!("{}", self.render_with_options(&options));
+    }
+
+    /// Renders the table as printed by `show_opts`.
+    pub fn render_with_options(&self, options: &ShowOptions) -> String {
+        let row_count = self.row_count();
+        let shown_rows = options.max_rows.map_or(row_count, |max| max.min(row_count));
+        let truncate = |text: String| -> String {
+            match options.max_cell_width {
+                Some(max_width) if text.chars().count() > max_width => {
+                    let mut cut: String = text.chars().take(max_width.saturating_sub(1)).collect();
+                    cut.push('…');
+                    cut
+                }
+                _ => text,
+            }
+        };
+
+        // Render every printed cell, one vector per line, starting with the header
+        let mut lines: Vec<Vec<String>> = Vec::with_capacity(shown_rows + 1);
+        let mut header: Vec<String> = Vec::new();
+        if options.show_row_numbers {
+            header.push("#".to_string());
+        }
+        header.extend(
+            self.columns
+                .iter()
+                .map(|column| truncate(column.name.clone())),
+        );
+        lines.push(header);
+
+        for row_idx in 0..shown_rows {
+            let mut line: Vec<String> = Vec::new();
+            if options.show_row_numbers {
+                line.push(row_idx.to_string());
+            }
+            line.extend(
+                self.columns
+                    .iter()
+                    .map(|column| match column.data.get(row_idx) {
+                        Some(value) => truncate(value.to_string()),
+                        None => String::new(),
+                    }),
+            );
+            lines.push(line);
+        }
+
+        // Every column is as wide as its widest cell
+        let mut widths = vec![0; lines[0].len()];
+        for line in &lines {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let render_line = |line: &Vec<String>| -> String {
+            line.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        let mut output = vec![render_line(&lines[0])];
+        let separator_len = (widths.iter().sum::<usize>() + widths.len()).saturating_sub(1);
+        output.push("-".repeat(separator_len));
+        output.extend(lines[1..].iter().map(render_line));
+
+        if shown_rows < row_count {
+            output.push(format!("... ({} more rows)", row_count - shown_rows));
+        }
+
+        output.join("\n")
+    }
+
+    /// Renders the table as a GitHub-flavored markdown table, e.g. for pasting into an issue.
+    /// Numeric columns are right-aligned and Text columns left-aligned. Pipe characters in
+    /// values are escaped, and NULL values are rendered as `NULL`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// println!("{}", table.to_markdown());
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let render_line = |cells: Vec<String>| -> String { format!("| {} |", cells.join(" | ")) };
+        let escape = |text: String| -> String { text.replace('|', "\\|") };
+
+        let mut lines = vec![render_line(
+            self.columns
+                .iter()
+                .map(|column| escape(column.name.clone()))
+                .collect(),
+        )];
+
+        lines.push(format!(
+            "|{}|",
+            self.columns
+                .iter()
+                .map(|column| match column.data_type {
+                    ColumnDataType::Integer | ColumnDataType::Float | ColumnDataType::Decimal => {
+                        "---:"
+                    }
+                    ColumnDataType::Text => ":---",
+                })
+                .collect::<Vec<_>>()
+                .join("|")
+        ));
+
+        for row_idx in 0..self.row_count() {
+            lines.push(render_line(
+                self.columns
+                    .iter()
+                    .map(|column| match column.data.get(row_idx) {
+                        Some(value) => escape(value.to_string()),
+                        None => String::new(),
+                    })
+                    .collect(),
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
@@ -1,17 +1,19 @@
 use crate::column::{Column, ColumnDataType, Value};
+use crate::table::display::separator_len;
+use crate::table::helpers::parse_column_value;
 use crate::table::Error;
-use crate::table::Table;
+use crate::table::{OutputFormat, Table};
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
 
 impl Table {
-    /// Function to export the table to a CSV or TXT file based on input.
+    /// Function to export the table to a CSV, TXT or markdown file based on input.
     ///
     /// # Arguments
     ///
     /// * `file_name` - A string representing the name of the file to export.
-    /// * `format` - A string representing the format of the file, either "csv" or "txt".
+    /// * `format` - A string representing the format of the file, either "csv", "txt" or "md".
     ///
     /// # Returns
     ///
@@ -23,7 +25,7 @@ impl Table {
     /// This function can return the following errors:
     ///
     /// * `Error::FileError` - If the file fails to create or write.
-    /// * `Error::InvalidFormat` - If the provided format is not "csv" or "txt".
+    /// * `Error::InvalidFormat` - If the provided format is not "csv", "txt" or "md".
     ///
     /// # Examples
     ///
@@ -37,6 +39,8 @@ impl Table {
     /// table.export_table("users.txt", "txt").unwrap();
     /// ```
     pub fn export_table(&self, file_name: &str, format: &str) -> Result<(), Error> {
+        let format = OutputFormat::from_str(format)?;
+
         let path = Path::new(file_name);
         let file = match File::create(path) {
             Ok(file) => file,
@@ -44,196 +48,47 @@ impl Table {
         };
         let mut writer = BufWriter::new(file);
 
-        match format.to_lowercase().as_str() {
-            "csv" => {
-                // Write column names as header
-                let header = self
-                    .columns
-                    .iter()
-                    .map(|c| c.name.clone())
-                    .collect::<Vec<_>>()
-                    .join(",");
-                writer
-                    .write_all(header.as_bytes())
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                writer
-                    .write_all(b"\n")
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-
-                // Write column data types
-                let data_types = self
-                    .columns
-                    .iter()
-                    .map(|c| format!("{}", c.data_type))
-                    .collect::<Vec<_>>()
-                    .join(",");
-                writer
-                    .write_all(data_types.as_bytes())
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                writer
-                    .write_all(b"\n")
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-
-                // Write primary key information
-                let primary_key_info = self
-                    .columns
-                    .iter()
-                    .map(|c| {
-                        if c.is_primary_key {
-                            "prim_key".to_string()
-                        } else {
-                            "nt_prim_key".to_string()
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .join(",");
-                writer
-                    .write_all(primary_key_info.as_bytes())
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                writer
-                    .write_all(b"\n")
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-
-                // Write data rows
-                let max_rows = self
-                    .columns
-                    .iter()
-                    .map(|column| column.data.len())
-                    .max()
-                    .unwrap_or(0);
-
-                for row_idx in 0..max_rows {
-                    let row_data: Vec<String> = self
-                        .columns
-                        .iter()
-                        .map(|column| {
-                            if row_idx < column.data.len() {
-                                format!("{}", column.data[row_idx])
-                            } else {
-                                "".to_string()
-                            }
-                        })
-                        .collect();
-
-                    let row_string = row_data.join(",");
-                    writer
-                        .write_all(row_string.as_bytes())
-                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                    writer
-                        .write_all(b"\n")
-                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                }
-            }
-            "txt" => {
-                // Find the maximum length of column names
-                let max_column_name_len = self
-                    .columns
-                    .iter()
-                    .map(|column| column.name.len())
-                    .max()
-                    .unwrap_or(0);
-
-                // Print the column names
-                for column in &self.columns {
-                    let padded_name =
-                        format!("{:>width$}", column.name, width = max_column_name_len);
-                    writer
-                        .write_all(padded_name.as_bytes())
-                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                    writer
-                        .write_all(b" ")
-                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                }
-                writer
-                    .write_all(b"\n")
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-
-                // Print the column data types
-                for column in &self.columns {
-                    let padded_data_type =
-                        format!("{:<width$}", column.data_type, width = max_column_name_len);
-                    writer
-                        .write_all(padded_data_type.as_bytes())
-                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                    writer
-                        .write_all(b" ")
-                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                }
-                writer
-                    .write_all(b"\n")
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-
-                // Print primary key information
-                for column in &self.columns {
-                    let primary_key_info = if column.is_primary_key {
-                        "prim_key".to_string()
-                    } else {
-                        "nt_prim_key".to_string()
-                    };
-                    let padded_primary_key_info =
-                        format!("{:<width$}", primary_key_info, width = max_column_name_len);
-                    writer
-                        .write_all(padded_primary_key_info.as_bytes())
-                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                    writer
-                        .write_all(b" ")
-                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                }
-                writer
-                    .write_all(b"\n")
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        self.write_to(&mut writer, format)
+    }
 
-                // Print a separator line
-                let separator_line: String = std::iter::repeat("-")
-                    .take(max_column_name_len * self.columns.len() + self.columns.len() - 1)
-                    .collect();
-                writer
-                    .write_all(separator_line.as_bytes())
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                writer
-                    .write_all(b"\n")
-                    .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-
-                // Get the maximum number of rows across all columns
-                let max_rows = self
-                    .columns
-                    .iter()
-                    .map(|column| column.data.len())
-                    .max()
-                    .unwrap_or(0);
-
-                // Print the data rows
-                for row_idx in 0..max_rows {
-                    for (_col_idx, column) in self.columns.iter().enumerate() {
-                        if row_idx < column.data.len() {
-                            let value = &column.data[row_idx];
-                            let padded_value =
-                                format!("{:<width$}", value, width = max_column_name_len);
-                            writer.write_all(padded_value.as_bytes()).map_err(|e| {
-                                Error::FileError(format!("Failed to write to file: {}", e))
-                            })?;
-                            writer.write_all(b" ").map_err(|e| {
-                                Error::FileError(format!("Failed to write to file: {}", e))
-                            })?;
-                        } else {
-                            let padding = " ".repeat(max_column_name_len);
-                            writer.write_all(padding.as_bytes()).map_err(|e| {
-                                Error::FileError(format!("Failed to write to file: {}", e))
-                            })?;
-                            writer.write_all(b" ").map_err(|e| {
-                                Error::FileError(format!("Failed to write to file: {}", e))
-                            })?;
-                        }
-                    }
-                    writer
-                        .write_all(b"\n")
-                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
-                }
-            }
-            _ => return Err(Error::InvalidFormat(format.to_string())),
+    /// Writes the table to any writer (a file, a socket, a `Vec<u8>`, ...) in the given format.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The destination of the rendered table.
+    /// * `format` - The `OutputFormat` to render: the CSV or TXT export formats, the grid printed by `show`, or a markdown table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the table was written and the writer flushed.
+    /// * `Err(Error)` if writing fails.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::FileError` - If writing to or flushing the writer fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{OutputFormat, Table};
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let mut buffer: Vec<u8> = Vec::new();
+    /// table.write_to(&mut buffer, OutputFormat::Csv).unwrap();
+    /// ```
+    pub fn write_to<W: Write>(&self, writer: &mut W, format: OutputFormat) -> Result<(), Error> {
+        match format {
+            OutputFormat::Csv => self.write_csv(writer, ','),
+            OutputFormat::Txt => self.write_txt(writer),
+            OutputFormat::Grid => writeln!(writer, "{}", self)
+                .and_then(|_| writer.flush())
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e))),
+            OutputFormat::Markdown => writeln!(writer, "{}", self.to_markdown())
+                .and_then(|_| writer.flush())
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e))),
         }
-
-        Ok(())
     }
 
     /// Imports a table stored in CSV or TXT format and defines a table variable from it.
@@ -274,108 +129,7 @@ impl Table {
         };
 
         match format.to_lowercase().as_str() {
-            "csv" => {
-                let reader = BufReader::new(file);
-                let mut lines = reader.lines().map(|line| line.unwrap());
-
-                // Read the column names
-                let column_names: Vec<String> = match lines.next() {
-                    Some(header_line) => header_line.split(',').map(|s| s.to_string()).collect(),
-                    None => return Err(Error::InvalidFormat("File is empty".to_string())),
-                };
-
-                // Read the column data types
-                let column_data_types: Vec<ColumnDataType> = match lines.next() {
-                    Some(data_types_line) => data_types_line
-                        .split(',')
-                        .map(|s| {
-                            Ok(match s {
-                                "Integer" => ColumnDataType::Integer,
-                                "Float" => ColumnDataType::Float,
-                                "Text" => ColumnDataType::Text,
-                                _ => {
-                                    return Err(Error::InvalidFormat(format!(
-                                        "Invalid data type: {}",
-                                        s
-                                    )))
-                                }
-                            })
-                        })
-                        .collect::<Result<Vec<_>, _>>()?,
-                    None => {
-                        return Err(Error::InvalidFormat(
-                            "File is missing data types".to_string(),
-                        ))
-                    }
-                };
-
-                // Read the primary key information
-                let primary_key_info: Vec<bool> = match lines.next() {
-                    Some(primary_key_line) => primary_key_line
-                        .split(',')
-                        .map(|s| {
-                            Ok(match s {
-                                "prim_key" => true,
-                                "nt_prim_key" => false,
-                                _ => {
-                                    return Err(Error::InvalidFormat(format!(
-                                        "Invalid primary key information: {}",
-                                        s
-                                    )))
-                                }
-                            })
-                        })
-                        .collect::<Result<Vec<_>, _>>()?,
-                    None => {
-                        return Err(Error::InvalidFormat(
-                            "File is missing primary key information".to_string(),
-                        ))
-                    }
-                };
-
-                // Create columns with the corresponding data types and primary key information
-                let mut columns: Vec<Column> = column_names
-                    .iter()
-                    .zip(column_data_types.iter())
-                    .zip(primary_key_info.iter())
-                    .map(|((name, data_type), is_primary_key)| {
-                        Column::new(name, data_type.clone(), None, *is_primary_key, None)
-                    })
-                    .collect();
-
-                // Read the data rows
-                for line in lines {
-                    let row_values: Vec<String> = line.split(',').map(|s| s.to_string()).collect();
-                    if row_values.len() != column_names.len() {
-                        return Err(Error::MismatchedColumnCount);
-                    }
-
-                    for (column, value_str) in columns.iter_mut().zip(row_values.into_iter()) {
-                        if value_str.trim().to_lowercase() == "null" {
-                            column.data.push(Value::Null);
-                        } else {
-                            match column.data_type {
-                                ColumnDataType::Integer => match value_str.parse::<i64>() {
-                                    Ok(value) => column.data.push(Value::Integer(value)),
-                                    Err(_) => {
-                                        return Err(Error::ParseError(column.data.len(), value_str))
-                                    }
-                                },
-                                ColumnDataType::Float => match value_str.parse::<f64>() {
-                                    Ok(value) => column.data.push(Value::Float(value)),
-                                    Err(_) => {
-                                        return Err(Error::ParseError(column.data.len(), value_str))
-                                    }
-                                },
-                                ColumnDataType::Text => column.data.push(Value::Text(value_str)),
-                            }
-                        }
-                    }
-                }
-
-                let table_name = file_name.to_string();
-                Table::new(&table_name, columns)
-            }
+            "csv" => Table::read_csv(BufReader::new(file), file_name, ','),
             "txt" => {
                 let reader = BufReader::new(file);
                 let mut lines = reader.lines().map(|line| line.unwrap());
@@ -398,6 +152,7 @@ impl Table {
                                 "Integer" => ColumnDataType::Integer,
                                 "Float" => ColumnDataType::Float,
                                 "Text" => ColumnDataType::Text,
+                                "Decimal" => ColumnDataType::Decimal,
                                 _ => {
                                     return Err(Error::InvalidFormat(format!(
                                         "Invalid data type: {}",
@@ -438,6 +193,17 @@ impl Table {
                     }
                 };
 
+                if column_data_types.len() != column_names.len()
+                    || primary_key_info.len() != column_names.len()
+                {
+                    return Err(Error::InvalidFormat(format!(
+                        "Header has {} columns, but {} data types and {} primary key entries",
+                        column_names.len(),
+                        column_data_types.len(),
+                        primary_key_info.len()
+                    )));
+                }
+
                 // the text file format has one line of separators, so we need to skip it
                 lines.next();
 
@@ -463,29 +229,422 @@ impl Table {
                         if value_str.trim().to_lowercase() == "null" {
                             column.data.push(Value::Null);
                         } else {
-                            match column.data_type {
-                                ColumnDataType::Integer => match value_str.parse::<i64>() {
-                                    Ok(value) => column.data.push(Value::Integer(value)),
-                                    Err(_) => {
-                                        return Err(Error::ParseError(column.data.len(), value_str))
-                                    }
-                                },
-                                ColumnDataType::Float => match value_str.parse::<f64>() {
-                                    Ok(value) => column.data.push(Value::Float(value)),
-                                    Err(_) => {
-                                        return Err(Error::ParseError(column.data.len(), value_str))
-                                    }
-                                },
-                                ColumnDataType::Text => column.data.push(Value::Text(value_str)),
-                            }
+                            let value = parse_column_value(column, value_str)?;
+                            column.data.push(value);
                         }
                     }
                 }
 
                 let table_name = file_name.to_string();
-                Table::new(&table_name, columns)
+                let table = Table::new(&table_name, columns)?;
+                table.validate()?;
+                Ok(table)
             }
             _ => Err(Error::InvalidFormat(format.to_string())),
         }
     }
+
+    /// Exports the table to a CSV file, separating fields with the given delimiter instead of `,`.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - A string representing the name of the file to export.
+    /// * `delimiter` - The character used to separate fields, e.g. `;` or `\t`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the export operation is successful.
+    /// * `Err(Error)` if an error occurs during the export operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::FileError` - If the file fails to create or write.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// table.export_table_with_delimiter("users.tsv", '\t').unwrap();
+    /// ```
+    pub fn export_table_with_delimiter(
+        &self,
+        file_name: &str,
+        delimiter: char,
+    ) -> Result<(), Error> {
+        let path = Path::new(file_name);
+        let file = match File::create(path) {
+            Ok(file) => file,
+            Err(e) => return Err(Error::FileError(format!("Failed to create file: {}", e))),
+        };
+        let mut writer = BufWriter::new(file);
+
+        self.write_csv(&mut writer, delimiter)
+    }
+
+    /// Imports a table stored in CSV format whose fields are separated by the given delimiter.
+    /// Only reads data that is stored in the same format as exported by `export_table_with_delimiter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - A string representing the name of the file to import.
+    /// * `delimiter` - The character used to separate fields, e.g. `;` or `\t`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - A `Table` instance created from the imported data if the import operation is successful.
+    /// * `Err(Error)` - An `Error` if the import operation fails.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::FileError` - If the file fails to open or read.
+    /// * `Error::InvalidFormat` - If the file has an invalid format.
+    /// * `Error::MismatchedColumnCount` - If the number of values in a row does not match the number of columns.
+    /// * `Error::ParseError` - If a value in the file cannot be parsed into the corresponding column's data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::import_table_with_delimiter("users.tsv", '\t').unwrap();
+    /// ```
+    pub fn import_table_with_delimiter(file_name: &str, delimiter: char) -> Result<Table, Error> {
+        let path = Path::new(file_name);
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) => return Err(Error::FileError(format!("Failed to open file: {}", e))),
+        };
+
+        Table::read_csv(BufReader::new(file), file_name, delimiter)
+    }
+
+    /// Writes the table in the TXT export format: the column names, data types and primary key
+    /// information, a separator line and the data rows, all padded into aligned columns.
+    fn write_txt<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        // Every column is as wide as its longest cell, including the data type and primary
+        // key information
+        let widths: Vec<usize> = self
+            .column_widths()
+            .into_iter()
+            .zip(&self.columns)
+            .map(|(width, column)| {
+                let primary_key_info_len = if column.is_primary_key {
+                    "prim_key".len()
+                } else {
+                    "nt_prim_key".len()
+                };
+                width
+                    .max(column.data_type.to_string().len())
+                    .max(primary_key_info_len)
+            })
+            .collect();
+
+        // Print the column names
+        for (column, width) in self.columns.iter().zip(&widths) {
+            let padded_name = format!("{:>width$}", column.name, width = width);
+            writer
+                .write_all(padded_name.as_bytes())
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+            writer
+                .write_all(b" ")
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        }
+        writer
+            .write_all(b"\n")
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+
+        // Print the column data types
+        for (column, width) in self.columns.iter().zip(&widths) {
+            let padded_data_type =
+                format!("{:<width$}", column.data_type.to_string(), width = width);
+            writer
+                .write_all(padded_data_type.as_bytes())
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+            writer
+                .write_all(b" ")
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        }
+        writer
+            .write_all(b"\n")
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+
+        // Print primary key information
+        for (column, width) in self.columns.iter().zip(&widths) {
+            let primary_key_info = if column.is_primary_key {
+                "prim_key".to_string()
+            } else {
+                "nt_prim_key".to_string()
+            };
+            let padded_primary_key_info = format!("{:<width$}", primary_key_info, width = width);
+            writer
+                .write_all(padded_primary_key_info.as_bytes())
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+            writer
+                .write_all(b" ")
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        }
+        writer
+            .write_all(b"\n")
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+
+        // Print a separator line
+        let separator_line = "-".repeat(separator_len(&widths));
+        writer
+            .write_all(separator_line.as_bytes())
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        writer
+            .write_all(b"\n")
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+
+        // Get the maximum number of rows across all columns
+        let max_rows = self
+            .columns
+            .iter()
+            .map(|column| column.data.len())
+            .max()
+            .unwrap_or(0);
+
+        // Print the data rows
+        for row_idx in 0..max_rows {
+            for (column, width) in self.columns.iter().zip(&widths) {
+                if row_idx < column.data.len() {
+                    let value = &column.data[row_idx];
+                    let padded_value = format!("{:<width$}", value, width = width);
+                    writer
+                        .write_all(padded_value.as_bytes())
+                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+                    writer
+                        .write_all(b" ")
+                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+                } else {
+                    let padding = " ".repeat(*width);
+                    writer
+                        .write_all(padding.as_bytes())
+                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+                    writer
+                        .write_all(b" ")
+                        .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+                }
+            }
+            writer
+                .write_all(b"\n")
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        }
+
+        writer
+            .flush()
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Writes the table in the CSV export format (header, data types, primary key information
+    /// and data rows), separating fields with `delimiter`.
+    fn write_csv<W: Write>(&self, writer: &mut W, delimiter: char) -> Result<(), Error> {
+        let delimiter = delimiter.to_string();
+
+        // Write column names as header
+        let header = self
+            .columns
+            .iter()
+            .map(|c| c.name.clone())
+            .collect::<Vec<_>>()
+            .join(&delimiter);
+        writer
+            .write_all(header.as_bytes())
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        writer
+            .write_all(b"\n")
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+
+        // Write column data types
+        let data_types = self
+            .columns
+            .iter()
+            .map(|c| format!("{}", c.data_type))
+            .collect::<Vec<_>>()
+            .join(&delimiter);
+        writer
+            .write_all(data_types.as_bytes())
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        writer
+            .write_all(b"\n")
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+
+        // Write primary key information
+        let primary_key_info = self
+            .columns
+            .iter()
+            .map(|c| {
+                if c.is_primary_key {
+                    "prim_key".to_string()
+                } else {
+                    "nt_prim_key".to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&delimiter);
+        writer
+            .write_all(primary_key_info.as_bytes())
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        writer
+            .write_all(b"\n")
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+
+        // Write data rows
+        let max_rows = self
+            .columns
+            .iter()
+            .map(|column| column.data.len())
+            .max()
+            .unwrap_or(0);
+
+        for row_idx in 0..max_rows {
+            let row_data: Vec<String> = self
+                .columns
+                .iter()
+                .map(|column| {
+                    if row_idx < column.data.len() {
+                        format!("{}", column.data[row_idx])
+                    } else {
+                        "".to_string()
+                    }
+                })
+                .collect();
+
+            let row_string = row_data.join(&delimiter);
+            writer
+                .write_all(row_string.as_bytes())
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+            writer
+                .write_all(b"\n")
+                .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+        }
+
+        writer
+            .flush()
+            .map_err(|e| Error::FileError(format!("Failed to write to file: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Reads a table stored in the CSV export format, splitting fields on `delimiter`.
+    fn read_csv<R: BufRead>(reader: R, file_name: &str, delimiter: char) -> Result<Table, Error> {
+        let mut lines = reader.lines().map(|line| line.unwrap());
+
+        // Read the column names, e.g. a trailing delimiter left by a spreadsheet export would
+        // otherwise add a column without a name
+        let column_names: Vec<String> = match lines.next() {
+            Some(header_line) => header_line
+                .split(delimiter)
+                .map(|s| s.trim())
+                .enumerate()
+                .map(|(idx, s)| {
+                    if s.is_empty() {
+                        return Err(Error::InvalidFormat(format!(
+                            "Empty column name at position {} of the header",
+                            idx
+                        )));
+                    }
+                    Ok(s.to_string())
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => return Err(Error::InvalidFormat("File is empty".to_string())),
+        };
+
+        // Read the column data types
+        let column_data_types: Vec<ColumnDataType> = match lines.next() {
+            Some(data_types_line) => data_types_line
+                .split(delimiter)
+                .map(|s| s.trim())
+                .map(|s| {
+                    Ok(match s {
+                        "Integer" => ColumnDataType::Integer,
+                        "Float" => ColumnDataType::Float,
+                        "Text" => ColumnDataType::Text,
+                        "Decimal" => ColumnDataType::Decimal,
+                        _ => return Err(Error::InvalidFormat(format!("Invalid data type: {}", s))),
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => {
+                return Err(Error::InvalidFormat(
+                    "File is missing data types".to_string(),
+                ))
+            }
+        };
+
+        // Read the primary key information
+        let primary_key_info: Vec<bool> = match lines.next() {
+            Some(primary_key_line) => primary_key_line
+                .split(delimiter)
+                .map(|s| s.trim())
+                .map(|s| {
+                    Ok(match s {
+                        "prim_key" => true,
+                        "nt_prim_key" => false,
+                        _ => {
+                            return Err(Error::InvalidFormat(format!(
+                                "Invalid primary key information: {}",
+                                s
+                            )))
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => {
+                return Err(Error::InvalidFormat(
+                    "File is missing primary key information".to_string(),
+                ))
+            }
+        };
+
+        if column_data_types.len() != column_names.len()
+            || primary_key_info.len() != column_names.len()
+        {
+            return Err(Error::InvalidFormat(format!(
+                "Header has {} columns, but {} data types and {} primary key entries",
+                column_names.len(),
+                column_data_types.len(),
+                primary_key_info.len()
+            )));
+        }
+
+        // Create columns with the corresponding data types and primary key information
+        let mut columns: Vec<Column> = column_names
+            .iter()
+            .zip(column_data_types.iter())
+            .zip(primary_key_info.iter())
+            .map(|((name, data_type), is_primary_key)| {
+                Column::new(name, *data_type, None, *is_primary_key, None)
+            })
+            .collect();
+
+        // Read the data rows
+        for line in lines {
+            let row_values: Vec<String> = line.split(delimiter).map(|s| s.to_string()).collect();
+            if row_values.len() != column_names.len() {
+                return Err(Error::MismatchedColumnCount);
+            }
+
+            for (column, value_str) in columns.iter_mut().zip(row_values) {
+                if value_str.trim().to_lowercase() == "null" {
+                    column.data.push(Value::Null);
+                } else {
+                    let value = parse_column_value(column, value_str)?;
+                    column.data.push(value);
+                }
+            }
+        }
+
+        let table = Table::new(file_name, columns)?;
+        table.validate()?;
+        Ok(table)
+    }
 }
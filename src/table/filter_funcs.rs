@@ -1,6 +1,6 @@
-use crate::table::helpers::evaluate_nested_conditions;
-use crate::table::{Error, NestedCondition, Table};
-use std::collections::HashSet;
+use crate::column::Value;
+use crate::table::helpers::{evaluate_nested_conditions, validate_nested_conditions};
+use crate::table::{Error, NestedCondition, Row, Table};
 
 impl Table {
     /// Filters the table rows based on the provided nested condition structure and projects the filtered rows with the specified columns.
@@ -32,12 +32,12 @@ impl Table {
     /// let mut table = Table::new(
     ///     "users",
     ///     vec![
-    ///         Column::new("user_id", ColumnDataType::Integer, None),
-    ///         Column::new("user_name", ColumnDataType::Text, None),
-    ///         Column::new("age", ColumnDataType::Integer, None),
-    ///         Column::new("score", ColumnDataType::Float, None),
+    ///         Column::new("user_id", ColumnDataType::Integer, None, false, None),
+    ///         Column::new("user_name", ColumnDataType::Text, None, false, None),
+    ///         Column::new("age", ColumnDataType::Integer, None, false, None),
+    ///         Column::new("score", ColumnDataType::Float, None, false, None),
     ///     ],
-    /// );
+    /// ).unwrap();
     ///
     /// // Insert some initial data
     /// table.insert(vec!["1".to_string(), "Alice".to_string(), "25".to_string(), "85.5".to_string()]).unwrap();
@@ -60,54 +60,58 @@ impl Table {
         column_names: Vec<String>,
         nested_condition: NestedCondition,
     ) -> Result<(), Error> {
-        // Create a new table with the same columns and data types
-        let mut filtered_table = self.copy();
-
-        // Filter the rows based on the nested condition
-        let columns_clone = self.columns.clone();
-        let mut row_indices_to_remove = Vec::new();
-
-        for (row_idx, _) in self.columns[0].data.iter().enumerate() {
-            let satisfies_condition =
-                evaluate_nested_conditions(&nested_condition, &columns_clone, row_idx)?;
-
-            if !satisfies_condition {
-                row_indices_to_remove.push(row_idx);
-            }
-        }
-
-        for column in &mut filtered_table.columns {
-            column.data = column
-                .data
-                .iter()
-                .enumerate()
-                .filter_map(|(idx, value)| {
-                    if !row_indices_to_remove.contains(&idx) {
-                        Some(value.clone())
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-        }
-
-        // Check if all provided column names exist in the table
-        let column_names_set: HashSet<String> = column_names.iter().cloned().collect();
-        let existing_columns: HashSet<String> =
-            self.columns.iter().map(|c| c.name.clone()).collect();
-        let non_existing_columns: Vec<String> = column_names_set
-            .difference(&existing_columns)
-            .cloned()
-            .collect();
-
-        if !non_existing_columns.is_empty() {
-            return Err(Error::NonExistingColumns(non_existing_columns));
-        }
-
-        // Project the filtered table with the provided column names
-        filtered_table.project(column_names)?;
+        self.filter_and_project_with_aliases(
+            column_names.into_iter().map(|name| (name, None)).collect(),
+            nested_condition,
+        )
+    }
 
-        Ok(())
+    /// Filters the table rows based on the provided nested condition structure and projects the filtered rows with the specified columns, renaming the ones that are given an alias.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - A vector of `(source_column, alias)` pairs. The output header uses the alias when one is given and the source column name otherwise.
+    /// * `nested_condition` - A `NestedCondition` enum representing the nested condition structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the filtering and projection operation is successful.
+    /// * `Err(Error)` if an error occurs during the operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumns` - If one or more of the source columns do not exist in the table.
+    /// * `Error::DuplicateOutputColumn` - If two projected columns end up with the same output name.
+    /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
+    /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let table = Table::new("users", /* ... */);
+    ///
+    /// // Show the names of users older than 25 under a "name" header
+    /// let nested_condition = NestedCondition::Condition(
+    ///     "age".to_string(),
+    ///     ">".to_string(),
+    ///     "25".to_string(),
+    /// );
+    /// table.filter_and_project_with_aliases(
+    ///     vec![("user_name".to_string(), Some("name".to_string()))],
+    ///     nested_condition,
+    /// ).unwrap();
+    /// ```
+    pub fn filter_and_project_with_aliases(
+        &self,
+        columns: Vec<(String, Option<String>)>,
+        nested_condition: NestedCondition,
+    ) -> Result<(), Error> {
+        self.filter(&nested_condition)?
+            .project_with_aliases(columns)
     }
 
     /// Filters the table rows based on the provided nested condition structure and prints the filtered rows to the console.
@@ -137,12 +141,12 @@ impl Table {
     /// let mut table = Table::new(
     ///     "users",
     ///     vec![
-    ///         Column::new("user_id", ColumnDataType::Integer, None),
-    ///         Column::new("user_name", ColumnDataType::Text, None),
-    ///         Column::new("age", ColumnDataType::Integer, None),
-    ///         Column::new("score", ColumnDataType::Float, None),
+    ///         Column::new("user_id", ColumnDataType::Integer, None, false, None),
+    ///         Column::new("user_name", ColumnDataType::Text, None, false, None),
+    ///         Column::new("age", ColumnDataType::Integer, None, false, None),
+    ///         Column::new("score", ColumnDataType::Float, None, false, None),
     ///     ],
-    /// );
+    /// ).unwrap();
     ///
     /// // Insert some initial data
     /// table.insert(vec!["1".to_string(), "Alice".to_string(), "25".to_string(), "85.5".to_string()]).unwrap();
@@ -168,50 +172,248 @@ impl Table {
         &self,
         nested_condition: NestedCondition,
     ) -> Result<(), Error> {
-        let columns_clone = self.columns.clone();
-
-        // Find the maximum length of column names
-        let max_column_name_len = self
-            .columns
-            .iter()
-            .map(|column| column.name.len())
-            .max()
-            .unwrap_or(0);
-
-        // Print the column names
-        for column in &self.columns {
-            let padded_name = format!("{:>width$}", column.name, width = max_column_name_len);
-            print!("{} ", padded_name);
+        println!("{}", self.filter(&nested_condition)?);
+
+        Ok(())
+    }
+
+    /// Returns a new table holding only the records that satisfy the provided nested condition structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `nested_condition` - A reference to the `NestedCondition` enum representing the nested condition structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - The filtered table, with the same columns and metadata as this one.
+    /// * `Err(Error)` - An error if the condition is invalid, even when the table is empty.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
+    /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+    /// * `Error::InvalidConditionValue` - If a numeric column is compared against a non-numeric value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let adults = table
+    ///     .filter(&NestedCondition::Condition(
+    ///         "age".to_string(),
+    ///         ">=".to_string(),
+    ///         "18".to_string(),
+    ///     ))
+    ///     .unwrap();
+    /// ```
+    pub fn filter(&self, nested_condition: &NestedCondition) -> Result<Table, Error> {
+        let nested_condition = &*self.resolve_condition(nested_condition)?;
+        validate_nested_conditions(nested_condition, &self.columns)?;
+
+        let mut row_indices = Vec::new();
+        for row_idx in 0..self.row_count() {
+            if evaluate_nested_conditions(nested_condition, &self.columns, row_idx)? {
+                row_indices.push(row_idx);
+            }
         }
-        println!();
-
-        // Print a separator line
-        let separator_line: String = std::iter::repeat("-")
-            .take(max_column_name_len * self.columns.len() + self.columns.len() - 1)
-            .collect();
-        println!("{}", separator_line);
-
-        // Iterate over each row and print rows that satisfy the conditions
-        for (row_idx, _) in self.columns[0].data.iter().enumerate() {
-            let satisfies_condition =
-                evaluate_nested_conditions(&nested_condition, &columns_clone, row_idx)?;
-
-            if satisfies_condition {
-                for (_col_idx, column) in self.columns.iter().enumerate() {
-                    if row_idx < column.data.len() {
-                        let value = &column.data[row_idx];
-                        let padded_value =
-                            format!("{:>width$}", value, width = max_column_name_len);
-                        print!("{} ", padded_value);
-                    } else {
-                        let padding = " ".repeat(max_column_name_len);
-                        print!("{} ", padding);
+
+        Ok(self.with_rows(&row_indices))
+    }
+
+    /// Returns the requested columns of the records that satisfy an optional nested condition
+    /// structure, as rows of values instead of printing them.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_names` - The columns to return, in order. An empty vector returns every column.
+    /// * `nested_condition` - An optional `NestedCondition`; when `None`, every record is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Vec<Value>>)` - One inner vector per matching record, holding the requested values in order.
+    /// * `Err(Error)` - An error if a column or the condition is invalid.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumns` - If one or more of the provided column names do not exist in the table.
+    /// * `Error::DuplicateOutputColumn` - If a column is requested more than once.
+    /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
+    /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+    /// * `Error::InvalidConditionValue` - If a numeric column is compared against a non-numeric value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let rows = table
+    ///     .select(
+    ///         vec!["user_name".to_string()],
+    ///         Some(NestedCondition::Condition(
+    ///             "age".to_string(),
+    ///             ">".to_string(),
+    ///             "25".to_string(),
+    ///         )),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn select(
+        &self,
+        column_names: Vec<String>,
+        nested_condition: Option<NestedCondition>,
+    ) -> Result<Vec<Vec<Value>>, Error> {
+        // Only a projection needs a table of its own, every column is read in place
+        let projected_table = if column_names.is_empty() {
+            None
+        } else {
+            Some(self.project_into_table(column_names)?)
+        };
+        let columns = projected_table
+            .as_ref()
+            .map_or(&self.columns, |table| &table.columns);
+
+        // Filter on the original table so the condition can use columns that are not returned
+        let row_indices: Vec<usize> = match &nested_condition {
+            Some(condition) => {
+                let condition = &*self.resolve_condition(condition)?;
+                validate_nested_conditions(condition, &self.columns)?;
+
+                let mut row_indices = Vec::new();
+                for row_idx in 0..self.row_count() {
+                    if evaluate_nested_conditions(condition, &self.columns, row_idx)? {
+                        row_indices.push(row_idx);
                     }
                 }
-                println!();
+                row_indices
             }
-        }
+            None => (0..self.row_count()).collect(),
+        };
 
-        Ok(())
+        Ok(row_indices
+            .into_iter()
+            .map(|row_idx| {
+                columns
+                    .iter()
+                    .map(|column| column.data.get(row_idx).cloned().unwrap_or(Value::Null))
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Like `select`, but returns every matching record as a `Row`, so values can be looked up
+    /// by column name.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_names` - The columns to return, in order. An empty vector returns every column.
+    /// * `nested_condition` - An optional `NestedCondition`; when `None`, every record is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Row>)` - One row per matching record, holding the requested columns in order.
+    /// * `Err(Error)` - An error if a column or the condition is invalid.
+    ///
+    /// # Errors
+    ///
+    /// The same as `select`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let condition = NestedCondition::parse("age > 25").unwrap();
+    /// for row in table.select_rows(vec![], Some(condition)).unwrap() {
+    ///     println!("{:?}", row.get("user_name"));
+    /// }
+    /// ```
+    pub fn select_rows(
+        &self,
+        column_names: Vec<String>,
+        nested_condition: Option<NestedCondition>,
+    ) -> Result<Vec<Row>, Error> {
+        // Rows hold the stored column names, whatever casing they were requested with
+        let row_column_names = if column_names.is_empty() {
+            self.columns.iter().map(|c| c.name.clone()).collect()
+        } else {
+            self.resolve_column_names(column_names.clone())?
+        };
+
+        Ok(self
+            .select(column_names, nested_condition)?
+            .into_iter()
+            .map(|values| {
+                Row::from(
+                    row_column_names
+                        .iter()
+                        .cloned()
+                        .zip(values)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect())
+    }
+
+    /// Returns the indices of the records where at least one text column contains a substring,
+    /// in table order. The search is case-sensitive, see `search_ci`, and skips NULL values and
+    /// columns of any other data type.
+    ///
+    /// # Arguments
+    ///
+    /// * `needle` - The substring to look for. An empty needle matches every record with a
+    ///   non-null text value.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<usize>` - The indices of the matching records, empty if none match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let matches = table.search("@example.com");
+    /// println!("{} users with an example.com address", matches.len());
+    /// ```
+    pub fn search(&self, needle: &str) -> Vec<usize> {
+        self.search_text_columns(|text| text.contains(needle))
+    }
+
+    /// The same as `search`, but letters match whatever their case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let matches = table.search_ci("alice");
+    /// ```
+    pub fn search_ci(&self, needle: &str) -> Vec<usize> {
+        let needle = needle.to_lowercase();
+        self.search_text_columns(|text| text.to_lowercase().contains(&needle))
+    }
+
+    /// Keeps the indices of the records with a text value matching the predicate.
+    fn search_text_columns(&self, matches: impl Fn(&str) -> bool) -> Vec<usize> {
+        (0..self.row_count())
+            .filter(|&row_idx| {
+                self.columns
+                    .iter()
+                    .any(|column| match &column.data[row_idx] {
+                        Value::Text(text) => matches(text),
+                        _ => false,
+                    })
+            })
+            .collect()
     }
 }
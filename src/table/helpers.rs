@@ -1,5 +1,100 @@
-use crate::column::{Column, ColumnDataType, Value};
-use crate::table::{operators::Operator, Error, NestedCondition};
+use crate::column::{compare_decimals, Column, ColumnDataType, Value};
+use crate::table::operators::{apply_operator, Operator};
+use crate::table::{Error, NestedCondition};
+use std::collections::HashSet;
+
+/// Parses a value into the data type of a column. "null" is not special-cased, callers decide
+/// whether it stands for NULL.
+///
+/// # Errors
+///
+/// * `Error::ParseError` - If the value cannot be parsed into the data type of the column.
+/// * `Error::NonFiniteFloat` - If a float parses as NaN or infinity, which columns don't hold
+///   since NaN isn't even equal to itself.
+pub(crate) fn parse_column_value(column: &Column, value_str: String) -> Result<Value, Error> {
+    let value = match column.data_type {
+        ColumnDataType::Integer => value_str.parse::<i64>().ok().map(Value::Integer),
+        ColumnDataType::Float => match value_str.parse::<f64>() {
+            Ok(value) if !value.is_finite() => {
+                return Err(Error::NonFiniteFloat(column.name.clone(), value_str))
+            }
+            result => result.ok().map(Value::Float),
+        },
+        ColumnDataType::Decimal => Value::parse_decimal(&value_str),
+        ColumnDataType::Text => return Ok(Value::Text(value_str)),
+    };
+
+    value.ok_or_else(|| Error::ParseError {
+        column_name: column.name.clone(),
+        value: value_str,
+        expected: column.data_type,
+    })
+}
+
+/// Parses a value like `parse_column_value`, but reads "null" (ignoring case and surrounding
+/// spaces) as NULL, the way inserts and updates take their values.
+pub(crate) fn parse_column_value_or_null(
+    column: &Column,
+    value_str: String,
+) -> Result<Value, Error> {
+    if value_str.trim().to_lowercase() == "null" {
+        Ok(Value::Null)
+    } else {
+        parse_column_value(column, value_str)
+    }
+}
+
+/// The value a new column takes in the existing records of a table: the parsed `fill` value,
+/// or the column's default value when no fill is given, or NULL without one.
+///
+/// # Errors
+///
+/// * `Error::ParseError` - If the fill value cannot be parsed into the data type of the column.
+pub(crate) fn column_fill_value(column: &Column, fill: Option<String>) -> Result<Value, Error> {
+    match fill {
+        Some(fill) => parse_column_value_or_null(column, fill),
+        None => Ok(column.default_value.clone().unwrap_or(Value::Null)),
+    }
+}
+
+/// Checks that a table or column name can be used in conditions and exports: it is not empty,
+/// has no leading or trailing whitespace, no control characters (e.g. newlines), and at most
+/// `max_length` characters.
+///
+/// # Errors
+///
+/// * `Error::InvalidName` - With the name and the rule it breaks.
+pub(crate) fn validate_name(name: &str, max_length: usize) -> Result<(), Error> {
+    let reason = if name.is_empty() {
+        "it is empty".to_string()
+    } else if name.trim() != name {
+        "it has leading or trailing whitespace".to_string()
+    } else if name.chars().any(char::is_control) {
+        "it contains a control character".to_string()
+    } else if name.chars().count() > max_length {
+        format!("it is longer than {} characters", max_length)
+    } else {
+        return Ok(());
+    };
+
+    Err(Error::InvalidName(name.to_string(), reason))
+}
+
+/// Checks that no column is named twice in the column list of an insert.
+///
+/// # Errors
+///
+/// * `Error::DuplicateColumnInInsert` - With the first column that is named again.
+pub(crate) fn check_insert_columns_unique(column_names: &[String]) -> Result<(), Error> {
+    let mut seen = HashSet::new();
+    for column_name in column_names {
+        if !seen.insert(column_name) {
+            return Err(Error::DuplicateColumnInInsert(column_name.clone()));
+        }
+    }
+
+    Ok(())
+}
 
 /// Evaluates a nested condition structure against a specific row in the table.
 ///
@@ -20,6 +115,7 @@ use crate::table::{operators::Operator, Error, NestedCondition};
 ///
 /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
 /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+/// * `Error::InvalidConditionValue` - If a numeric column is compared against a non-numeric value.
 pub(crate) fn evaluate_nested_conditions(
     condition: &NestedCondition,
     columns: &[Column],
@@ -37,17 +133,23 @@ pub(crate) fn evaluate_nested_conditions(
             let operator = Operator::from_str(&operator)
                 .map_err(|_e| Error::InvalidOperator(operator.clone()))?;
 
+            validate_condition_value(column_name, cond_column_data_type, &operator, value)?;
+
+            // A column shorter than the table reads as NULL in the records it has no value for
             let ref_value = columns
                 .iter()
                 .find(|c| c.name == *column_name)
                 .ok_or(Error::NonExistingColumn(column_name.clone()))?
                 .data
                 .get(row_idx)
-                .cloned();
+                .unwrap_or(&Value::Null);
 
-            Ok(ref_value.map_or(false, |v| {
-                satisfies_condition(&v, cond_column_data_type, &value, &operator)
-            }))
+            Ok(satisfies_condition(
+                ref_value,
+                cond_column_data_type,
+                value,
+                &operator,
+            ))
         }
         NestedCondition::And(left, right) => {
             let left_result = evaluate_nested_conditions(left, columns, row_idx)?;
@@ -59,6 +161,7 @@ pub(crate) fn evaluate_nested_conditions(
             let right_result = evaluate_nested_conditions(right, columns, row_idx)?;
             Ok(left_result || right_result)
         }
+        NestedCondition::Not(inner) => Ok(!evaluate_nested_conditions(inner, columns, row_idx)?),
     }
 }
 
@@ -74,40 +177,168 @@ pub(crate) fn evaluate_nested_conditions(
 /// # Returns
 ///
 /// * `bool` - `true` if the value satisfies the condition, `false` otherwise.
+///
+/// # NULL handling
+///
+/// Comparisons follow SQL semantics: comparing a NULL value with any operator other than
+/// `IS NULL`/`IS NOT NULL` is unknown and therefore never matches, so `age != 30` does not
+/// return rows where `age` is NULL. Use the `IS NULL` operator (the condition value is ignored)
+/// to find those rows, and `IS NOT NULL` for the opposite.
 pub fn satisfies_condition(
     value: &Value,
     cond_column_data_type: ColumnDataType,
     cond_value: &str,
     operator: &Operator,
 ) -> bool {
-    match (value, &cond_column_data_type) {
-        (Value::Integer(val), ColumnDataType::Integer) => {
-            let cond_value: i64 = cond_value.parse().unwrap();
-            match operator {
-                Operator::Equal => val == &cond_value,
-                Operator::NotEqual => val != &cond_value,
-                Operator::LessThan => val < &cond_value,
-                Operator::GreaterThan => val > &cond_value,
-                Operator::LessThanOrEqual => val <= &cond_value,
-                Operator::GreaterThanOrEqual => val >= &cond_value,
+    match (value, operator) {
+        (Value::Null, Operator::IsNull) => return true,
+        (Value::Null, _) => return false,
+        (_, Operator::IsNull) => return false,
+        (_, Operator::IsNotNull) => return true,
+        _ => {}
+    }
+
+    // Every data type only has to order the value relative to the condition value
+    let ordering = match (value, &cond_column_data_type) {
+        (Value::Integer(val), ColumnDataType::Integer) => match cond_value.trim().parse::<i64>() {
+            Ok(cond_value) => Some(val.cmp(&cond_value)),
+            // Not an integer literal (e.g. "30.5"), so compare as floats instead
+            Err(_) => match cond_value.trim().parse::<f64>() {
+                Ok(cond_value) => (*val as f64).partial_cmp(&cond_value),
+                Err(_) => return false,
+            },
+        },
+        (Value::Float(val), ColumnDataType::Float) => match cond_value.trim().parse::<f64>() {
+            Ok(cond_value) => val.partial_cmp(&cond_value),
+            Err(_) => return false,
+        },
+        (Value::Decimal(..), ColumnDataType::Decimal) => match Value::parse_decimal(cond_value) {
+            Some(cond_value) => compare_decimals(value, &cond_value),
+            None => return false,
+        },
+        (Value::Text(val), ColumnDataType::Text) => match operator {
+            Operator::Equal | Operator::NotEqual => Some(val.as_str().cmp(cond_value)),
+            _ => return false, // Other operators not supported for Text data type
+        },
+        _ => return false, // Unsupported data type or value combination
+    };
+
+    apply_operator(ordering, operator)
+}
+
+/// Validates a nested condition structure against the columns of a table without evaluating it.
+///
+/// # Arguments
+///
+/// * `condition` - A reference to the `NestedCondition` enum representing the nested condition structure.
+/// * `columns` - A slice of `Column` instances representing the columns in the table.
+///
+/// # Returns
+///
+/// * `Ok(())` - If every column referenced in the condition exists, every operator is supported
+///   and every value can be compared against its column.
+/// * `Err(Error)` - The first problem found while walking the condition.
+///
+/// # Errors
+///
+/// This function can return the following errors:
+///
+/// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
+/// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+/// * `Error::InvalidConditionValue` - If a numeric column is compared against a non-numeric value.
+pub(crate) fn validate_nested_conditions(
+    condition: &NestedCondition,
+    columns: &[Column],
+) -> Result<(), Error> {
+    match condition {
+        NestedCondition::Condition(column_name, operator, value) => {
+            let column = columns
+                .iter()
+                .find(|c| c.name == *column_name)
+                .ok_or(Error::NonExistingColumn(column_name.clone()))?;
+
+            let operator = Operator::from_str(operator)
+                .map_err(|_e| Error::InvalidOperator(operator.clone()))?;
+
+            validate_condition_value(column_name, column.data_type, &operator, value)
+        }
+        NestedCondition::And(left, right) | NestedCondition::Or(left, right) => {
+            validate_nested_conditions(left, columns)?;
+            validate_nested_conditions(right, columns)
+        }
+        NestedCondition::Not(inner) => validate_nested_conditions(inner, columns),
+    }
+}
+
+/// Checks that a condition value can be compared against a column of the given data type.
+/// Numeric columns accept both integer and float literals, so `age = 30.0` is valid for an
+/// Integer column and `score > 85` for a Float column. `IS NULL`/`IS NOT NULL` ignore the value.
+///
+/// # Errors
+///
+/// * `Error::InvalidConditionValue` - If the value of a numeric condition is not a number.
+pub(crate) fn validate_condition_value(
+    column_name: &str,
+    data_type: ColumnDataType,
+    operator: &Operator,
+    value: &str,
+) -> Result<(), Error> {
+    if matches!(operator, Operator::IsNull | Operator::IsNotNull) {
+        return Ok(());
+    }
+
+    match data_type {
+        ColumnDataType::Integer | ColumnDataType::Float => {
+            if value.trim().parse::<f64>().is_err() {
+                return Err(Error::InvalidConditionValue(
+                    column_name.to_string(),
+                    value.to_string(),
+                ));
             }
+            Ok(())
         }
-        (Value::Float(val), ColumnDataType::Float) => {
-            let cond_value: f64 = cond_value.parse().unwrap();
-            match operator {
-                Operator::Equal => val == &cond_value,
-                Operator::NotEqual => val != &cond_value,
-                Operator::LessThan => val < &cond_value,
-                Operator::GreaterThan => val > &cond_value,
-                Operator::LessThanOrEqual => val <= &cond_value,
-                Operator::GreaterThanOrEqual => val >= &cond_value,
+        ColumnDataType::Decimal => {
+            if Value::parse_decimal(value).is_none() {
+                return Err(Error::InvalidConditionValue(
+                    column_name.to_string(),
+                    value.to_string(),
+                ));
             }
+            Ok(())
         }
-        (Value::Text(val), ColumnDataType::Text) => match operator {
-            Operator::Equal => val == cond_value,
-            Operator::NotEqual => val != cond_value,
-            _ => false, // Other operators not supported for Text data type
-        },
-        _ => false, // Unsupported data type or value combination
+        ColumnDataType::Text => Ok(()),
+    }
+}
+
+/// Small xorshift64 pseudo-random number generator, used where reproducible randomness is
+/// needed (e.g. row sampling) without pulling in a dependency. Not suitable for cryptography.
+pub(crate) struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        // xorshift never leaves the all-zero state, so replace a zero seed with a fixed constant
+        XorShift64 {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Returns a number in `0..bound`. `bound` must be greater than 0.
+    pub(crate) fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
     }
 }
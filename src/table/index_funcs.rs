@@ -0,0 +1,310 @@
+use crate::column::{ColumnDataType, Value};
+use crate::table::helpers::validate_nested_conditions;
+use crate::table::operators::{apply_operator, Operator};
+use crate::table::{Error, NestedCondition, Table};
+use std::collections::HashMap;
+
+impl Table {
+    /// Creates a secondary index on a column, mapping every value to the records holding it.
+    /// The index is kept up to date by inserts, updates and deletes, and is used by
+    /// `filter_indexed` for equality conditions on the column. Creating an index that already
+    /// exists rebuilds it.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - The name of the column to index.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the index was created.
+    /// * `Err(Error)` if the column does not exist.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::NonExistingColumn` - If the column does not exist in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// table.create_index("user_name").unwrap();
+    /// ```
+    pub fn create_index(&mut self, column_name: &str) -> Result<(), Error> {
+        let column_idx = self.column_position(column_name)?;
+        let index = self.build_index(column_idx);
+        self.indexes
+            .insert(self.columns[column_idx].name.clone(), index);
+        Ok(())
+    }
+
+    /// Drops the secondary index on a column, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - The name of the indexed column.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the column has no index anymore.
+    /// * `Err(Error)` if the column does not exist.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::NonExistingColumn` - If the column does not exist in the table.
+    pub fn drop_index(&mut self, column_name: &str) -> Result<(), Error> {
+        let column_idx = self.column_position(column_name)?;
+        self.indexes.remove(&self.columns[column_idx].name);
+        Ok(())
+    }
+
+    /// Returns whether the column has a secondary index.
+    pub fn has_index(&self, column_name: &str) -> bool {
+        self.resolve_column_name(column_name)
+            .is_ok_and(|column_name| self.indexes.contains_key(column_name))
+    }
+
+    /// Rebuilds every secondary index from scratch and shrinks the storage of the columns and
+    /// indexes to fit the records the table holds now. Inserts grow the columns ahead of their
+    /// records and `truncate` keeps their memory for the next inserts, so call this once a
+    /// table is done shrinking and won't grow back soon, e.g. after purging old records.
+    /// Primary key and unique checks scan the column data, so there is no separate primary key
+    /// index to rebuild.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let mut table = Table::new("events", /* ... */);
+    /// table
+    ///     .delete_with_nested_conditions(&NestedCondition::parse("year < 2020").unwrap())
+    ///     .unwrap();
+    /// table.compact();
+    /// ```
+    pub fn compact(&mut self) {
+        for column in self.columns.iter_mut() {
+            column.data.shrink_to_fit();
+        }
+        // The primary key is kept for its metadata, any data it was cloned with is stale
+        if let Some(primary_key) = &mut self.primary_key_column {
+            primary_key.data = Vec::new();
+        }
+
+        self.rebuild_indexes();
+        for index in self.indexes.values_mut() {
+            index.shrink_to_fit();
+        }
+    }
+
+    /// Filters the table like `filter`, but answers a simple equality condition on an indexed
+    /// column, e.g. `NestedCondition::Condition("user_name", "=", "Alice")`, from the index
+    /// instead of scanning every record. Any other condition falls back to the scan, so the
+    /// result is always the same as `filter`'s.
+    ///
+    /// # Arguments
+    ///
+    /// * `nested_condition` - A reference to the `NestedCondition` enum representing the nested condition structure.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - The filtered table, with the same columns and metadata as this one.
+    /// * `Err(Error)` - An error if the condition is invalid.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
+    /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+    /// * `Error::InvalidConditionValue` - If a numeric column is compared against a non-numeric value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// table.create_index("user_name").unwrap();
+    /// let alices = table
+    ///     .filter_indexed(&NestedCondition::Condition(
+    ///         "user_name".to_string(),
+    ///         "=".to_string(),
+    ///         "Alice".to_string(),
+    ///     ))
+    ///     .unwrap();
+    /// ```
+    pub fn filter_indexed(&self, nested_condition: &NestedCondition) -> Result<Table, Error> {
+        let nested_condition = &*self.resolve_condition(nested_condition)?;
+        validate_nested_conditions(nested_condition, &self.columns)?;
+
+        if let NestedCondition::Condition(column_name, operator, value) = nested_condition {
+            if let Some(index) = self.indexes.get(column_name) {
+                let data_type = self.columns[self.column_position(column_name)?].data_type;
+                let key = index_key(data_type, value);
+
+                if let (Ok(Operator::Equal), Some(key)) = (Operator::from_str(operator), key) {
+                    let row_indices = index.get(&key).map_or(&[][..], |rows| rows.as_slice());
+                    return Ok(self.with_rows(row_indices));
+                }
+            }
+        }
+
+        self.filter(nested_condition)
+    }
+
+    /// Filters the table on a range of primary key values, e.g. `id > 1000`. Records keep
+    /// their insertion order rather than being sorted by key, so this is a linear scan; for an
+    /// integer key it compares the raw integers instead of going through the generic condition
+    /// evaluation, and any other key type falls back to `filter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - One of the range operators `>`, `>=`, `<` and `<=`.
+    /// * `value` - The boundary value, compared against the primary key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - The records whose primary key lies in the range, in table order.
+    /// * `Err(Error)` - An error if the table has no primary key, or the operator or value is invalid.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NoPrimaryKey` - If the table has no primary key column.
+    /// * `Error::InvalidOperator` - If `op` is not a range operator.
+    /// * `Error::InvalidConditionValue` - If a numeric key is compared against a non-numeric value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// let recent_users = table.filter_primary_key_range(">", "1000").unwrap();
+    /// ```
+    pub fn filter_primary_key_range(&self, op: &str, value: &str) -> Result<Table, Error> {
+        let operator = match Operator::from_str(op) {
+            Ok(
+                operator @ (Operator::LessThan
+                | Operator::GreaterThan
+                | Operator::LessThanOrEqual
+                | Operator::GreaterThanOrEqual),
+            ) => operator,
+            _ => return Err(Error::InvalidOperator(op.to_string())),
+        };
+        let column = self
+            .columns
+            .iter()
+            .find(|column| column.is_primary_key)
+            .ok_or(Error::NoPrimaryKey)?;
+
+        if let (ColumnDataType::Integer, Ok(boundary)) = (column.data_type, value.parse::<i64>()) {
+            let row_indices: Vec<usize> = column
+                .data
+                .iter()
+                .enumerate()
+                .filter(|(_, key)| match key {
+                    Value::Integer(key) => apply_operator(Some(key.cmp(&boundary)), &operator),
+                    _ => false,
+                })
+                .map(|(row_idx, _)| row_idx)
+                .collect();
+            return Ok(self.with_rows(&row_indices));
+        }
+
+        self.filter(&NestedCondition::Condition(
+            column.name.clone(),
+            op.to_string(),
+            value.to_string(),
+        ))
+    }
+
+    /// Adds the last record to every index, after it was appended by an insert.
+    pub(crate) fn index_last_row(&mut self) {
+        debug_assert!(self.validate().is_ok(), "columns of unequal lengths");
+        let Some(row_idx) = self.row_count().checked_sub(1) else {
+            return;
+        };
+
+        for (column_name, index) in self.indexes.iter_mut() {
+            if let Some(column) = self.columns.iter().find(|c| &c.name == column_name) {
+                let value = column.data.get(row_idx).cloned().unwrap_or(Value::Null);
+                index.entry(value).or_default().push(row_idx);
+            }
+        }
+    }
+
+    /// Moves a record to the bucket of its new value in the index of a column, if the column
+    /// has one, after the value was updated from `old_value`. Buckets stay in record order.
+    pub(crate) fn reindex_value(&mut self, column_idx: usize, row_idx: usize, old_value: &Value) {
+        let column = &self.columns[column_idx];
+        let Some(index) = self.indexes.get_mut(&column.name) else {
+            return;
+        };
+        let new_value = &column.data[row_idx];
+        if new_value == old_value {
+            return;
+        }
+
+        if let Some(row_indices) = index.get_mut(old_value) {
+            if let Ok(position) = row_indices.binary_search(&row_idx) {
+                row_indices.remove(position);
+            }
+            if row_indices.is_empty() {
+                index.remove(old_value);
+            }
+        }
+        let row_indices = index.entry(new_value.clone()).or_default();
+        if let Err(position) = row_indices.binary_search(&row_idx) {
+            row_indices.insert(position, row_idx);
+        }
+    }
+
+    /// Rebuilds every index from scratch, after records were updated or deleted.
+    pub(crate) fn rebuild_indexes(&mut self) {
+        debug_assert!(self.validate().is_ok(), "columns of unequal lengths");
+        let column_names: Vec<String> = self.indexes.keys().cloned().collect();
+        for column_name in column_names {
+            match self.column_position(&column_name) {
+                Ok(column_idx) => {
+                    let index = self.build_index(column_idx);
+                    self.indexes.insert(column_name, index);
+                }
+                Err(_) => {
+                    self.indexes.remove(&column_name);
+                }
+            }
+        }
+    }
+
+    fn build_index(&self, column_idx: usize) -> HashMap<Value, Vec<usize>> {
+        let mut index: HashMap<Value, Vec<usize>> = HashMap::new();
+        for (row_idx, value) in self.columns[column_idx].data.iter().enumerate() {
+            index.entry(value.clone()).or_default().push(row_idx);
+        }
+        index
+    }
+}
+
+/// The index key matched by `column = value`, or `None` when the literal doesn't map to a
+/// single stored value (e.g. `30.5` on an Integer column), in which case the scan decides.
+fn index_key(data_type: ColumnDataType, value: &str) -> Option<Value> {
+    match data_type {
+        ColumnDataType::Integer => value.trim().parse().ok().map(Value::Integer),
+        ColumnDataType::Float => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| !value.is_nan())
+            .map(Value::Float),
+        ColumnDataType::Text => Some(Value::Text(value.to_string())),
+        ColumnDataType::Decimal => Value::parse_decimal(value),
+    }
+}
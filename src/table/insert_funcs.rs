@@ -1,5 +1,9 @@
 use crate::column::{ColumnDataType, Value};
-use crate::table::{Error, Table};
+use crate::table::helpers::{
+    check_insert_columns_unique, parse_column_value, parse_column_value_or_null,
+};
+use crate::table::table_errors::BatchError;
+use crate::table::{Error, InsertOutcome, Row, Table};
 use std::collections::HashSet;
 
 impl Table {
@@ -11,7 +15,7 @@ impl Table {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` if the insertion operation is successful.
+    /// * `Ok(InsertOutcome)` with the row index and primary key of the new record if the insertion operation is successful.
     /// * `Err(Error)` if an error occurs during the insertion operation.
     ///
     /// # Errors
@@ -22,6 +26,8 @@ impl Table {
     /// * `Error::ParseError` - If a data value cannot be parsed into the corresponding column's data type.
     /// * `Error::NullPrimaryKey` - If the primary key column value is null.
     /// * `Error::DuplicatePrimaryKey` - If the primary key value already exists in the table.
+    /// * `Error::NullValue` - If a null value is provided for a not-null column.
+    /// * `Error::DuplicateValue` - If a value for a unique column already exists in the table.
     ///
     /// # Examples
     ///
@@ -30,38 +36,133 @@ impl Table {
     /// use crate::table::Table;
     ///
     /// let mut table = Table::new("users", vec![
-    ///     Column::new("id", ColumnDataType::Integer, Some(true)), // Primary key column
-    ///     Column::new("name", ColumnDataType::Text, None),
-    ///     Column::new("age", ColumnDataType::Integer, None),
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    ///     Column::new("age", ColumnDataType::Integer, None, false, None),
     /// ]);
     ///
     /// table.insert(vec!["1".to_string(), "Alice".to_string(), "25".to_string()]).unwrap();
     /// ```
-    pub fn insert(&mut self, data: Vec<String>) -> Result<(), Error> {
-        if data.len() != self.columns.len() {
+    pub fn insert(&mut self, data: Vec<String>) -> Result<InsertOutcome, Error> {
+        let parsed_values = self.parse_record(data)?;
+        self.append_record(parsed_values)
+    }
+
+    /// Inserts a new record of already typed values into the table, without parsing strings.
+    /// Each value must be NULL or match the data type of its column, e.g. `Value::Integer` for
+    /// an Integer column.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - A vector of `Value`s, one per column of the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(InsertOutcome)` with the row index and primary key of the new record if the insertion operation is successful.
+    /// * `Err(Error)` if an error occurs during the insertion operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::MismatchedColumnCount` - If the number of values does not match the number of columns in the table.
+    /// * `Error::TypeMismatch` - If a value does not match the data type of its column.
+    /// * `Error::NonFiniteFloat` - If a float value is NaN or infinite.
+    /// * `Error::NullPrimaryKey` - If the primary key column value is null.
+    /// * `Error::DuplicatePrimaryKey` - If the primary key value already exists in the table.
+    /// * `Error::NullValue` - If a null value is provided for a not-null column.
+    /// * `Error::DuplicateValue` - If a value for a unique column already exists in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::{Column, ColumnDataType, Value};
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", vec![
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    /// ]).unwrap();
+    ///
+    /// table.insert_values(vec![Value::Integer(1), Value::Text("Alice".to_string())]).unwrap();
+    /// ```
+    pub fn insert_values(&mut self, values: Vec<Value>) -> Result<InsertOutcome, Error> {
+        if values.len() != self.columns.len() {
             return Err(Error::MismatchedColumnCount);
         }
 
-        let mut parsed_values: Vec<Value> = Vec::with_capacity(self.columns.len());
-
-        for (column, value_str) in self.columns.iter().zip(data.into_iter()) {
-            if value_str.trim().to_lowercase() == "null" {
-                parsed_values.push(Value::Null);
-            } else {
-                match column.data_type {
-                    ColumnDataType::Integer => match value_str.parse::<i64>() {
-                        Ok(value) => parsed_values.push(Value::Integer(value)),
-                        Err(_) => return Err(Error::ParseError(parsed_values.len(), value_str)),
-                    },
-                    ColumnDataType::Float => match value_str.parse::<f64>() {
-                        Ok(value) => parsed_values.push(Value::Float(value)),
-                        Err(_) => return Err(Error::ParseError(parsed_values.len(), value_str)),
-                    },
-                    ColumnDataType::Text => parsed_values.push(Value::Text(value_str)),
+        for (column, value) in self.columns.iter().zip(values.iter()) {
+            let matches_type = matches!(
+                (column.data_type, value),
+                (_, Value::Null)
+                    | (ColumnDataType::Integer, Value::Integer(_))
+                    | (ColumnDataType::Float, Value::Float(_))
+                    | (ColumnDataType::Text, Value::Text(_))
+                    | (ColumnDataType::Decimal, Value::Decimal(..))
+            );
+            if !matches_type {
+                return Err(Error::TypeMismatch(column.name.clone(), column.data_type));
+            }
+            if let Value::Float(value) = value {
+                if !value.is_finite() {
+                    return Err(Error::NonFiniteFloat(
+                        column.name.clone(),
+                        value.to_string(),
+                    ));
                 }
             }
         }
 
+        self.append_record(values)
+    }
+
+    /// Inserts a new record like `insert`, with NULL given as `None` instead of the text "null".
+    /// Every `Some` value is parsed as data, so `Some("null")` stores the text "null" in a Text
+    /// column, and fails to parse in the other columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - One optional string per column of the table, `None` for NULL.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(InsertOutcome)` with the row index and primary key of the new record if the insertion operation is successful.
+    /// * `Err(Error)` if an error occurs during the insertion operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the same errors as `insert`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// // A user whose last name is literally "Null", and whose age is unknown
+    /// table
+    ///     .insert_typed(vec![Some("1".to_string()), Some("Null".to_string()), None])
+    ///     .unwrap();
+    /// ```
+    pub fn insert_typed(&mut self, values: Vec<Option<String>>) -> Result<InsertOutcome, Error> {
+        if values.len() != self.columns.len() {
+            return Err(Error::MismatchedColumnCount);
+        }
+
+        let parsed_values = self
+            .columns
+            .iter()
+            .zip(values)
+            .map(|(column, value)| match value {
+                Some(value_str) => parse_column_value(column, value_str),
+                None => Ok(Value::Null),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.append_record(parsed_values)
+    }
+
+    /// Checks a full record of parsed values against the table's constraints and appends it.
+    fn append_record(&mut self, parsed_values: Vec<Value>) -> Result<InsertOutcome, Error> {
         // Check if the primary key column exists and validate the primary key value
         if let Some(primary_key_column) = &self.primary_key_column {
             let primary_key_idx = self
@@ -85,14 +186,18 @@ impl Table {
             }
         }
 
+        self.check_column_constraints(&parsed_values)?;
+
         for (column, value) in self.columns.iter_mut().zip(parsed_values.into_iter()) {
             column.data.push(value);
         }
+        self.index_last_row();
 
-        Ok(())
+        Ok(self.last_row_outcome())
     }
 
-    /// Inserts a new record into the table with data for specific columns.
+    /// Inserts a new record into the table with data for specific columns. The other columns
+    /// take their default value, or NULL if they have none.
     ///
     /// # Arguments
     ///
@@ -101,19 +206,22 @@ impl Table {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` if the insert operation is successful.
+    /// * `Ok(InsertOutcome)` with the row index and primary key of the new record if the insert operation is successful.
     /// * `Err(Error)` if an error occurs during the insert operation.
     ///
     /// # Errors
     ///
     /// This function can return the following errors:
     ///
+    /// * `Error::DuplicateColumnInInsert` - If a column name is provided more than once.
     /// * `Error::NonExistingColumns` - If one or more of the provided column names do not exist in the table.
     /// * `Error::MismatchedColumnCount` - If the number of provided data items does not match the number of provided column names.
     /// * `Error::ParseError` - If a data item cannot be parsed into the corresponding column's data type.
-    /// * `Error::PrimaryKeyNotProvided` - If the primary key column is not provided in the `column_names` vector.
+    /// * `Error::PrimaryKeyNotProvided` - If the primary key column is not provided in the `column_names` vector and has no default value.
     /// * `Error::NullPrimaryKey` - If the primary key column value is null.
     /// * `Error::DuplicatePrimaryKey` - If the primary key value already exists in the table.
+    /// * `Error::NullValue` - If a null value is provided for a not-null column, or it is omitted and has no default value.
+    /// * `Error::DuplicateValue` - If a value for a unique column already exists in the table.
     ///
     /// # Examples
     ///
@@ -136,7 +244,11 @@ impl Table {
         &mut self,
         column_names: Vec<String>,
         data: Vec<String>,
-    ) -> Result<(), Error> {
+    ) -> Result<InsertOutcome, Error> {
+        // A column given twice would silently keep only its last value
+        let column_names = self.resolve_column_names(column_names)?;
+        check_insert_columns_unique(&column_names)?;
+
         // Check if all provided column names exist in the table
         let column_names_set: HashSet<String> = column_names.iter().cloned().collect();
         let existing_columns: HashSet<String> =
@@ -155,35 +267,28 @@ impl Table {
             return Err(Error::MismatchedColumnCount);
         }
 
-        // Check if the provided columns contain the primary key column
+        // Check if the provided columns contain the primary key column, unless it has a default
         if let Some(primary_key_column) = &self.primary_key_column {
-            if !column_names.contains(&primary_key_column.name) {
+            if !column_names.contains(&primary_key_column.name)
+                && primary_key_column.default_value.is_none()
+            {
                 return Err(Error::PrimaryKeyNotProvided(
                     primary_key_column.name.clone(),
                 ));
             }
         }
 
-        let mut parsed_values: Vec<Value> = vec![Value::Null; self.columns.len()];
+        // Omitted columns take their default value, or NULL without one
+        let mut parsed_values: Vec<Value> = self
+            .columns
+            .iter()
+            .map(|column| column.default_value.clone().unwrap_or(Value::Null))
+            .collect();
 
         for (column_name, value_str) in column_names.iter().zip(data.into_iter()) {
             if let Some(column_idx) = self.columns.iter().position(|c| c.name == *column_name) {
                 let column = &self.columns[column_idx];
-                if value_str.trim().to_lowercase() == "null" {
-                    parsed_values[column_idx] = Value::Null;
-                } else {
-                    match column.data_type {
-                        ColumnDataType::Integer => match value_str.parse::<i64>() {
-                            Ok(value) => parsed_values[column_idx] = Value::Integer(value),
-                            Err(_) => return Err(Error::ParseError(column_idx, value_str)),
-                        },
-                        ColumnDataType::Float => match value_str.parse::<f64>() {
-                            Ok(value) => parsed_values[column_idx] = Value::Float(value),
-                            Err(_) => return Err(Error::ParseError(column_idx, value_str)),
-                        },
-                        ColumnDataType::Text => parsed_values[column_idx] = Value::Text(value_str),
-                    }
-                }
+                parsed_values[column_idx] = parse_column_value_or_null(column, value_str)?;
             }
         }
 
@@ -209,10 +314,440 @@ impl Table {
             }
         }
 
+        self.check_column_constraints(&parsed_values)?;
+
         for (column, value) in self.columns.iter_mut().zip(parsed_values.into_iter()) {
             column.data.push(value);
         }
+        self.index_last_row();
+
+        Ok(self.last_row_outcome())
+    }
+
+    /// Inserts a new record given as a `Row` of column names and values. Like in
+    /// `insert_with_columns`, the columns may come in any order and the omitted ones take their
+    /// default value, or NULL if they have none.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The values of the new record by column name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(InsertOutcome)` with the row index and primary key of the new record if the insert operation is successful.
+    /// * `Err(Error)` if an error occurs during the insert operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::DuplicateColumnInInsert` - If the row has a column more than once.
+    /// * `Error::NonExistingColumns` - If one or more of the columns of the row do not exist in the table.
+    /// * `Error::TypeMismatch` - If a value does not match the data type of its column.
+    /// * `Error::PrimaryKeyNotProvided` - If the row has no primary key value and the primary key column has no default value.
+    /// * `Error::NullPrimaryKey` - If the primary key column value is null.
+    /// * `Error::DuplicatePrimaryKey` - If the primary key value already exists in the table.
+    /// * `Error::NullValue` - If a null value is provided for a not-null column, or it is omitted and has no default value.
+    /// * `Error::DuplicateValue` - If a value for a unique column already exists in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::Value;
+    /// use crate::table::{Row, Table};
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// let row = Row::new()
+    ///     .with("user_name", Value::Text("Emily".to_string()))
+    ///     .with("user_id", Value::Integer(5));
+    /// table.insert_row(row).unwrap();
+    /// ```
+    pub fn insert_row(&mut self, row: Row) -> Result<InsertOutcome, Error> {
+        let (column_names, values): (Vec<String>, Vec<Value>) = row.entries.into_iter().unzip();
+        let column_names = self.resolve_column_names(column_names)?;
+        check_insert_columns_unique(&column_names)?;
+
+        let non_existing_columns: Vec<String> = column_names
+            .iter()
+            .filter(|column_name| !self.columns.iter().any(|c| c.name == **column_name))
+            .cloned()
+            .collect();
+        if !non_existing_columns.is_empty() {
+            return Err(Error::NonExistingColumns(non_existing_columns));
+        }
+
+        if let Some(primary_key_column) = &self.primary_key_column {
+            if !column_names.contains(&primary_key_column.name)
+                && primary_key_column.default_value.is_none()
+            {
+                return Err(Error::PrimaryKeyNotProvided(
+                    primary_key_column.name.clone(),
+                ));
+            }
+        }
+
+        let mut record: Vec<Value> = self
+            .columns
+            .iter()
+            .map(|column| column.default_value.clone().unwrap_or(Value::Null))
+            .collect();
+        for (column_name, value) in column_names.iter().zip(values) {
+            if let Some(column_idx) = self.columns.iter().position(|c| c.name == *column_name) {
+                record[column_idx] = value;
+            }
+        }
+
+        self.insert_values(record)
+    }
+
+    /// The outcome of inserting the last record of the table.
+    fn last_row_outcome(&self) -> InsertOutcome {
+        let row_index = self.row_count().saturating_sub(1);
+        InsertOutcome {
+            row_index,
+            primary_key: self.primary_key_column.as_ref().and_then(|primary_key| {
+                self.columns
+                    .iter()
+                    .find(|c| c.name == primary_key.name)
+                    .and_then(|column| column.data.get(row_index).cloned())
+            }),
+        }
+    }
+
+    /// Checks a full record of parsed values against the not-null and unique constraints
+    /// of the columns.
+    pub(crate) fn check_column_constraints(&self, values: &[Value]) -> Result<(), Error> {
+        for (column, value) in self.columns.iter().zip(values.iter()) {
+            if *value == Value::Null {
+                if column.not_null {
+                    return Err(Error::NullValue(column.name.clone()));
+                }
+            } else if column.unique && column.data.contains(value) {
+                return Err(Error::DuplicateValue(
+                    column.name.clone(),
+                    value.to_string(),
+                ));
+            }
+        }
 
         Ok(())
     }
+
+    /// Inserts a new record into the table like `insert`, but silently skips it when its primary
+    /// key or a value of a unique column already exists, e.g. when replaying an import that
+    /// overlaps the existing data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - A vector of `String` values representing the data to be inserted. The number of values must match the number of columns in the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` if the record was inserted.
+    /// * `Ok(false)` if the record was ignored because of a duplicate key.
+    /// * `Err(Error)` if any other error occurs during the insertion operation.
+    ///
+    /// # Errors
+    ///
+    /// The same as `insert`, except for `Error::DuplicatePrimaryKey` and `Error::DuplicateValue`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::{Column, ColumnDataType};
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", vec![
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    /// ]).unwrap();
+    ///
+    /// assert!(table.insert_or_ignore(vec!["1".to_string(), "Alice".to_string()]).unwrap());
+    /// assert!(!table.insert_or_ignore(vec!["1".to_string(), "Bob".to_string()]).unwrap());
+    /// ```
+    pub fn insert_or_ignore(&mut self, data: Vec<String>) -> Result<bool, Error> {
+        match self.insert(data) {
+            Ok(_) => Ok(true),
+            Err(Error::DuplicatePrimaryKey) | Err(Error::DuplicateValue(..)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Inserts a new record into the table, replacing the record with the same primary key if
+    /// there is one, like SQL's `REPLACE`. The old record is deleted and the new one appended,
+    /// so every column takes the new value, including NULLs. If the new record is rejected, the
+    /// old one is kept in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - A vector of `String` values representing the data to be inserted. The number of values must match the number of columns in the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` if a record with the same primary key was replaced.
+    /// * `Ok(false)` if the record was inserted as a new one.
+    /// * `Err(Error)` if an error occurs during the operation.
+    ///
+    /// # Errors
+    ///
+    /// The same as `insert`, except for `Error::DuplicatePrimaryKey`, plus:
+    ///
+    /// * `Error::NoPrimaryKey` - If the table has no primary key column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::{Column, ColumnDataType};
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", vec![
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    /// ]).unwrap();
+    ///
+    /// assert!(!table.replace(vec!["1".to_string(), "Alice".to_string()]).unwrap());
+    /// assert!(table.replace(vec!["1".to_string(), "null".to_string()]).unwrap());
+    /// ```
+    pub fn replace(&mut self, data: Vec<String>) -> Result<bool, Error> {
+        let primary_key_idx = self
+            .columns
+            .iter()
+            .position(|c| c.is_primary_key)
+            .ok_or(Error::NoPrimaryKey)?;
+        let record = self.parse_record(data)?;
+
+        let Some(row_idx) = self.columns[primary_key_idx]
+            .data
+            .iter()
+            .position(|value| *value == record[primary_key_idx])
+        else {
+            self.insert_parsed_many(vec![record])
+                .map_err(|batch_error| batch_error.error)?;
+            return Ok(false);
+        };
+
+        let old_record: Vec<Value> = self
+            .columns
+            .iter_mut()
+            .map(|column| column.data.remove(row_idx))
+            .collect();
+        self.rebuild_indexes();
+
+        if let Err(batch_error) = self.insert_parsed_many(vec![record]) {
+            for (column, value) in self.columns.iter_mut().zip(old_record) {
+                column.data.insert(row_idx, value);
+            }
+            self.rebuild_indexes();
+            return Err(batch_error.error);
+        }
+
+        Ok(true)
+    }
+
+    /// Inserts several records into the table at once. Every record is parsed and checked
+    /// against the constraints of the table, including duplicate keys within the batch, before
+    /// any data is changed, so either all records are inserted or none.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The records to insert, each a vector of `String` values with one value per column.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of inserted records.
+    /// * `Err(BatchError)` with the position of the first rejected record in `rows` and why it was rejected.
+    ///
+    /// # Errors
+    ///
+    /// A rejected record can have any of the errors of `insert`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::{Column, ColumnDataType};
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", vec![
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    /// ]).unwrap();
+    ///
+    /// let inserted = table.insert_many(vec![
+    ///     vec!["1".to_string(), "Alice".to_string()],
+    ///     vec!["2".to_string(), "Bob".to_string()],
+    /// ]).unwrap();
+    /// assert_eq!(inserted, 2);
+    /// ```
+    pub fn insert_many(&mut self, rows: Vec<Vec<String>>) -> Result<usize, BatchError> {
+        let records = rows
+            .into_iter()
+            .enumerate()
+            .map(|(row_idx, row)| {
+                self.parse_record(row)
+                    .map_err(|error| BatchError { row_idx, error })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.insert_parsed_many(records)
+    }
+
+    /// Parses a full record of values into the data types of the columns.
+    pub(crate) fn parse_record(&self, data: Vec<String>) -> Result<Vec<Value>, Error> {
+        if data.len() != self.columns.len() {
+            return Err(Error::MismatchedColumnCount);
+        }
+
+        let mut parsed_values: Vec<Value> = Vec::with_capacity(self.columns.len());
+
+        for (column, value_str) in self.columns.iter().zip(data) {
+            parsed_values.push(parse_column_value_or_null(column, value_str)?);
+        }
+
+        Ok(parsed_values)
+    }
+
+    /// Checks parsed records against the primary key, not-null and unique constraints, and
+    /// appends them to the table only if every record passes.
+    pub(crate) fn insert_parsed_many(
+        &mut self,
+        records: Vec<Vec<Value>>,
+    ) -> Result<usize, BatchError> {
+        let primary_key_idx = self
+            .primary_key_column
+            .as_ref()
+            .and_then(|primary_key| self.columns.iter().position(|c| c.name == primary_key.name));
+
+        {
+            // Values already taken in every key column, so no record needs a scan of the data
+            let mut taken_values: Vec<Option<HashSet<&Value>>> = self
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(column_idx, column)| {
+                    (Some(column_idx) == primary_key_idx || column.unique)
+                        .then(|| column.data.iter().collect())
+                })
+                .collect();
+
+            for (row_idx, record) in records.iter().enumerate() {
+                let rejected = |error| BatchError { row_idx, error };
+
+                for (column_idx, (column, value)) in self.columns.iter().zip(record).enumerate() {
+                    let is_primary_key = Some(column_idx) == primary_key_idx;
+                    if *value == Value::Null {
+                        if is_primary_key {
+                            return Err(rejected(Error::NullPrimaryKey));
+                        }
+                        if column.not_null {
+                            return Err(rejected(Error::NullValue(column.name.clone())));
+                        }
+                    } else if let Some(taken_values) = &mut taken_values[column_idx] {
+                        if !taken_values.insert(value) {
+                            return Err(rejected(if is_primary_key {
+                                Error::DuplicatePrimaryKey
+                            } else {
+                                Error::DuplicateValue(column.name.clone(), value.to_string())
+                            }));
+                        }
+                    }
+                }
+            }
+        }
+
+        let inserted = records.len();
+        for column in &mut self.columns {
+            column.data.reserve(inserted);
+        }
+        for record in records {
+            for (column, value) in self.columns.iter_mut().zip(record) {
+                column.data.push(value);
+            }
+            self.index_last_row();
+        }
+
+        Ok(inserted)
+    }
+
+    /// Appends every record of another table to this one, like SQL's `UNION ALL`. The tables
+    /// need the same column names with the same data types, in any order. The records are
+    /// checked like in `insert_many`, so either all of them are appended or none.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The table whose records are appended. It is left unchanged.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of appended records.
+    /// * `Err(Error)` if the schemas differ or a record is rejected.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::SchemaMismatch` - With every column that is missing from one of the tables or
+    ///   has another data type in each of them.
+    /// * `Error::NullPrimaryKey` - If a primary key value of `other` is null.
+    /// * `Error::DuplicatePrimaryKey` - If a primary key value is in both tables, or twice in
+    ///   `other`.
+    /// * `Error::NullValue` - If `other` has a null value in a not-null column.
+    /// * `Error::DuplicateValue` - If a value of a unique column is in both tables, or twice in
+    ///   `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut sales = Table::new("sales_january", /* ... */);
+    /// let february = Table::new("sales_february", /* ... */);
+    /// let appended = sales.append(&february).unwrap();
+    /// ```
+    pub fn append(&mut self, other: &Table) -> Result<usize, Error> {
+        let records = self.records_to_append(other)?;
+        self.insert_parsed_many(records)
+            .map_err(|batch_error| batch_error.error)
+    }
+
+    /// Checks that another table has the same columns and returns its records with the values
+    /// in the column order of this table.
+    pub(crate) fn records_to_append(&self, other: &Table) -> Result<Vec<Vec<Value>>, Error> {
+        let mut differences = Vec::new();
+        let mut source_columns = Vec::with_capacity(self.columns.len());
+        for column in &self.columns {
+            match other.columns.iter().find(|c| c.name == column.name) {
+                Some(source_column) if source_column.data_type == column.data_type => {
+                    source_columns.push(source_column)
+                }
+                Some(source_column) => differences.push(format!(
+                    "column '{}' is {} in this table but {} in the appended table",
+                    column.name, column.data_type, source_column.data_type
+                )),
+                None => differences.push(format!(
+                    "column '{}' is missing from the appended table",
+                    column.name
+                )),
+            }
+        }
+        for column in &other.columns {
+            if !self.columns.iter().any(|c| c.name == column.name) {
+                differences.push(format!(
+                    "column '{}' does not exist in this table",
+                    column.name
+                ));
+            }
+        }
+        if !differences.is_empty() {
+            return Err(Error::SchemaMismatch(differences));
+        }
+
+        Ok((0..other.row_count())
+            .map(|row_idx| {
+                source_columns
+                    .iter()
+                    .map(|column| column.data.get(row_idx).cloned().unwrap_or(Value::Null))
+                    .collect()
+            })
+            .collect())
+    }
 }
@@ -1,21 +1,165 @@
+mod aggregate_funcs;
+pub(crate) mod condition_parser;
 mod delete_funcs;
+pub(crate) mod display;
 mod export_import;
 mod filter_funcs;
 pub(crate) mod helpers;
+mod index_funcs;
 mod insert_funcs;
 pub(crate) mod operators;
+pub(crate) mod schema;
+mod set_funcs;
+mod sort_funcs;
 pub(crate) mod table_errors;
 mod table_utils;
 mod update_funcs;
 
-use crate::column::Column;
+use crate::column::{Column, Value};
 use crate::table::table_errors::Error;
+use std::collections::HashMap;
 
-#[derive(Debug)]
+/// Longest table or column name accepted, in characters. A `Database` can lower it with
+/// `set_max_name_length`.
+pub const MAX_NAME_LENGTH: usize = 255;
+
+/// A condition on the records of a table: a comparison `(column_name, operator, value)`, or
+/// conditions combined with `And`, `Or` and `Not`. See `NestedCondition::parse` to write one
+/// as a string expression.
+#[derive(Debug, Clone, PartialEq)]
 pub enum NestedCondition {
     Condition(String, String, String),
     And(Box<NestedCondition>, Box<NestedCondition>),
     Or(Box<NestedCondition>, Box<NestedCondition>),
+    /// Holds for every record the inner condition doesn't hold for, including records where
+    /// it is false because of a NULL value.
+    Not(Box<NestedCondition>),
+}
+
+/// An operand of an update expression: the value of a column in the record being updated, or
+/// a literal parsed as the data type of the updated column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Col(String),
+    Lit(String),
+}
+
+/// Computes the new value of a column from the record being updated, see `Table::update_expr`.
+/// `Sub` and `Mul` apply to numeric columns only; `Add` also concatenates Text values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Add(Operand, Operand),
+    Sub(Operand, Operand),
+    Mul(Operand, Operand),
+}
+
+/// Aggregate functions that can be applied to a column when grouping rows.
+/// NULL values are ignored by every aggregate, so `Count` counts non-null values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AggregateFn {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+/// Formats a table can be written in, see `Table::write_to`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    /// The CSV export format, read back by `import_table`.
+    Csv,
+    /// The TXT export format, read back by `import_table`.
+    Txt,
+    /// The grid printed by `show`.
+    Grid,
+    /// A markdown table, see `Table::to_markdown`. Export only.
+    Markdown,
+}
+
+/// Where a record ended up after an insert: its row index in the table and the value of its
+/// primary key, if the table has one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsertOutcome {
+    pub row_index: usize,
+    pub primary_key: Option<Value>,
+}
+
+/// A record as column names with their values, e.g. to insert with `Table::insert_row` or as
+/// returned by `Table::select_rows`. Values are looked up by column name with `get`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Row {
+    pub(crate) entries: Vec<(String, Value)>,
+}
+
+impl Row {
+    /// Creates an empty row.
+    pub fn new() -> Self {
+        Row::default()
+    }
+
+    /// Returns the row with the value of a column added, to build a row in one expression:
+    /// `Row::new().with("id", Value::Integer(1)).with("name", Value::Text(name))`.
+    pub fn with(mut self, column_name: &str, value: Value) -> Self {
+        self.entries.push((column_name.to_string(), value));
+        self
+    }
+
+    /// Returns the value of a column, or None if the row has no such column.
+    pub fn get(&self, column_name: &str) -> Option<&Value> {
+        self.entries
+            .iter()
+            .find(|(name, _)| name == column_name)
+            .map(|(_, value)| value)
+    }
+
+    /// The column names and values of the row, in order.
+    pub fn entries(&self) -> &[(String, Value)] {
+        &self.entries
+    }
+}
+
+impl From<Vec<(String, Value)>> for Row {
+    fn from(entries: Vec<(String, Value)>) -> Self {
+        Row { entries }
+    }
+}
+
+/// Profile of a column, see `Table::column_summary`. `count` and `distinct_count` only consider
+/// non-null values, and the statistics are None when the column has no non-null values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSummary {
+    pub count: usize,
+    pub null_count: usize,
+    pub distinct_count: usize,
+    pub min: Option<Value>,
+    pub max: Option<Value>,
+    /// Always None for Text columns.
+    pub mean: Option<Value>,
+}
+
+impl OutputFormat {
+    /// Parses an export format name ("csv", "txt" or "md"), ignoring case.
+    pub(crate) fn from_str(format: &str) -> Result<OutputFormat, Error> {
+        match format.to_lowercase().as_str() {
+            "csv" => Ok(OutputFormat::Csv),
+            "txt" => Ok(OutputFormat::Txt),
+            "md" => Ok(OutputFormat::Markdown),
+            _ => Err(Error::InvalidFormat(format.to_string())),
+        }
+    }
+}
+
+/// Options for `Table::show_opts`. The default options show every record, untruncated,
+/// without row numbers.
+#[derive(Debug, Clone, Default)]
+pub struct ShowOptions {
+    /// Maximum number of records to print; the rest is summarized in a trailing line.
+    pub max_rows: Option<usize>,
+    /// Maximum width of a cell; longer values are cut and end with "…".
+    pub max_cell_width: Option<usize>,
+    /// Whether to print the (zero-based) record index in front of every record.
+    pub show_row_numbers: bool,
 }
 
 /// Struct representing a table with a name and a vector of columns
@@ -26,4 +170,8 @@ pub struct Table {
     pub(crate) columns: Vec<Column>,
     pub(crate) primary_key_column: Option<Column>,
     pub(crate) referenced_as_foreign_key: Vec<(String, String)>,
+    // Secondary indexes by column name, mapping every value to the rows holding it
+    pub(crate) indexes: HashMap<String, HashMap<Value, Vec<usize>>>,
+    // Whether column names given by callers ignore case, see `set_case_insensitive_names`
+    pub(crate) case_insensitive_names: bool,
 }
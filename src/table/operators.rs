@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 /// This Operator enum represents the different comparison operators that can be used in an update
 /// or select condition. These are mapped to respective operations on execution.
 #[derive(Debug, PartialEq)]
@@ -8,6 +10,8 @@ pub(crate) enum Operator {
     GreaterThan,
     LessThanOrEqual,
     GreaterThanOrEqual,
+    IsNull,
+    IsNotNull,
 }
 
 impl Operator {
@@ -21,7 +25,29 @@ impl Operator {
             ">" => Ok(Operator::GreaterThan),
             "<=" => Ok(Operator::LessThanOrEqual),
             ">=" => Ok(Operator::GreaterThanOrEqual),
-            _ => Err(format!("Invalid operator: {}", s)),
+            _ => match s.trim().to_lowercase().as_str() {
+                "is null" => Ok(Operator::IsNull),
+                "is not null" => Ok(Operator::IsNotNull),
+                _ => Err(format!("Invalid operator: {}", s)),
+            },
+        }
+    }
+}
+
+/// Applies a comparison operator to the ordering of a value relative to a condition value.
+/// `None` means the two values are not comparable (e.g. a NaN float), which only satisfies
+/// `!=`. The `IS NULL` operators don't depend on the ordering: a non-null value is never null.
+pub(crate) fn apply_operator(ordering: Option<Ordering>, operator: &Operator) -> bool {
+    match operator {
+        Operator::Equal => ordering == Some(Ordering::Equal),
+        Operator::NotEqual => ordering != Some(Ordering::Equal),
+        Operator::LessThan => ordering == Some(Ordering::Less),
+        Operator::GreaterThan => ordering == Some(Ordering::Greater),
+        Operator::LessThanOrEqual => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        Operator::GreaterThanOrEqual => {
+            matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
         }
+        Operator::IsNull => false,
+        Operator::IsNotNull => true,
     }
 }
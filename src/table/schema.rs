@@ -0,0 +1,410 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::column::{ColumnDataType, ForeignKeyInfo, Value};
+use crate::table::Table;
+
+/// Structured description of a single column, without any of its data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub data_type: ColumnDataType,
+    pub is_primary_key: bool,
+    pub foreign_key: Option<ForeignKeyInfo>,
+    pub not_null: bool,
+    pub unique: bool,
+}
+
+/// Structured description of a table: its name and the schema of every column in table order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSchema {
+    pub name: String,
+    pub columns: Vec<ColumnSchema>,
+}
+
+/// Renders the schema as printed by `describe`: the table name, the column names, a separator
+/// line, the data types and the primary key information, followed by a line for each of the
+/// not-null constraints, unique constraints and foreign keys the table has.
+impl fmt::Display for TableSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Table: {}", self.name)?;
+        writeln!(f)?;
+
+        // Find the maximum length of column names
+        let max_column_name_len = self
+            .columns
+            .iter()
+            .map(|column| column.name.len())
+            .max()
+            .unwrap_or(0);
+
+        let render_line = |cells: Vec<String>| -> String {
+            cells
+                .iter()
+                .map(|cell| format!("{:<width$} ", cell, width = max_column_name_len))
+                .collect()
+        };
+
+        // Column names
+        writeln!(
+            f,
+            "{}",
+            render_line(
+                self.columns
+                    .iter()
+                    .map(|column| column.name.clone())
+                    .collect()
+            )
+        )?;
+
+        // Separator line
+        let separator_len =
+            (max_column_name_len * self.columns.len() + self.columns.len()).saturating_sub(1);
+        writeln!(f, "{}", "-".repeat(separator_len))?;
+
+        // Data types
+        writeln!(
+            f,
+            "{}",
+            render_line(
+                self.columns
+                    .iter()
+                    .map(|column| column.data_type.to_string())
+                    .collect()
+            )
+        )?;
+
+        // Primary key information
+        write!(
+            f,
+            "{}",
+            render_line(
+                self.columns
+                    .iter()
+                    .map(|column| {
+                        if column.is_primary_key {
+                            "prim_key".to_string()
+                        } else {
+                            "nt_prim_key".to_string()
+                        }
+                    })
+                    .collect()
+            )
+        )?;
+
+        // Constraints, each only shown when the table has some, with "-" for the other columns
+        let constraint_line = |marker: &dyn Fn(&ColumnSchema) -> Option<String>| {
+            let cells: Vec<Option<String>> = self.columns.iter().map(marker).collect();
+            cells.iter().any(Option::is_some).then(|| {
+                render_line(
+                    cells
+                        .into_iter()
+                        .map(|cell| cell.unwrap_or_else(|| "-".to_string()))
+                        .collect(),
+                )
+            })
+        };
+        let constraint_lines = [
+            constraint_line(&|column| column.not_null.then(|| "not_null".to_string())),
+            constraint_line(&|column| column.unique.then(|| "unique".to_string())),
+            constraint_line(&|column| {
+                column.foreign_key.as_ref().map(|fk_info| {
+                    format!(
+                        "-> {}.{}",
+                        fk_info.reference_table, fk_info.reference_column
+                    )
+                })
+            }),
+        ];
+        for line in constraint_lines.into_iter().flatten() {
+            write!(f, "\n{}", line)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A difference in one attribute of a column between two schemas, as `(before, after)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnChange {
+    DataType(ColumnDataType, ColumnDataType),
+    PrimaryKey(bool, bool),
+    ForeignKey(Option<ForeignKeyInfo>, Option<ForeignKeyInfo>),
+    NotNull(bool, bool),
+    Unique(bool, bool),
+}
+
+/// Differences between two schemas of a table, see `TableSchema::diff`. Columns are matched
+/// by name, so a renamed column shows up as removed and added, and column order is ignored.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SchemaDiff {
+    // Columns only in the other schema
+    pub added_columns: Vec<ColumnSchema>,
+    // Columns only in this schema
+    pub removed_columns: Vec<ColumnSchema>,
+    // Columns in both schemas whose attributes differ, with every differing attribute
+    pub changed_columns: Vec<(String, Vec<ColumnChange>)>,
+}
+
+impl SchemaDiff {
+    /// Whether both schemas are the same, up to the order of their columns.
+    pub fn is_empty(&self) -> bool {
+        self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.changed_columns.is_empty()
+    }
+}
+
+/// Renders one line per difference: `+` for added columns, `-` for removed ones and `~` for
+/// changed ones, or "No differences".
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No differences");
+        }
+
+        let yes_no = |flag: &bool| if *flag { "yes" } else { "no" };
+        let foreign_key = |fk_info: &Option<ForeignKeyInfo>| match fk_info {
+            Some(fk_info) => format!("{}({})", fk_info.reference_table, fk_info.reference_column),
+            None => "none".to_string(),
+        };
+
+        let mut lines = Vec::new();
+        for column in &self.added_columns {
+            lines.push(format!("+ column {} ({})", column.name, column.data_type));
+        }
+        for column in &self.removed_columns {
+            lines.push(format!("- column {} ({})", column.name, column.data_type));
+        }
+        for (column_name, changes) in &self.changed_columns {
+            let changes: Vec<String> = changes
+                .iter()
+                .map(|change| match change {
+                    ColumnChange::DataType(before, after) => {
+                        format!("type {} -> {}", before, after)
+                    }
+                    ColumnChange::PrimaryKey(before, after) => {
+                        format!("primary key {} -> {}", yes_no(before), yes_no(after))
+                    }
+                    ColumnChange::ForeignKey(before, after) => {
+                        format!(
+                            "foreign key {} -> {}",
+                            foreign_key(before),
+                            foreign_key(after)
+                        )
+                    }
+                    ColumnChange::NotNull(before, after) => {
+                        format!("not null {} -> {}", yes_no(before), yes_no(after))
+                    }
+                    ColumnChange::Unique(before, after) => {
+                        format!("unique {} -> {}", yes_no(before), yes_no(after))
+                    }
+                })
+                .collect();
+            lines.push(format!("~ column {}: {}", column_name, changes.join(", ")));
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl TableSchema {
+    /// Lists how the columns of another schema differ from the columns of this one, e.g. the
+    /// same table in two environments. Added and removed columns keep the order of their
+    /// schema, changed columns the order of this schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let staging = Table::new("users", /* ... */);
+    /// let production = Table::new("users", /* ... */);
+    /// println!("{}", staging.schema().diff(&production.schema()));
+    /// ```
+    pub fn diff(&self, other: &TableSchema) -> SchemaDiff {
+        let find = |schema: &TableSchema, name: &str| {
+            schema
+                .columns
+                .iter()
+                .find(|column| column.name == name)
+                .cloned()
+        };
+
+        let mut diff = SchemaDiff {
+            added_columns: other
+                .columns
+                .iter()
+                .filter(|column| find(self, &column.name).is_none())
+                .cloned()
+                .collect(),
+            ..SchemaDiff::default()
+        };
+
+        for before in &self.columns {
+            let Some(after) = find(other, &before.name) else {
+                diff.removed_columns.push(before.clone());
+                continue;
+            };
+
+            let mut changes = Vec::new();
+            if before.data_type != after.data_type {
+                changes.push(ColumnChange::DataType(before.data_type, after.data_type));
+            }
+            if before.is_primary_key != after.is_primary_key {
+                changes.push(ColumnChange::PrimaryKey(
+                    before.is_primary_key,
+                    after.is_primary_key,
+                ));
+            }
+            if before.foreign_key != after.foreign_key {
+                changes.push(ColumnChange::ForeignKey(
+                    before.foreign_key.clone(),
+                    after.foreign_key,
+                ));
+            }
+            if before.not_null != after.not_null {
+                changes.push(ColumnChange::NotNull(before.not_null, after.not_null));
+            }
+            if before.unique != after.unique {
+                changes.push(ColumnChange::Unique(before.unique, after.unique));
+            }
+            if !changes.is_empty() {
+                diff.changed_columns.push((before.name.clone(), changes));
+            }
+        }
+
+        diff
+    }
+}
+
+impl Table {
+    /// Returns the structure of the table as data: its name and one `ColumnSchema` per column in
+    /// table order. Unlike `describe`, nothing is printed, so callers can build their own views
+    /// on top of it; the `Display` output of the returned schema is what `describe` prints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::{Column, ColumnDataType};
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", vec![
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None),
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    /// ]).unwrap();
+    ///
+    /// let schema = table.schema();
+    /// assert_eq!(schema.name, "users");
+    /// assert!(schema.columns[0].is_primary_key);
+    /// ```
+    pub fn schema(&self) -> TableSchema {
+        TableSchema {
+            name: self.name.clone(),
+            columns: self
+                .columns
+                .iter()
+                .map(|column| ColumnSchema {
+                    name: column.name.clone(),
+                    data_type: column.data_type,
+                    is_primary_key: column.is_primary_key,
+                    foreign_key: column.foreign_key.clone(),
+                    not_null: column.not_null,
+                    unique: column.unique,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns a `CREATE TABLE` statement for the table, to recreate it in another database.
+    /// Data types map to `INTEGER`, `REAL`, `TEXT` and `NUMERIC`, and every column lists its
+    /// constraints and default value. Foreign keys come last as `FOREIGN KEY` clauses. Names that
+    /// aren't plain identifiers are double-quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::{Column, ColumnDataType};
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", vec![
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None),
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     table.to_ddl(),
+    ///     "CREATE TABLE users (\n    id INTEGER PRIMARY KEY,\n    name TEXT\n);"
+    /// );
+    /// ```
+    pub fn to_ddl(&self) -> String {
+        let mut definitions: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let data_type = match column.data_type {
+                    ColumnDataType::Integer => "INTEGER",
+                    ColumnDataType::Float => "REAL",
+                    ColumnDataType::Text => "TEXT",
+                    ColumnDataType::Decimal => "NUMERIC",
+                };
+                let mut definition = format!("{} {}", sql_identifier(&column.name), data_type);
+                if column.is_primary_key {
+                    definition.push_str(" PRIMARY KEY");
+                }
+                if column.not_null {
+                    definition.push_str(" NOT NULL");
+                }
+                if column.unique {
+                    definition.push_str(" UNIQUE");
+                }
+                if let Some(default_value) = &column.default_value {
+                    definition.push_str(" DEFAULT ");
+                    definition.push_str(&sql_literal(default_value));
+                }
+                definition
+            })
+            .collect();
+
+        for column in &self.columns {
+            if let Some(fk_info) = &column.foreign_key {
+                definitions.push(format!(
+                    "FOREIGN KEY ({}) REFERENCES {}({})",
+                    sql_identifier(&column.name),
+                    sql_identifier(&fk_info.reference_table),
+                    sql_identifier(&fk_info.reference_column)
+                ));
+            }
+        }
+
+        format!(
+            "CREATE TABLE {} (\n    {}\n);",
+            sql_identifier(&self.name),
+            definitions.join(",\n    ")
+        )
+    }
+}
+
+/// Double-quotes a name unless it is a plain identifier: a letter or underscore followed by
+/// letters, digits and underscores.
+fn sql_identifier(name: &str) -> Cow<'_, str> {
+    let mut chars = name.chars();
+    let is_plain = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_plain {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("\"{}\"", name.replace('"', "\"\"")))
+    }
+}
+
+/// Writes a value as a SQL literal. Floats keep all their digits, unlike their display form.
+fn sql_literal(value: &Value) -> String {
+    match value {
+        Value::Text(text) => format!("'{}'", text.replace('\'', "''")),
+        Value::Float(value) => format!("{:?}", value),
+        value => value.to_string(),
+    }
+}
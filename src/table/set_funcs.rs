@@ -0,0 +1,214 @@
+use crate::column::Value;
+use crate::table::{Error, Table};
+use std::collections::HashSet;
+
+impl Table {
+    /// Returns the distinct records found in this table or in another one, like SQL's `UNION`.
+    /// Whole records are compared, and NULL values equal each other for this purpose. The
+    /// tables need the same column names with the same data types, in any order.
+    ///
+    /// The result keeps the name, column names and data types of this table but none of its
+    /// constraints, like `union_all`: two different records may share a primary key value.
+    /// Its records are the distinct records of this table first, followed by the new records
+    /// of `other`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The table to combine with this one.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - A new table with the distinct records of both tables.
+    /// * `Err(Error)` - An error if the schemas differ.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::SchemaMismatch` - With every column that is missing from one of the tables or
+    ///   has another data type in each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let customers = Table::new("customers", /* ... */);
+    /// let suppliers = Table::new("suppliers", /* ... */);
+    /// customers.union(&suppliers).unwrap().show();
+    /// ```
+    pub fn union(&self, other: &Table) -> Result<Table, Error> {
+        let all_records = self.union_all(other)?;
+
+        let mut seen_records = HashSet::new();
+        let row_indices: Vec<usize> = (0..all_records.row_count())
+            .filter(|&row_idx| seen_records.insert(all_records.row_key(row_idx)))
+            .collect();
+        Ok(all_records.with_rows(&row_indices))
+    }
+
+    /// Returns every record of this table followed by every record of another one, like SQL's
+    /// `UNION ALL`. The tables need the same column names with the same data types, in any
+    /// order. Nothing is deduplicated, so the result keeps the column names and data types of
+    /// this table but none of its constraints: a primary key value in both tables is kept twice.
+    /// Use `union` to keep each distinct record once.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The table whose records follow the records of this one.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - A new table with the records of both tables.
+    /// * `Err(Error)` - An error if the schemas differ.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::SchemaMismatch` - With every column that is missing from one of the tables or
+    ///   has another data type in each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let january = Table::new("sales_january", /* ... */);
+    /// let february = Table::new("sales_february", /* ... */);
+    /// january.union_all(&february).unwrap().show();
+    /// ```
+    pub fn union_all(&self, other: &Table) -> Result<Table, Error> {
+        let other_records = self.records_to_append(other)?;
+
+        let mut result = self.with_rows(&(0..self.row_count()).collect::<Vec<_>>());
+        result.primary_key_column = None;
+        result.referenced_as_foreign_key.clear();
+        for (column_idx, column) in result.columns.iter_mut().enumerate() {
+            column.is_primary_key = false;
+            column.foreign_key = None;
+            column.not_null = false;
+            column.unique = false;
+            column.data.extend(
+                other_records
+                    .iter()
+                    .map(|record| record[column_idx].clone()),
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the distinct records of this table that are also in another one, like SQL's
+    /// `INTERSECT`. Records are compared as in `union`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The table whose records are kept.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - A new table with the metadata of this table and the common records, in
+    ///   the order of this table.
+    /// * `Err(Error)` - An error if the schemas differ.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::SchemaMismatch` - With every column that is missing from one of the tables or
+    ///   has another data type in each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let january_buyers = Table::new("january_buyers", /* ... */);
+    /// let february_buyers = Table::new("february_buyers", /* ... */);
+    /// january_buyers.intersect(&february_buyers).unwrap().show();
+    /// ```
+    pub fn intersect(&self, other: &Table) -> Result<Table, Error> {
+        self.distinct_rows_by_membership(other, true)
+    }
+
+    /// Returns the distinct records of this table that are not in another one, like SQL's
+    /// `EXCEPT`. Records are compared as in `union`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The table whose records are removed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - A new table with the metadata of this table and the remaining records,
+    ///   in the order of this table.
+    /// * `Err(Error)` - An error if the schemas differ.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::SchemaMismatch` - With every column that is missing from one of the tables or
+    ///   has another data type in each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let subscribers = Table::new("subscribers", /* ... */);
+    /// let unsubscribed = Table::new("unsubscribed", /* ... */);
+    /// subscribers.except(&unsubscribed).unwrap().show();
+    /// ```
+    pub fn except(&self, other: &Table) -> Result<Table, Error> {
+        self.distinct_rows_by_membership(other, false)
+    }
+
+    /// Returns the distinct records of this table that are not in another one. This is
+    /// `except` under its set-theory name, e.g. to diff two snapshots of the same data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let yesterday = Table::new("users_yesterday", /* ... */);
+    /// let today = Table::new("users_today", /* ... */);
+    /// // Records added or changed since yesterday
+    /// today.difference(&yesterday).unwrap().show();
+    /// ```
+    pub fn difference(&self, other: &Table) -> Result<Table, Error> {
+        self.except(other)
+    }
+
+    /// Keeps the distinct records of this table that are (or, with `in_other` false, are not)
+    /// records of `other`.
+    fn distinct_rows_by_membership(&self, other: &Table, in_other: bool) -> Result<Table, Error> {
+        let other_records = self.records_to_append(other)?;
+        let other_keys: HashSet<Vec<&Value>> = other_records
+            .iter()
+            .map(|record| record.iter().collect())
+            .collect();
+
+        let mut seen_records = HashSet::new();
+        let row_indices: Vec<usize> = (0..self.row_count())
+            .filter(|&row_idx| {
+                let key = self.row_key(row_idx);
+                other_keys.contains(&key) == in_other && seen_records.insert(key)
+            })
+            .collect();
+
+        Ok(self.with_rows(&row_indices))
+    }
+
+    /// The values of a record in column order, as a key to compare whole records. It relies on
+    /// `Value` equality, so NULL matches NULL and decimals that only differ in scale match.
+    /// Values missing from a short column read as NULL.
+    fn row_key(&self, row_idx: usize) -> Vec<&Value> {
+        self.columns
+            .iter()
+            .map(|column| column.data.get(row_idx).unwrap_or(&Value::Null))
+            .collect()
+    }
+}
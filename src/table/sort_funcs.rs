@@ -0,0 +1,112 @@
+use crate::column::Value;
+use crate::table::{Error, Table};
+use std::cmp::Ordering;
+
+impl Table {
+    /// Returns a new table with the records sorted by the values of the given column.
+    /// NULL values are placed at the end regardless of the direction, and records with equal
+    /// values keep their original relative order.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - The name of the column to sort by.
+    /// * `descending` - Whether to sort from the largest value to the smallest.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - The sorted table, with the same columns and metadata as this one.
+    /// * `Err(Error)` - An error if the column does not exist.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::NonExistingColumn` - If the column does not exist in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let by_age = table.order_by("age", false).unwrap();
+    /// ```
+    pub fn order_by(&self, column_name: &str, descending: bool) -> Result<Table, Error> {
+        let column = &self.columns[self.column_position(column_name)?];
+
+        let mut row_indices: Vec<usize> = (0..self.row_count()).collect();
+        row_indices.sort_by(|&a, &b| compare_rows(&column.data, a, b, descending));
+
+        Ok(self.with_rows(&row_indices))
+    }
+
+    /// Returns a new table holding the first `n` records as ordered by `order_by`, e.g. the
+    /// five highest scores. Only the selected records are fully sorted.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - The name of the column to sort by.
+    /// * `n` - The number of records to return. If it exceeds the number of records, all are returned.
+    /// * `descending` - Whether to take the largest values instead of the smallest.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - A table with at most `n` records, with the same columns and metadata as this one.
+    /// * `Err(Error)` - An error if the column does not exist.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::NonExistingColumn` - If the column does not exist in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let leaderboard = table.top_n("score", 5, true).unwrap();
+    /// ```
+    pub fn top_n(&self, column_name: &str, n: usize, descending: bool) -> Result<Table, Error> {
+        let column = &self.columns[self.column_position(column_name)?];
+
+        // Row indices break ties, which keeps the selection deterministic and stable
+        let compare =
+            |&a: &usize, &b: &usize| compare_rows(&column.data, a, b, descending).then(a.cmp(&b));
+
+        let mut row_indices: Vec<usize> = (0..self.row_count()).collect();
+        if n < row_indices.len() {
+            if n == 0 {
+                row_indices.clear();
+            } else {
+                row_indices.select_nth_unstable_by(n - 1, compare);
+                row_indices.truncate(n);
+            }
+        }
+        row_indices.sort_by(compare);
+
+        Ok(self.with_rows(&row_indices))
+    }
+}
+
+/// Orders two records by their value in `data`, placing NULLs (and missing values) last.
+/// Other values are ordered by `Value::cmp_value`.
+fn compare_rows(data: &[Value], a: usize, b: usize, descending: bool) -> Ordering {
+    let left = data.get(a).unwrap_or(&Value::Null);
+    let right = data.get(b).unwrap_or(&Value::Null);
+
+    match (left, right) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Greater,
+        (_, Value::Null) => Ordering::Less,
+        _ => {
+            let ordering = left.cmp_value(right).unwrap_or(Ordering::Equal);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+    }
+}
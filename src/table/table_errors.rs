@@ -1,7 +1,13 @@
+use crate::column::ColumnDataType;
+
 #[derive(Debug)]
 pub enum Error {
     MismatchedColumnCount,
-    ParseError(usize, String),
+    ParseError {
+        column_name: String,
+        value: String,
+        expected: ColumnDataType,
+    },
     NonExistingColumns(Vec<String>),
     NonExistingColumn(String), // column_name
     InvalidOperator(String),   // operator_str
@@ -11,7 +17,30 @@ pub enum Error {
     DuplicatePrimaryKey,
     NullPrimaryKey,
     CannotBatchUpdatePrimaryKey,
-    PrimaryKeyNotProvided(String), // column_name
+    CannotUpdatePrimaryKey(String),        // column_name
+    CannotDropPrimaryKey(String),          // column_name
+    PrimaryKeyNotProvided(String),         // column_name
+    NonNumericColumn(String),              // column_name
+    ColumnAlreadyExists(String),           // column_name
+    InvalidConditionValue(String, String), // column_name, value
+    NullValue(String),                     // column_name
+    DuplicateValue(String, String),        // column_name, value
+    DuplicateOutputColumn(String),         // column_name
+    DuplicateColumnInInsert(String),       // column_name
+    InvalidName(String, String),           // name, reason
+    AmbiguousColumnName(String),           // column_name
+    InvalidExpression(String),             // reason
+    InvalidConditionSyntax(usize, String), // character position, reason
+    SchemaMismatch(Vec<String>),           // descriptions of the differing columns
+    NoColumns,
+    NoPrimaryKey,
+    TypeMismatch(String, ColumnDataType), // column_name, expected data type
+    InconsistentColumnLengths(String, usize, usize), // column_name, column_length, expected_length
+    IncompatibleTypeConversion(String, ColumnDataType, String), // column_name, new data type, value
+    ForeignKeyTypeChange(String),         // column_name
+    InvalidColumnOrder(String),           // reason
+    DuplicateValues(String, Vec<String>), // column_name, duplicated values
+    NonFiniteFloat(String, String),       // column_name, value
 }
 
 impl std::fmt::Display for Error {
@@ -20,9 +49,15 @@ impl std::fmt::Display for Error {
             Error::MismatchedColumnCount => {
                 write!(f, "Number of values doesn't match the number of columns")
             }
-            Error::ParseError(index, value) => {
-                write!(f, "Failed to parse value '{}' at index {}", value, index)
-            }
+            Error::ParseError {
+                column_name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "Failed to parse value '{}' as {} for column '{}'",
+                value, expected, column_name
+            ),
             Error::NonExistingColumns(columns) => write!(
                 f,
                 "The following columns do not exist: {}",
@@ -40,11 +75,123 @@ impl std::fmt::Display for Error {
             Error::CannotBatchUpdatePrimaryKey => {
                 write!(f, "Primary key column disallows batch updates")
             }
+            Error::CannotUpdatePrimaryKey(column_name) => {
+                write!(
+                    f,
+                    "The primary key column '{}' cannot be updated",
+                    column_name
+                )
+            }
+            Error::CannotDropPrimaryKey(column_name) => {
+                write!(
+                    f,
+                    "The primary key column '{}' cannot be dropped",
+                    column_name
+                )
+            }
             Error::PrimaryKeyNotProvided(column_name) => {
                 write!(f, "Primary key column '{}' not provided", column_name)
             }
+            Error::NonNumericColumn(column_name) => {
+                write!(f, "The column '{}' is not numeric", column_name)
+            }
+            Error::ColumnAlreadyExists(column_name) => {
+                write!(f, "The column '{}' already exists", column_name)
+            }
+            Error::InvalidConditionValue(column_name, value) => write!(
+                f,
+                "Invalid value '{}' in condition on column '{}'",
+                value, column_name
+            ),
+            Error::NullValue(column_name) => {
+                write!(
+                    f,
+                    "The column '{}' does not accept null values",
+                    column_name
+                )
+            }
+            Error::DuplicateValue(column_name, value) => write!(
+                f,
+                "Duplicate value '{}' in unique column '{}'",
+                value, column_name
+            ),
+            Error::DuplicateOutputColumn(column_name) => write!(
+                f,
+                "The output column '{}' appears more than once in the projection",
+                column_name
+            ),
+            Error::InvalidName(name, reason) => write!(f, "Invalid name {:?}: {}", name, reason),
+            Error::AmbiguousColumnName(column_name) => write!(
+                f,
+                "The column name '{}' matches several columns that only differ by case",
+                column_name
+            ),
+            Error::DuplicateColumnInInsert(column_name) => write!(
+                f,
+                "The column '{}' is given more than once in the insert",
+                column_name
+            ),
+            Error::InconsistentColumnLengths(column_name, column_length, expected_length) => {
+                write!(
+                    f,
+                    "The column '{}' holds {} values, but the table has {} records",
+                    column_name, column_length, expected_length
+                )
+            }
+            Error::TypeMismatch(column_name, expected) => write!(
+                f,
+                "The column '{}' expects a value of type {}",
+                column_name, expected
+            ),
+            Error::IncompatibleTypeConversion(column_name, new_type, value) => write!(
+                f,
+                "The value '{}' of column '{}' cannot be converted to {}",
+                value, column_name, new_type
+            ),
+            Error::ForeignKeyTypeChange(column_name) => write!(
+                f,
+                "The column '{}' is part of a foreign key, its type cannot be changed",
+                column_name
+            ),
+            Error::DuplicateValues(column_name, values) => write!(
+                f,
+                "The column '{}' holds duplicates of the values: {}",
+                column_name,
+                values.join(", ")
+            ),
+            Error::NonFiniteFloat(column_name, value) => write!(
+                f,
+                "The column '{}' only holds finite floats, not '{}'",
+                column_name, value
+            ),
+            Error::InvalidColumnOrder(reason) => write!(f, "Invalid column order: {}", reason),
+            Error::NoPrimaryKey => write!(f, "The table has no primary key column"),
+            Error::NoColumns => write!(f, "A table needs at least one column"),
+            Error::InvalidExpression(reason) => write!(f, "Invalid expression: {}", reason),
+            Error::InvalidConditionSyntax(position, reason) => {
+                write!(f, "Invalid condition at position {}: {}", position, reason)
+            }
+            Error::SchemaMismatch(differences) => {
+                write!(f, "The table schemas differ: {}", differences.join("; "))
+            }
         }
     }
 }
 
 impl std::error::Error for Error {}
+
+/// Error of a batch insert: the position of the first rejected record in the batch, and why it
+/// was rejected. Nothing is inserted when a batch fails.
+#[derive(Debug)]
+pub struct BatchError {
+    pub row_idx: usize,
+    pub error: Error,
+}
+
+impl std::fmt::Display for BatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Record {} of the batch: {}", self.row_idx, self.error)
+    }
+}
+
+impl std::error::Error for BatchError {}
@@ -1,6 +1,8 @@
-use crate::column::{Column, Value};
-use crate::table::{Error, Table};
-use std::collections::HashSet;
+use crate::column::{Column, ColumnDataType, Value};
+use crate::table::helpers::{column_fill_value, parse_column_value, validate_name, XorShift64};
+use crate::table::{Error, NestedCondition, Table, MAX_NAME_LENGTH};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 
 impl Table {
     /// Creates a new `Table` instance with the provided table name and columns.
@@ -13,12 +15,14 @@ impl Table {
     /// # Returns
     ///
     /// * `Ok(Table)` - A `Table` instance with the provided name and columns.
-    /// * `Err(Error)` - An error if multiple columns are marked as the primary key.
+    /// * `Err(Error)` - An error if a name is invalid, no columns are given or multiple columns are marked as the primary key.
     ///
     /// # Errors
     ///
-    /// This function can return the following error:
+    /// This function can return the following errors:
     ///
+    /// * `Error::InvalidName` - If the table name or a column name is empty, has leading or trailing whitespace, contains a control character or is longer than `MAX_NAME_LENGTH`.
+    /// * `Error::NoColumns` - If `columns` is empty.
     /// * `Error::MultiplePrimaryKeys` - If more than one column is marked as the primary key.
     ///
     /// # Examples
@@ -28,14 +32,23 @@ impl Table {
     /// use crate::table::Table;
     ///
     /// let columns = vec![
-    ///     Column::new("id", ColumnDataType::Integer, Some(true)), // Primary key column
-    ///     Column::new("name", ColumnDataType::Text, None),
-    ///     Column::new("age", ColumnDataType::Integer, None),
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    ///     Column::new("age", ColumnDataType::Integer, None, false, None),
     /// ];
     ///
     /// let table = Table::new("users", columns).unwrap();
     /// ```
     pub fn new(table_name: &str, columns: Vec<Column>) -> Result<Table, Error> {
+        validate_name(table_name, MAX_NAME_LENGTH)?;
+        for column in &columns {
+            validate_name(&column.name, MAX_NAME_LENGTH)?;
+        }
+
+        if columns.is_empty() {
+            return Err(Error::NoColumns);
+        }
+
         let mut primary_key_column: Option<Column> = None;
 
         // Validate that only one column is marked as the primary key
@@ -53,9 +66,482 @@ impl Table {
             columns,
             primary_key_column,
             referenced_as_foreign_key: Vec::new(),
+            indexes: HashMap::new(),
+            case_insensitive_names: false,
         })
     }
 
+    /// Adds a new column at the end of the table. The existing records get the same value in
+    /// it: the given fill value, or else the column's default value, or else NULL.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The new column. Its data is replaced by the fill values.
+    /// * `fill` - An optional value for the existing records, parsed into the column's data type.
+    ///   "null" stands for NULL.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the column was added.
+    /// * `Err(Error)` - An error if the column can't be added, in which case the table is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::InvalidName` - If the column name is invalid, see `Table::new`.
+    /// * `Error::ColumnAlreadyExists` - If the table already has a column with this name.
+    /// * `Error::MultiplePrimaryKeys` - If the column is a primary key and the table already has one.
+    /// * `Error::ParseError` - If the fill value cannot be parsed into the column's data type.
+    /// * `Error::NullPrimaryKey` / `Error::NullValue` - If the existing records would be filled with NULL
+    ///   in a primary key or not-null column.
+    /// * `Error::DuplicatePrimaryKey` / `Error::DuplicateValue` - If several existing records would be
+    ///   filled with the same value in a primary key or unique column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::{Column, ColumnDataType};
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// let column = Column::builder("country", ColumnDataType::Text).not_null().build();
+    /// table.add_column(column, Some("unknown".to_string())).unwrap();
+    /// ```
+    pub fn add_column(&mut self, column: Column, fill: Option<String>) -> Result<(), Error> {
+        validate_name(&column.name, MAX_NAME_LENGTH)?;
+
+        let lowercase_name = column.name.to_lowercase();
+        if self.columns.iter().any(|c| {
+            c.name == column.name
+                || (self.case_insensitive_names && c.name.to_lowercase() == lowercase_name)
+        }) {
+            return Err(Error::ColumnAlreadyExists(column.name));
+        }
+
+        if column.is_primary_key && self.primary_key_column.is_some() {
+            return Err(Error::MultiplePrimaryKeys);
+        }
+
+        let fill_value = column_fill_value(&column, fill)?;
+        let row_count = self.row_count();
+        if fill_value == Value::Null && row_count > 0 {
+            if column.is_primary_key {
+                return Err(Error::NullPrimaryKey);
+            }
+            if column.not_null {
+                return Err(Error::NullValue(column.name));
+            }
+        } else if fill_value != Value::Null && row_count > 1 {
+            if column.is_primary_key {
+                return Err(Error::DuplicatePrimaryKey);
+            }
+            if column.unique {
+                return Err(Error::DuplicateValue(column.name, fill_value.to_string()));
+            }
+        }
+
+        let mut column = column;
+        column.data = vec![fill_value; row_count];
+        if column.is_primary_key {
+            self.primary_key_column = Some(column.clone());
+        }
+        self.columns.push(column);
+
+        Ok(())
+    }
+
+    /// Removes a column and its data from the table, along with its secondary index.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - The name of the column to drop.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the column was dropped.
+    /// * `Err(Error)` - An error if the column can't be dropped, in which case the table is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If the column does not exist in the table.
+    /// * `Error::CannotDropPrimaryKey` - If the column is the primary key column.
+    /// * `Error::NoColumns` - If the column is the last one of the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// table.drop_column("age").unwrap();
+    /// ```
+    pub fn drop_column(&mut self, column_name: &str) -> Result<(), Error> {
+        let column_idx = self.column_position(column_name)?;
+        let column = &self.columns[column_idx];
+
+        if column.is_primary_key {
+            return Err(Error::CannotDropPrimaryKey(column.name.clone()));
+        }
+        if self.columns.len() == 1 {
+            return Err(Error::NoColumns);
+        }
+
+        let column = self.columns.remove(column_idx);
+        self.indexes.remove(&column.name);
+
+        Ok(())
+    }
+
+    /// Removes the primary key constraint of the table. The column and its data stay, as an
+    /// ordinary column, so it can be updated in batches with `update_column` again.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the primary key constraint was removed.
+    /// * `Err(Error)` - An error if the table has no primary key.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::NoPrimaryKey` - If the table has no primary key column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// table.drop_primary_key().unwrap();
+    /// assert!(table.primary_key().is_none());
+    /// ```
+    pub fn drop_primary_key(&mut self) -> Result<(), Error> {
+        let column = self
+            .columns
+            .iter_mut()
+            .find(|column| column.is_primary_key)
+            .ok_or(Error::NoPrimaryKey)?;
+        column.is_primary_key = false;
+        self.primary_key_column = None;
+
+        Ok(())
+    }
+
+    /// Makes an existing column unique, once its current values are checked. NULL values don't
+    /// count as duplicates, like in inserts.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - The name of the column to make unique.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the column is unique now.
+    /// * `Err(Error)` - An error if the column holds duplicates, in which case it is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If the column does not exist in the table.
+    /// * `Error::DuplicateValues` - With every value held by several records, each once, in the
+    ///   order they first appear.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// table.add_unique_constraint("email").unwrap();
+    /// ```
+    pub fn add_unique_constraint(&mut self, column_name: &str) -> Result<(), Error> {
+        let column_idx = self.column_position(column_name)?;
+        let column = &self.columns[column_idx];
+
+        let mut seen_values = HashSet::new();
+        let mut duplicated_values: Vec<&Value> = Vec::new();
+        for value in column.data.iter().filter(|value| **value != Value::Null) {
+            if !seen_values.insert(value) && !duplicated_values.contains(&value) {
+                duplicated_values.push(value);
+            }
+        }
+        if !duplicated_values.is_empty() {
+            return Err(Error::DuplicateValues(
+                column.name.clone(),
+                duplicated_values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect(),
+            ));
+        }
+
+        let column = &mut self.columns[column_idx];
+        column.unique = true;
+        if column.is_primary_key {
+            self.primary_key_column = Some(column.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Renames a column of the table. The primary key and a secondary index on the column
+    /// follow the new name.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_name` - The current name of the column.
+    /// * `new_name` - The new name of the column.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the column was renamed.
+    /// * `Err(Error)` - An error if the column can't be renamed, in which case the table is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If the column does not exist in the table.
+    /// * `Error::InvalidName` - If the new name is invalid, see `Table::new`.
+    /// * `Error::ColumnAlreadyExists` - If another column of the table already has the new name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// table.rename_column("user_name", "name").unwrap();
+    /// ```
+    pub fn rename_column(&mut self, old_name: &str, new_name: &str) -> Result<(), Error> {
+        let column_idx = self.column_position(old_name)?;
+        validate_name(new_name, MAX_NAME_LENGTH)?;
+
+        let lowercase_name = new_name.to_lowercase();
+        let collision = self.columns.iter().enumerate().any(|(idx, c)| {
+            idx != column_idx
+                && (c.name == new_name
+                    || (self.case_insensitive_names && c.name.to_lowercase() == lowercase_name))
+        });
+        if collision {
+            return Err(Error::ColumnAlreadyExists(new_name.to_string()));
+        }
+
+        let old_name = std::mem::replace(&mut self.columns[column_idx].name, new_name.to_string());
+        if let Some(primary_key_column) = &mut self.primary_key_column {
+            if primary_key_column.name == old_name {
+                primary_key_column.name = new_name.to_string();
+            }
+        }
+        if let Some(index) = self.indexes.remove(&old_name) {
+            self.indexes.insert(new_name.to_string(), index);
+        }
+
+        Ok(())
+    }
+
+    /// Rearranges the columns of the table, e.g. for `show`, exports and positional inserts.
+    /// Each column keeps its data, constraints and indexes.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The names of all the columns of the table, each once, in their new order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the columns were rearranged.
+    /// * `Err(Error)` - An error if the order is invalid, in which case the table is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If a name in the order does not exist in the table.
+    /// * `Error::InvalidColumnOrder` - If a column is listed twice or left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// table
+    ///     .reorder_columns(vec!["user_name".to_string(), "user_id".to_string()])
+    ///     .unwrap();
+    /// ```
+    pub fn reorder_columns(&mut self, order: Vec<String>) -> Result<(), Error> {
+        let mut positions = Vec::with_capacity(order.len());
+        for column_name in &order {
+            let column_idx = self.column_position(column_name)?;
+            if positions.contains(&column_idx) {
+                return Err(Error::InvalidColumnOrder(format!(
+                    "column '{}' is listed twice",
+                    self.columns[column_idx].name
+                )));
+            }
+            positions.push(column_idx);
+        }
+        if let Some(missing) = (0..self.columns.len()).find(|idx| !positions.contains(idx)) {
+            return Err(Error::InvalidColumnOrder(format!(
+                "column '{}' is missing",
+                self.columns[missing].name
+            )));
+        }
+
+        let mut columns: Vec<Option<Column>> = self.columns.drain(..).map(Some).collect();
+        self.columns = positions
+            .into_iter()
+            .filter_map(|column_idx| columns[column_idx].take())
+            .collect();
+
+        Ok(())
+    }
+
+    /// Changes the data type of a column, converting its values, default value included:
+    ///
+    /// * Integers become floats or decimals.
+    /// * Floats and decimals become integers if they are whole, and each other if they fit.
+    /// * Any value becomes text through its display form, so floats keep two decimals.
+    /// * Text becomes a number if it parses as one.
+    ///
+    /// NULL stays NULL. Foreign key columns and primary keys referenced by other tables keep
+    /// their type, since the values on both sides of the reference would no longer compare.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - The name of the column to convert.
+    /// * `new_type` - The new data type of the column.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the column was converted.
+    /// * `Err(Error)` - An error if the column can't be converted, in which case the table is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumn` - If the column does not exist in the table.
+    /// * `Error::ForeignKeyTypeChange` - If the column is a foreign key or is referenced by one.
+    /// * `Error::IncompatibleTypeConversion` - If a value can't be converted to the new type.
+    /// * `Error::DuplicatePrimaryKey` / `Error::DuplicateValue` - If different values of a primary
+    ///   key or unique column become equal, e.g. floats only differing after two decimals as text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::column::ColumnDataType;
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// table.change_column_type("age", ColumnDataType::Float).unwrap();
+    /// ```
+    pub fn change_column_type(
+        &mut self,
+        column_name: &str,
+        new_type: ColumnDataType,
+    ) -> Result<(), Error> {
+        let column_idx = self.column_position(column_name)?;
+        let column = &self.columns[column_idx];
+        if column.data_type == new_type {
+            return Ok(());
+        }
+        if column.foreign_key.is_some()
+            || (column.is_primary_key && !self.referenced_as_foreign_key.is_empty())
+        {
+            return Err(Error::ForeignKeyTypeChange(column.name.clone()));
+        }
+
+        let mut converted_column = column.clone_without_data();
+        converted_column.data_type = new_type;
+        let convert = |value: &Value| {
+            convert_value(value, &converted_column).ok_or_else(|| {
+                Error::IncompatibleTypeConversion(column.name.clone(), new_type, value.to_string())
+            })
+        };
+        let data = column
+            .data
+            .iter()
+            .map(convert)
+            .collect::<Result<Vec<_>, _>>()?;
+        let default_value = column.default_value.as_ref().map(convert).transpose()?;
+
+        if column.is_primary_key || column.unique {
+            let mut seen_values = HashSet::new();
+            for value in data.iter().filter(|value| **value != Value::Null) {
+                if !seen_values.insert(value) {
+                    return Err(if column.is_primary_key {
+                        Error::DuplicatePrimaryKey
+                    } else {
+                        Error::DuplicateValue(column.name.clone(), value.to_string())
+                    });
+                }
+            }
+        }
+
+        let column = &mut self.columns[column_idx];
+        column.data_type = new_type;
+        column.data = data;
+        column.default_value = default_value;
+        if column.is_primary_key {
+            self.primary_key_column = Some(column.clone());
+        }
+        self.rebuild_indexes();
+
+        Ok(())
+    }
+
+    /// Returns the name of the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// assert_eq!(table.name(), "users.csv");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the columns of the table, in order, along with the values they hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// for column in table.columns() {
+    ///     println!("{}: {} values", column.name, column.data.len());
+    /// }
+    /// ```
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Returns the primary key column of the table, with its current values, or `None` if the
+    /// table has no primary key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// if let Some(primary_key) = table.primary_key() {
+    ///     println!("Primary key: {}", primary_key.name);
+    /// }
+    /// ```
+    pub fn primary_key(&self) -> Option<&Column> {
+        self.columns.iter().find(|column| column.is_primary_key)
+    }
+
     /// Creates a copy of the current `Table` instance.
     ///
     /// # Returns
@@ -69,9 +555,9 @@ impl Table {
     /// use crate::table::Table;
     ///
     /// let mut table = Table::new("users", vec![
-    ///     Column::new("id", ColumnDataType::Integer, Some(true)), // Primary key column
-    ///     Column::new("name", ColumnDataType::Text, None),
-    ///     Column::new("age", ColumnDataType::Integer, None),
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    ///     Column::new("age", ColumnDataType::Integer, None, false, None),
     /// ]).unwrap();
     ///
     /// // Insert some initial data
@@ -105,6 +591,8 @@ impl Table {
             columns: new_columns,
             primary_key_column: new_primary_key_column,
             referenced_as_foreign_key: self.referenced_as_foreign_key.clone(),
+            indexes: HashMap::new(),
+            case_insensitive_names: self.case_insensitive_names,
         }
     }
 
@@ -119,9 +607,9 @@ impl Table {
     /// use crate::table::Table;
     ///
     /// let mut table = Table::new("users", vec![
-    ///     Column::new("id", ColumnDataType::Integer, Some(true)), // Primary key column
-    ///     Column::new("name", ColumnDataType::Text, None),
-    ///     Column::new("age", ColumnDataType::Integer, None),
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    ///     Column::new("age", ColumnDataType::Integer, None, false, None),
     /// ]).unwrap();
     ///
     /// // Insert some initial data
@@ -131,49 +619,7 @@ impl Table {
     /// table.show();
     /// ```
     pub fn show(&self) {
-        // Find the maximum length of column names
-        let max_column_name_len = self
-            .columns
-            .iter()
-            .map(|column| column.name.len())
-            .max()
-            .unwrap_or(0);
-
-        // Print the column names
-        for column in &self.columns {
-            let padded_name = format!("{:>width$}", column.name, width = max_column_name_len);
-            print!("{} ", padded_name);
-        }
-        println!();
-
-        // Print a separator line
-        let separator_line: String = std::iter::repeat("-")
-            .take(max_column_name_len * self.columns.len() + self.columns.len() - 1)
-            .collect();
-        println!("{}", separator_line);
-
-        // Get the maximum number of rows across all columns
-        let max_rows = self
-            .columns
-            .iter()
-            .map(|column| column.data.len())
-            .max()
-            .unwrap_or(0);
-
-        // Print the data rows
-        for row_idx in 0..max_rows {
-            for (_col_idx, column) in self.columns.iter().enumerate() {
-                if row_idx < column.data.len() {
-                    let value = &column.data[row_idx];
-                    let padded_value = format!("{:<width$}", value, width = max_column_name_len);
-                    print!("{} ", padded_value);
-                } else {
-                    let padding = " ".repeat(max_column_name_len);
-                    print!("{} ", padding);
-                }
-            }
-            println!();
-        }
+        println!("{}", self);
     }
 
     /// Displays the requested columns from the table.
@@ -205,80 +651,164 @@ impl Table {
     /// table.project(vec!["user_id".to_string(), "age".to_string()]).unwrap();
     /// ```
     pub fn project(&self, column_names: Vec<String>) -> Result<(), Error> {
-        if column_names.is_empty() {
+        self.project_with_aliases(column_names.into_iter().map(|name| (name, None)).collect())
+    }
+
+    /// Displays the requested columns from the table, renaming the ones that are given an alias.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - A vector of `(source_column, alias)` pairs. The output header uses the alias when one is given and the source column name otherwise. If an empty vector is provided, the function will call the `show` function to display all columns.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the projection operation is successful.
+    /// * `Err(Error)` if an error occurs during the projection operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumns` - If one or more of the source columns do not exist in the table.
+    /// * `Error::DuplicateOutputColumn` - If two projected columns end up with the same output name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// // Display user_name as "name" and age unchanged
+    /// table.project_with_aliases(vec![
+    ///     ("user_name".to_string(), Some("name".to_string())),
+    ///     ("age".to_string(), None),
+    /// ]).unwrap();
+    /// ```
+    pub fn project_with_aliases(
+        &self,
+        columns: Vec<(String, Option<String>)>,
+    ) -> Result<(), Error> {
+        if columns.is_empty() {
             // If no column names are provided, call the show function
             self.show();
             return Ok(());
         }
 
-        // Check if all provided column names exist in the table
-        let column_names_set: HashSet<String> = column_names.iter().cloned().collect();
-        let existing_columns: HashSet<String> =
-            self.columns.iter().map(|c| c.name.clone()).collect();
-        let non_existing_columns: Vec<String> = column_names_set
-            .difference(&existing_columns)
-            .cloned()
-            .collect();
+        println!("{}", self.project_into_table_with_aliases(columns)?);
 
-        if !non_existing_columns.is_empty() {
-            return Err(Error::NonExistingColumns(non_existing_columns));
-        }
+        Ok(())
+    }
 
-        // Find the maximum length of requested column names
-        let max_column_name_len = column_names
-            .iter()
-            .map(|name| name.len())
-            .max()
-            .unwrap_or(0);
+    /// Returns a new table holding only the requested columns, in the requested order.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_names` - A vector of strings representing the names of the columns to keep.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - The projected table. Column metadata is kept, and the primary key is kept if its column is projected.
+    /// * `Err(Error)` if an error occurs during the projection operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumns` - If one or more of the provided column names do not exist in the table.
+    /// * `Error::DuplicateOutputColumn` - If a column is requested more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let ages = table.project_into_table(vec!["user_id".to_string(), "age".to_string()]).unwrap();
+    /// ```
+    pub fn project_into_table(&self, column_names: Vec<String>) -> Result<Table, Error> {
+        self.project_into_table_with_aliases(
+            column_names.into_iter().map(|name| (name, None)).collect(),
+        )
+    }
+
+    /// Returns a new table holding only the requested columns, renaming the ones that are given an alias.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - A vector of `(source_column, alias)` pairs. The returned table's column uses the alias when one is given and the source column name otherwise.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Table)` - The projected table. Column metadata is kept, and the primary key is kept if its column is projected.
+    /// * `Err(Error)` if an error occurs during the projection operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NonExistingColumns` - If one or more of the source columns do not exist in the table.
+    /// * `Error::DuplicateOutputColumn` - If two projected columns end up with the same output name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::new("users", /* ... */);
+    /// let report = table.project_into_table_with_aliases(vec![
+    ///     ("user_name".to_string(), Some("name".to_string())),
+    ///     ("age".to_string(), None),
+    /// ]).unwrap();
+    /// ```
+    pub fn project_into_table_with_aliases(
+        &self,
+        columns: Vec<(String, Option<String>)>,
+    ) -> Result<Table, Error> {
+        let columns = columns
+            .into_iter()
+            .map(|(source, alias)| Ok((self.resolve_column_name(&source)?.to_string(), alias)))
+            .collect::<Result<Vec<_>, Error>>()?;
 
-        // Print the requested column names
-        for column_name in &column_names {
-            let padded_name = format!("{:>width$}", column_name, width = max_column_name_len);
-            print!("{} ", padded_name);
+        // Check if all provided source columns exist in the table
+        let existing_columns: HashSet<&str> =
+            self.columns.iter().map(|c| c.name.as_str()).collect();
+        let mut non_existing_columns: Vec<String> = Vec::new();
+        for (source, _) in &columns {
+            if !existing_columns.contains(source.as_str()) && !non_existing_columns.contains(source)
+            {
+                non_existing_columns.push(source.clone());
+            }
         }
-        println!();
 
-        // Print a separator line
-        let separator_line: String = std::iter::repeat("-")
-            .take(max_column_name_len * column_names.len() + column_names.len() - 1)
-            .collect();
-        println!("{}", separator_line);
+        if !non_existing_columns.is_empty() {
+            return Err(Error::NonExistingColumns(non_existing_columns));
+        }
 
-        // Get the maximum number of rows across the requested columns
-        let max_rows = column_names
-            .iter()
-            .map(|name| {
-                self.columns
-                    .iter()
-                    .find(|c| c.name == *name)
-                    .map(|c| c.data.len())
-                    .unwrap_or(0)
-            })
-            .max()
-            .unwrap_or(0);
-
-        // Print the data rows for the requested columns
-        for row_idx in 0..max_rows {
-            for column_name in &column_names {
-                if let Some(column) = self.columns.iter().find(|c| c.name == *column_name) {
-                    if row_idx < column.data.len() {
-                        let value = &column.data[row_idx];
-                        let padded_value =
-                            format!("{:>width$}", value, width = max_column_name_len);
-                        print!("{} ", padded_value);
-                    } else {
-                        let padding = " ".repeat(max_column_name_len);
-                        print!("{} ", padding);
-                    }
-                }
+        let mut output_names: HashSet<String> = HashSet::new();
+        let mut projected_columns = Vec::with_capacity(columns.len());
+
+        for (source, alias) in columns {
+            let output_name = alias.unwrap_or_else(|| source.clone());
+            if !output_names.insert(output_name.clone()) {
+                return Err(Error::DuplicateOutputColumn(output_name));
             }
-            println!();
+
+            let mut column = self
+                .columns
+                .iter()
+                .find(|c| c.name == source)
+                .cloned()
+                .unwrap();
+            column.name = output_name;
+            projected_columns.push(column);
         }
 
-        Ok(())
+        let mut projected_table = Table::new(&self.name, projected_columns)?;
+        projected_table.case_insensitive_names = self.case_insensitive_names;
+        Ok(projected_table)
     }
 
-    /// Prints the structure of the table, including the column names, their corresponding data types, and primary key information.
+    /// Prints the structure of the table, including the column names, their corresponding data types, primary key information, constraints and foreign keys.
     ///
     /// # Examples
     ///
@@ -287,71 +817,112 @@ impl Table {
     /// use crate::table::Table;
     ///
     /// let table = Table::new("users", vec![
-    ///     Column::new("id", ColumnDataType::Integer, Some(true)), // Primary key column
-    ///     Column::new("name", ColumnDataType::Text, None),
-    ///     Column::new("age", ColumnDataType::Integer, None),
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    ///     Column::new("age", ColumnDataType::Integer, None, false, None),
     /// ]).unwrap();
     ///
     /// table.describe();
     /// ```
     pub fn describe(&self) {
-        println!("Table: {}", self.name);
-        println!();
-
-        // Find the maximum length of column names
-        let max_column_name_len = self
-            .columns
-            .iter()
-            .map(|column| column.name.len())
-            .max()
-            .unwrap_or(0);
+        println!("{}", self.render_description());
+    }
 
-        // Print the column names
-        for column in &self.columns {
-            let padded_name = format!("{:<width$}", column.name, width = max_column_name_len);
-            print!("{} ", padded_name);
+    /// Counts the number of records or non-null values in a specific column or the entire table.
+    ///
+    /// Deprecated: despite its name, `column_count(None)` counts records. Use `row_count` or
+    /// `non_null_count` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - An optional string representing the name of the column to count non-null values for. If `None`, the function will count the total number of records in the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The count of records or non-null values, depending on whether a column name was provided or not.
+    /// * `Err(Error)` - An error if the provided column name does not exist in the table.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::NonExistingColumn` - If the provided column name does not exist in the table.
+    #[deprecated(note = "use `row_count` or `non_null_count` instead")]
+    pub fn column_count(&self, column_name: Option<String>) -> Result<usize, Error> {
+        match column_name {
+            Some(column_name) => self.non_null_count(&column_name),
+            None => Ok(self.row_count()),
         }
-        println!();
-
-        // Print a separator line
-        let separator_line: String = std::iter::repeat("-")
-            .take(max_column_name_len * self.columns.len() + self.columns.len() - 1)
-            .collect();
-        println!("{}", separator_line);
+    }
 
-        // Print the data types
-        for column in &self.columns {
-            let data_type_name = format!("{}", column.data_type);
-            let padded_data_type =
-                format!("{:<width$}", data_type_name, width = max_column_name_len);
-            print!("{} ", padded_data_type);
-        }
-        println!();
+    /// Returns the number of records in the table, taken as the length of the longest column
+    /// so that an empty table (or one without columns) yields 0 instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// println!("{} users", table.row_count());
+    /// ```
+    pub fn row_count(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|column| column.data.len())
+            .max()
+            .unwrap_or(0)
+    }
 
-        // Print primary key information
-        for column in &self.columns {
-            let primary_key_info = if column.is_primary_key {
-                "prim_key".to_string()
-            } else {
-                "nt_prim_key".to_string()
-            };
-            let padded_primary_key_info =
-                format!("{:<width$}", primary_key_info, width = max_column_name_len);
-            print!("{} ", padded_primary_key_info);
+    /// Checks that every column holds the same number of values, which the rest of the table
+    /// relies on when walking records by index.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If all columns have the same length.
+    /// * `Err(Error)` - An error naming the first column whose length differs.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following error:
+    ///
+    /// * `Error::InconsistentColumnLengths` - If a column holds fewer or more values than the
+    ///   record count, taken as the length of the longest column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// table.validate().unwrap();
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        let expected_length = self.row_count();
+        match self
+            .columns
+            .iter()
+            .find(|column| column.data.len() != expected_length)
+        {
+            Some(column) => Err(Error::InconsistentColumnLengths(
+                column.name.clone(),
+                column.data.len(),
+                expected_length,
+            )),
+            None => Ok(()),
         }
-        println!();
     }
 
-    /// Counts the number of records or non-null values in a specific column or the entire table.
+    /// Counts the non-null values in a column.
     ///
     /// # Arguments
     ///
-    /// * `column_name` - An optional string representing the name of the column to count non-null values for. If `None`, the function will count the total number of records in the table.
+    /// * `column_name` - A string slice representing the name of the column to count non-null values for.
     ///
     /// # Returns
     ///
-    /// * `Ok(usize)` - The count of records or non-null values, depending on whether a column name was provided or not.
-    /// * `Err(Error)` - An error if the provided column name does not exist in the table.
+    /// * `Ok(usize)` - The number of non-null values in the column.
+    /// * `Err(Error)` - An error if the column does not exist in the table.
     ///
     /// # Errors
     ///
@@ -362,49 +933,243 @@ impl Table {
     /// # Examples
     ///
     /// ```
-    /// use crate::column::{Column, ColumnDataType};
     /// use crate::table::Table;
     ///
-    /// let mut table = Table::new("users", vec![
-    ///     Column::new("id", ColumnDataType::Integer, Some(true)), // Primary key column
-    ///     Column::new("name", ColumnDataType::Text, None),
-    ///     Column::new("age", ColumnDataType::Integer, None),
-    /// ]).unwrap();
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// let ages_known = table.non_null_count("age").unwrap();
+    /// ```
+    pub fn non_null_count(&self, column_name: &str) -> Result<usize, Error> {
+        let column = &self.columns[self.column_position(column_name)?];
+
+        Ok(column
+            .data
+            .iter()
+            .filter(|v| !matches!(v, Value::Null))
+            .count())
+    }
+
+    /// Returns a new table holding the first `n` records (or all of them if there are fewer).
+    /// The schema metadata (primary key, foreign keys and back-references) is preserved.
     ///
-    /// // Insert some initial data
-    /// table.insert(vec!["1".to_string(), "Alice".to_string(), "25".to_string()]).unwrap();
-    /// table.insert(vec!["2".to_string(), "Bob".to_string(), "30".to_string()]).unwrap();
-    /// table.insert(vec!["3".to_string(), "Charlie".to_string(), "".to_string()]).unwrap(); // null age
+    /// # Examples
     ///
-    /// // Count total records
-    /// let total_count = table.count(None).unwrap();
+    /// ```
+    /// use crate::table::Table;
     ///
-    /// // Count non-null values in "age" column
-    /// let age_count = table.count(Some("age".to_string())).unwrap();
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// let first_rows = table.head(5);
     /// ```
-    pub fn column_count(&self, column_name: Option<String>) -> Result<usize, Error> {
-        return if let Some(column_name) = column_name {
-            // Check if the provided column name exists
-            if let Some(column) = self.columns.iter().find(|c| c.name == column_name) {
-                // Count the non-null values in the specified column
-                let non_null_count = column
-                    .data
-                    .iter()
-                    .filter(|v| !matches!(v, Value::Null))
-                    .count();
-                Ok(non_null_count)
-            } else {
-                Err(Error::NonExistingColumn(column_name))
+    pub fn head(&self, n: usize) -> Table {
+        let row_indices: Vec<usize> = (0..self.row_count().min(n)).collect();
+        self.with_rows(&row_indices)
+    }
+
+    /// Returns a new table holding the last `n` records (or all of them if there are fewer).
+    /// The schema metadata (primary key, foreign keys and back-references) is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// let last_rows = table.tail(5);
+    /// ```
+    pub fn tail(&self, n: usize) -> Table {
+        let row_count = self.row_count();
+        let row_indices: Vec<usize> = (row_count.saturating_sub(n)..row_count).collect();
+        self.with_rows(&row_indices)
+    }
+
+    /// Returns a new table holding `n` records chosen uniformly at random without replacement.
+    /// The same `seed` always selects the same records, and the selected records keep their
+    /// relative order from the original table. If `n` is at least the number of records, every
+    /// record is returned. The schema metadata is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let table = Table::import_table("users.csv", "csv").unwrap();
+    /// let sample = table.sample(10, 42);
+    /// ```
+    pub fn sample(&self, n: usize, seed: u64) -> Table {
+        let row_count = self.row_count();
+        let mut row_indices: Vec<usize> = (0..row_count).collect();
+
+        if n < row_count {
+            // Partial Fisher-Yates shuffle: the first n slots end up holding the sample
+            let mut rng = XorShift64::new(seed);
+            for i in 0..n {
+                let j = i + rng.next_below(row_count - i);
+                row_indices.swap(i, j);
             }
-        } else {
-            // If no column name is provided, count the total number of records
-            let max_rows = self
-                .columns
-                .iter()
-                .map(|column| column.data.len())
-                .max()
-                .unwrap_or(0);
-            Ok(max_rows)
+            row_indices.truncate(n);
+            row_indices.sort_unstable();
+        }
+
+        self.with_rows(&row_indices)
+    }
+
+    /// Prints the first `n` records of the table, using the same layout as `show`.
+    pub fn show_head(&self, n: usize) {
+        self.head(n).show();
+    }
+
+    /// Prints the last `n` records of the table, using the same layout as `show`.
+    pub fn show_tail(&self, n: usize) {
+        self.tail(n).show();
+    }
+
+    /// Makes column names given to the table ignore case, e.g. `Age` finds the column `age`.
+    /// This applies to the columns of conditions, projections and `insert_with_columns`, and to
+    /// the single-column functions like `order_by`. Columns keep their original names, which
+    /// are also used in every output. Disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether column names should ignore case.
+    ///
+    /// # Errors
+    ///
+    /// While enabled, a name matching several columns that only differ by case is rejected with
+    /// `Error::AmbiguousColumnName` by the function it is given to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{NestedCondition, Table};
+    ///
+    /// let mut table = Table::new("users", /* ... */);
+    /// table.set_case_insensitive_names(true);
+    /// let adults = table.filter(&NestedCondition::parse("Age >= 18").unwrap()).unwrap();
+    /// ```
+    pub fn set_case_insensitive_names(&mut self, enabled: bool) {
+        self.case_insensitive_names = enabled;
+    }
+
+    /// Returns the name of the column a caller-given name refers to. With case-insensitive
+    /// names that is the column whose name only differs by case, otherwise the name itself.
+    /// Unknown names are returned as they are, for the caller to report.
+    pub(crate) fn resolve_column_name<'a>(
+        &'a self,
+        column_name: &'a str,
+    ) -> Result<&'a str, Error> {
+        if !self.case_insensitive_names {
+            return Ok(column_name);
+        }
+
+        let lowercase_name = column_name.to_lowercase();
+        let mut matches = self
+            .columns
+            .iter()
+            .filter(|c| c.name.to_lowercase() == lowercase_name);
+        match (matches.next(), matches.next()) {
+            (Some(column), None) => Ok(&column.name),
+            (Some(_), Some(_)) => Err(Error::AmbiguousColumnName(column_name.to_string())),
+            (None, _) => Ok(column_name),
+        }
+    }
+
+    /// Resolves every column name of a list, see `resolve_column_name`.
+    pub(crate) fn resolve_column_names(
+        &self,
+        column_names: Vec<String>,
+    ) -> Result<Vec<String>, Error> {
+        if !self.case_insensitive_names {
+            return Ok(column_names);
+        }
+
+        column_names
+            .iter()
+            .map(|column_name| Ok(self.resolve_column_name(column_name)?.to_string()))
+            .collect()
+    }
+
+    /// Resolves the column names of a condition, see `resolve_column_name`. The condition is
+    /// only copied when names may need rewriting.
+    pub(crate) fn resolve_condition<'a>(
+        &self,
+        condition: &'a NestedCondition,
+    ) -> Result<Cow<'a, NestedCondition>, Error> {
+        if !self.case_insensitive_names {
+            return Ok(Cow::Borrowed(condition));
+        }
+
+        let resolve = |inner: &NestedCondition| -> Result<Box<NestedCondition>, Error> {
+            Ok(Box::new(self.resolve_condition(inner)?.into_owned()))
+        };
+        let resolved = match condition {
+            NestedCondition::Condition(column_name, operator, value) => NestedCondition::Condition(
+                self.resolve_column_name(column_name)?.to_string(),
+                operator.clone(),
+                value.clone(),
+            ),
+            NestedCondition::And(left, right) => {
+                NestedCondition::And(resolve(left)?, resolve(right)?)
+            }
+            NestedCondition::Or(left, right) => {
+                NestedCondition::Or(resolve(left)?, resolve(right)?)
+            }
+            NestedCondition::Not(inner) => NestedCondition::Not(resolve(inner)?),
         };
+
+        Ok(Cow::Owned(resolved))
+    }
+
+    /// Creates a copy of the table holding only the records at the given row indices, in that
+    /// order. Column metadata and foreign key back-references are kept as they are.
+    pub(crate) fn with_rows(&self, row_indices: &[usize]) -> Table {
+        let columns: Vec<Column> = self
+            .columns
+            .iter()
+            .map(|column| {
+                let mut new_column = column.clone_without_data();
+                new_column.data = row_indices
+                    .iter()
+                    .map(|&row_idx| column.data.get(row_idx).cloned().unwrap_or(Value::Null))
+                    .collect();
+                new_column
+            })
+            .collect();
+
+        Table {
+            name: self.name.clone(),
+            columns,
+            primary_key_column: self.primary_key_column.clone(),
+            referenced_as_foreign_key: self.referenced_as_foreign_key.clone(),
+            indexes: HashMap::new(),
+            case_insensitive_names: self.case_insensitive_names,
+        }
+    }
+}
+
+/// Converts a value to the data type of a column, see `Table::change_column_type`. Returns
+/// `None` if the value has no exact counterpart in that type.
+fn convert_value(value: &Value, column: &Column) -> Option<Value> {
+    match (value, column.data_type) {
+        (Value::Null, _) => Some(Value::Null),
+        (_, ColumnDataType::Text) => Some(Value::Text(value.to_string())),
+        (Value::Text(text), _) => parse_column_value(column, text.clone()).ok(),
+        (Value::Integer(value), ColumnDataType::Float) => Some(Value::Float(*value as f64)),
+        (Value::Integer(value), ColumnDataType::Decimal) => Some(Value::Decimal(*value as i128, 0)),
+        // The upper bound is exclusive, i64::MAX rounds up to 2^63 as a float
+        (Value::Float(value), ColumnDataType::Integer) => {
+            (value.fract() == 0.0 && *value >= i64::MIN as f64 && *value < i64::MAX as f64)
+                .then_some(Value::Integer(*value as i64))
+        }
+        (Value::Float(value), ColumnDataType::Decimal) => Value::parse_decimal(&value.to_string()),
+        (Value::Decimal(mantissa, scale), ColumnDataType::Integer) => {
+            let factor = 10i128.checked_pow(*scale)?;
+            if mantissa % factor != 0 {
+                return None;
+            }
+            i64::try_from(mantissa / factor).ok().map(Value::Integer)
+        }
+        (Value::Decimal(mantissa, scale), ColumnDataType::Float) => {
+            Some(Value::Float(*mantissa as f64 / 10f64.powi(*scale as i32)))
+        }
+        _ => Some(value.clone()),
     }
 }
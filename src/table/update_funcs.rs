@@ -1,6 +1,10 @@
-use crate::column::{ColumnDataType, Value};
-use crate::table::helpers::evaluate_nested_conditions;
-use crate::table::{Error, NestedCondition, Table};
+use crate::column::{align_decimals, Column, ColumnDataType, Value};
+use crate::table::helpers::{
+    evaluate_nested_conditions, parse_column_value, parse_column_value_or_null,
+    validate_nested_conditions,
+};
+use crate::table::{Error, Expr, NestedCondition, Operand, Table};
+use std::collections::HashSet;
 
 impl Table {
     /// Updates the values of a specified column with a new value.
@@ -8,7 +12,8 @@ impl Table {
     /// # Arguments
     ///
     /// * `column_name` - A string slice representing the name of the column to update.
-    /// * `new_value` - A string slice representing the new value to be set for the column.
+    /// * `new_value` - A string slice representing the new value to be set for the column, or
+    ///   "null" for NULL.
     ///
     /// # Returns
     ///
@@ -22,6 +27,7 @@ impl Table {
     /// * `Error::NonExistingColumn` - If the specified column does not exist in the table.
     /// * `Error::ParseError` - If the new value cannot be parsed into the corresponding column's data type.
     /// * `Error::CannotBatchUpdatePrimaryKey` - If the column to be updated is the primary key column.
+    /// * `Error::DuplicateValue` - If the column is unique and the table has more than one record.
     ///
     /// # Examples
     ///
@@ -30,41 +36,28 @@ impl Table {
     /// use crate::table::Table;
     ///
     /// let mut table = Table::new("users", vec![
-    ///     Column::new("id", ColumnDataType::Integer, Some(true)), // Primary key column
-    ///     Column::new("name", ColumnDataType::Text, None),
-    ///     Column::new("age", ColumnDataType::Integer, None),
+    ///     Column::new("id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///     Column::new("name", ColumnDataType::Text, None, false, None),
+    ///     Column::new("age", ColumnDataType::Integer, None, false, None),
     /// ]);
     ///
     /// // Update the "age" column with the value 30
     /// table.update_column("age", "30").unwrap();
     /// ```
     pub fn update_column(&mut self, column_name: &str, new_value: &str) -> Result<(), Error> {
+        let update_idx = self.column_position(column_name)?;
+
         // Check if the requested column is the primary key column
-        if let Some(primary_key_column) = &self.primary_key_column {
-            if primary_key_column.name == column_name {
-                return Err(Error::CannotBatchUpdatePrimaryKey);
-            }
+        if self.columns[update_idx].is_primary_key {
+            return Err(Error::CannotBatchUpdatePrimaryKey);
         }
 
-        let update_column = self
-            .columns
-            .iter_mut()
-            .find(|c| c.name == column_name)
-            .ok_or(Error::NonExistingColumn(column_name.to_string()))?;
-
-        let new_value = match update_column.data_type {
-            ColumnDataType::Integer => new_value
-                .parse::<i64>()
-                .map(Value::Integer)
-                .map_err(|_| Error::ParseError(0, new_value.to_string()))?,
-            ColumnDataType::Float => new_value
-                .parse::<f64>()
-                .map(Value::Float)
-                .map_err(|_| Error::ParseError(0, new_value.to_string()))?,
-            ColumnDataType::Text => Value::Text(new_value.to_string()),
-        };
-
-        update_column.data = vec![new_value.clone(); update_column.data.len()];
+        let new_value =
+            parse_column_value_or_null(&self.columns[update_idx], new_value.to_string())?;
+        let updates = (0..self.row_count())
+            .map(|row_idx| (row_idx, new_value.clone()))
+            .collect();
+        self.assign_values(update_idx, updates)?;
 
         Ok(())
     }
@@ -73,12 +66,13 @@ impl Table {
     ///
     /// # Arguments
     ///
-    /// * `update_input` - A tuple containing the column name to update and the new value.
+    /// * `update_input` - A tuple containing the column name to update and the new value, with
+    ///   "null" for NULL.
     /// * `nested_condition` - A `NestedCondition` enum representing the nested condition structure.
     ///
     /// # Returns
     ///
-    /// * `Ok(())` if the update operation is successful.
+    /// * `Ok(usize)` with the number of updated records if the update operation is successful.
     /// * `Err(Error)` if an error occurs during the update operation.
     ///
     /// # Errors
@@ -90,6 +84,7 @@ impl Table {
     /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
     /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
     /// * `Error::DuplicatePrimaryKey` - If the update operation results in duplicate primary key values.
+    /// * `Error::DuplicateValue` - If the update operation results in duplicate values in a unique column.
     ///
     /// # Examples
     ///
@@ -100,11 +95,11 @@ impl Table {
     /// let mut table = Table::new(
     ///     "users",
     ///     vec![
-    ///         Column::new("user_id", ColumnDataType::Integer, Some(true)), // Primary key column
-    ///         Column::new("user_name", ColumnDataType::Text, None),
-    ///         Column::new("age", ColumnDataType::Integer, None),
+    ///         Column::new("user_id", ColumnDataType::Integer, None, true, None), // Primary key column
+    ///         Column::new("user_name", ColumnDataType::Text, None, false, None),
+    ///         Column::new("age", ColumnDataType::Integer, None, false, None),
     ///     ],
-    /// );
+    /// ).unwrap();
     ///
     /// // Insert some initial data
     /// table.insert(vec!["1".to_string(), "Alice".to_string(), "27".to_string()]).unwrap();
@@ -140,78 +135,480 @@ impl Table {
         &mut self,
         update_input: (String, String),
         nested_condition: NestedCondition,
-    ) -> Result<(), Error> {
-        // Make a copy of the table, so we can restore if needed
-        let table_copy = self.copy();
-
+    ) -> Result<usize, Error> {
         // Validate column name in update_input
-        let update_column = self
-            .columns
-            .iter()
-            .find(|c| c.name == update_input.0)
-            .ok_or(Error::NonExistingColumn(update_input.0.clone()))?;
+        let update_idx = self.column_position(&update_input.0)?;
 
         // Parse new_value according to the column's data type
-        let new_value = match update_column.data_type {
-            ColumnDataType::Integer => update_input
-                .1
-                .parse::<i64>()
-                .map(Value::Integer)
-                .map_err(|_| Error::ParseError(1, update_input.1.clone()))?,
-            ColumnDataType::Float => update_input
-                .1
-                .parse::<f64>()
-                .map(Value::Float)
-                .map_err(|_| Error::ParseError(1, update_input.1.clone()))?,
-            ColumnDataType::Text => Value::Text(update_input.1),
+        let new_value = parse_column_value_or_null(&self.columns[update_idx], update_input.1)?;
+        let nested_condition = self.resolve_condition(&nested_condition)?;
+
+        // Compute the new values of the matching records before touching any data
+        let mut updates = Vec::new();
+        for i in 0..self.columns[update_idx].data.len() {
+            if evaluate_nested_conditions(&nested_condition, &self.columns, i)? {
+                updates.push((i, new_value.clone()));
+            }
+        }
+
+        self.assign_values(update_idx, updates)
+    }
+
+    /// Updates a column with a value computed from each matching record, e.g. to increase a
+    /// score by 5 for every user over 30.
+    ///
+    /// Column operands must have the same data type as the updated column, and literals are
+    /// parsed as that data type. If an operand is NULL, the result is NULL.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name` - A string slice representing the name of the column to update.
+    /// * `expr` - An `Expr` computing the new value from the current record.
+    /// * `nested_condition` - A `NestedCondition` enum selecting the records to update.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of updated records if the update operation is successful.
+    /// * `Err(Error)` if an error occurs during the update operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors. No record is modified when one occurs.
+    ///
+    /// * `Error::NonExistingColumn` - If the updated column or a column operand does not exist.
+    /// * `Error::InvalidExpression` - If the operand types don't match the updated column, the
+    ///   operation doesn't apply to its data type, or an Integer or Decimal result overflows.
+    /// * `Error::NonExistingColumn` - If a column in the condition does not exist in the table.
+    /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+    /// * `Error::DuplicatePrimaryKey` - If the update operation results in duplicate primary key values.
+    /// * `Error::DuplicateValue` - If the update operation results in duplicate values in a unique column.
+    /// * `Error::NullPrimaryKey` / `Error::NullValue` - If a NULL operand makes a new value NULL in
+    ///   the primary key or a not-null column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::{Expr, NestedCondition, Operand, Table};
+    ///
+    /// let mut table = Table::import_table("users.csv", "csv").unwrap();
+    ///
+    /// // Give everyone over 30 a 5-point score bump
+    /// table.update_expr(
+    ///     "score",
+    ///     Expr::Add(Operand::Col("score".to_string()), Operand::Lit("5.0".to_string())),
+    ///     NestedCondition::Condition("age".to_string(), ">".to_string(), "30".to_string()),
+    /// ).unwrap();
+    /// ```
+    pub fn update_expr(
+        &mut self,
+        column_name: &str,
+        expr: Expr,
+        nested_condition: NestedCondition,
+    ) -> Result<usize, Error> {
+        let update_idx = self.column_position(column_name)?;
+        let data_type = self.columns[update_idx].data_type;
+
+        let (left, right) = match &expr {
+            Expr::Add(left, right) => (left, right),
+            Expr::Sub(left, right) | Expr::Mul(left, right) => {
+                if data_type == ColumnDataType::Text {
+                    return Err(Error::InvalidExpression(format!(
+                        "only addition applies to the Text column '{}'",
+                        column_name
+                    )));
+                }
+                (left, right)
+            }
         };
 
-        let update_column_name = self
+        // Check both operands against the updated column before touching any data
+        let left = self.resolve_operand(left, column_name, data_type)?;
+        let right = self.resolve_operand(right, column_name, data_type)?;
+        let nested_condition = self.resolve_condition(&nested_condition)?;
+
+        let mut updates = Vec::new();
+        for i in 0..self.columns[update_idx].data.len() {
+            if evaluate_nested_conditions(&nested_condition, &self.columns, i)? {
+                let left = left.value_at(&self.columns, i);
+                let right = right.value_at(&self.columns, i);
+                updates.push((i, apply_expr(&expr, left, right, column_name)?));
+            }
+        }
+
+        self.assign_values(update_idx, updates)
+    }
+
+    /// Resolves an expression operand for an update of a column of the given data type.
+    fn resolve_operand(
+        &self,
+        operand: &Operand,
+        column_name: &str,
+        data_type: ColumnDataType,
+    ) -> Result<ResolvedOperand, Error> {
+        match operand {
+            Operand::Col(name) => {
+                let column_idx = self.column_position(name)?;
+                let operand_type = self.columns[column_idx].data_type;
+                if operand_type != data_type {
+                    return Err(Error::InvalidExpression(format!(
+                        "column '{}' is {}, but the updated column '{}' is {}",
+                        name, operand_type, column_name, data_type
+                    )));
+                }
+                Ok(ResolvedOperand::Column(column_idx))
+            }
+            Operand::Lit(literal) => {
+                let value = match data_type {
+                    ColumnDataType::Integer => literal.parse::<i64>().map(Value::Integer).ok(),
+                    ColumnDataType::Float => literal
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|value| value.is_finite())
+                        .map(Value::Float),
+                    ColumnDataType::Text => Some(Value::Text(literal.clone())),
+                    ColumnDataType::Decimal => Value::parse_decimal(literal),
+                };
+                value.map(ResolvedOperand::Literal).ok_or_else(|| {
+                    Error::InvalidExpression(format!(
+                        "literal '{}' is not a valid {} for column '{}'",
+                        literal, data_type, column_name
+                    ))
+                })
+            }
+        }
+    }
+
+    /// Replaces every occurrence of a value in a column with another value, e.g. to normalize
+    /// `"N.Y."` to `"NY"`, optionally only in the records matching a condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The name of the column to update.
+    /// * `from` - The value to replace, parsed according to the column's data type.
+    /// * `to` - The new value, parsed according to the column's data type.
+    /// * `condition` - An optional `NestedCondition` restricting the records that are updated.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` with the number of changed values, 0 when `from` equals `to`.
+    /// * `Err(Error)` if an error occurs during the update operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors. No record is modified when one occurs.
+    ///
+    /// * `Error::NonExistingColumn` - If the column, or a column in the condition, does not exist in the table.
+    /// * `Error::ParseError` - If `from` or `to` cannot be parsed into the data type of the column.
+    /// * `Error::InvalidOperator` - If an invalid operator is used in the condition.
+    /// * `Error::DuplicateValue` - If the column is unique and `to` would appear more than once.
+    /// * `Error::DuplicatePrimaryKey` - If the column is the primary key and `to` would appear more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::import_table("addresses.csv", "csv").unwrap();
+    /// let changed = table.replace_values("state", "N.Y.", "NY", None).unwrap();
+    /// ```
+    pub fn replace_values(
+        &mut self,
+        column: &str,
+        from: &str,
+        to: &str,
+        condition: Option<NestedCondition>,
+    ) -> Result<usize, Error> {
+        let column_idx = self.column_position(column)?;
+        let from = parse_column_value(&self.columns[column_idx], from.to_string())?;
+        let to = parse_column_value(&self.columns[column_idx], to.to_string())?;
+        let condition = match &condition {
+            Some(condition) => Some(self.resolve_condition(condition)?),
+            None => None,
+        };
+        if let Some(condition) = &condition {
+            validate_nested_conditions(condition, &self.columns)?;
+        }
+        if from == to {
+            return Ok(0);
+        }
+
+        let mut updates = Vec::new();
+        for (row_idx, value) in self.columns[column_idx].data.iter().enumerate() {
+            if *value != from {
+                continue;
+            }
+            if let Some(condition) = &condition {
+                if !evaluate_nested_conditions(condition, &self.columns, row_idx)? {
+                    continue;
+                }
+            }
+            updates.push((row_idx, to.clone()));
+        }
+
+        // Several records taking the same value of a unique column, or the value already being
+        // in it, would break the constraint
+        let target_column = &self.columns[column_idx];
+        if target_column.unique
+            && !target_column.is_primary_key
+            && (updates.len() > 1 || (updates.len() == 1 && target_column.data.contains(&to)))
+        {
+            return Err(Error::DuplicateValue(
+                target_column.name.clone(),
+                to.to_string(),
+            ));
+        }
+
+        self.assign_values(column_idx, updates)
+    }
+
+    /// Updates the record with the given primary key value, applying each `(column, value)`
+    /// assignment to it. The values are parsed and checked like in
+    /// `update_with_nested_conditions`, and either every assignment is applied or none is.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The primary key value of the record to update.
+    /// * `assignments` - A vector of `(column_name, new_value)` pairs. The primary key itself may be assigned a new value.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` if the record was found and updated.
+    /// * `Ok(false)` if no record has that primary key value.
+    /// * `Err(Error)` if an error occurs during the update operation.
+    ///
+    /// # Errors
+    ///
+    /// This function can return the following errors:
+    ///
+    /// * `Error::NoPrimaryKey` - If the table has no primary key column.
+    /// * `Error::InvalidConditionValue` - If the key cannot be parsed into the primary key's data type.
+    /// * `Error::NonExistingColumn` - If an assigned column does not exist in the table.
+    /// * `Error::ParseError` - If a new value cannot be parsed into the data type of its column.
+    /// * `Error::DuplicatePrimaryKey` - If the new primary key value is already used by another record.
+    /// * `Error::DuplicateValue` - If a new value of a unique column is already used by another record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::import_table("users.csv", "csv").unwrap();
+    /// let found = table
+    ///     .update_by_pk("5", vec![("user_name".to_string(), "Bob".to_string())])
+    ///     .unwrap();
+    /// ```
+    pub fn update_by_pk(
+        &mut self,
+        key: &str,
+        assignments: Vec<(String, String)>,
+    ) -> Result<bool, Error> {
+        let row_idx = self.primary_key_row(key)?;
+
+        // Parse every assignment before touching any data
+        let mut parsed_assignments = Vec::with_capacity(assignments.len());
+        for (column_name, value) in assignments {
+            let column_idx = self.column_position(&column_name)?;
+            let value = parse_column_value_or_null(&self.columns[column_idx], value)?;
+            parsed_assignments.push((column_idx, value));
+        }
+
+        let Some(row_idx) = row_idx else {
+            return Ok(false);
+        };
+
+        // New key values may only collide with the record itself
+        for (column_idx, value) in &parsed_assignments {
+            self.check_assigned_values(*column_idx, &[(row_idx, value.clone())])?;
+        }
+
+        for (column_idx, value) in parsed_assignments {
+            self.assign_values(column_idx, vec![(row_idx, value)])?;
+        }
+
+        Ok(true)
+    }
+
+    /// Updates the record with the given primary key value like `update_by_pk`, but keeps the
+    /// primary key itself fixed, for the common case of editing the other fields of a record.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The primary key value of the record to update.
+    /// * `updates` - A vector of `(column_name, new_value)` pairs, none of them for the primary key column.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` if the record was found and updated.
+    /// * `Ok(false)` if no record has that primary key value.
+    /// * `Err(Error)` if an error occurs during the update operation.
+    ///
+    /// # Errors
+    ///
+    /// The same as `update_by_pk`, plus:
+    ///
+    /// * `Error::CannotUpdatePrimaryKey` - If one of the updates targets the primary key column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crate::table::Table;
+    ///
+    /// let mut table = Table::import_table("users.csv", "csv").unwrap();
+    /// table
+    ///     .update_by_primary_key("5", vec![("user_name".to_string(), "Bob".to_string())])
+    ///     .unwrap();
+    /// ```
+    pub fn update_by_primary_key(
+        &mut self,
+        key: &str,
+        updates: Vec<(String, String)>,
+    ) -> Result<bool, Error> {
+        let primary_key_name = &self.primary_key().ok_or(Error::NoPrimaryKey)?.name;
+        if let Some((column_name, _)) = updates.iter().find(|(name, _)| name == primary_key_name) {
+            return Err(Error::CannotUpdatePrimaryKey(column_name.clone()));
+        }
+
+        self.update_by_pk(key, updates)
+    }
+
+    /// Finds the record holding the given primary key value, parsed according to the primary
+    /// key's data type.
+    pub(crate) fn primary_key_row(&self, key: &str) -> Result<Option<usize>, Error> {
+        let primary_key_column = self
             .columns
             .iter()
-            .find(|c| c.name == update_input.0)
-            .ok_or(Error::NonExistingColumn(update_input.0.clone()))?
-            .name
-            .clone();
-
-        let columns_clone = self.columns.clone();
-
-        for record in &mut self.columns {
-            if record.name == update_column_name {
-                record.data = record.data.iter().enumerate().try_fold(
-                    Vec::new(),
-                    |mut acc, (i, value)| {
-                        let update_record =
-                            evaluate_nested_conditions(&nested_condition, &columns_clone, i)?;
-
-                        if update_record {
-                            acc.push(new_value.clone());
-                        } else {
-                            acc.push(value.clone());
-                        }
-
-                        Ok(acc)
-                    },
-                )?;
-
-                // If the updated column is the primary key column, check for duplicates
-                if record.is_primary_key {
-                    let mut duplicate_found = false;
-                    for v in &record.data {
-                        if record.data.iter().filter(|&x| *x == *v).count() > 1 {
-                            duplicate_found = true;
-                            break;
-                        }
-                    }
-
-                    if duplicate_found {
-                        self.columns = table_copy.columns;
-                        return Err(Error::DuplicatePrimaryKey);
-                    }
-                }
+            .find(|c| c.is_primary_key)
+            .ok_or(Error::NoPrimaryKey)?;
+        let key_value = parse_column_value(primary_key_column, key.to_string()).map_err(|_| {
+            Error::InvalidConditionValue(primary_key_column.name.clone(), key.to_string())
+        })?;
+
+        Ok(primary_key_column
+            .data
+            .iter()
+            .position(|value| *value == key_value))
+    }
+
+    /// Assigns the given values to the records at the given indices of a column, once they are
+    /// checked with `check_assigned_values`. Returns the number of updated records.
+    pub(crate) fn assign_values(
+        &mut self,
+        update_idx: usize,
+        updates: Vec<(usize, Value)>,
+    ) -> Result<usize, Error> {
+        self.check_assigned_values(update_idx, &updates)?;
+
+        let updated = updates.len();
+        for (row_idx, new_value) in updates {
+            let old_value =
+                std::mem::replace(&mut self.columns[update_idx].data[row_idx], new_value);
+            self.reindex_value(update_idx, row_idx, &old_value);
+        }
+
+        Ok(updated)
+    }
+
+    /// Checks that assigning the given values to the records at the given indices of a column
+    /// keeps its constraints: no NULL in the primary key or a not-null column, and no value
+    /// shared by two records in the primary key or a unique column. Nothing is modified.
+    pub(crate) fn check_assigned_values(
+        &self,
+        column_idx: usize,
+        updates: &[(usize, Value)],
+    ) -> Result<(), Error> {
+        let column = &self.columns[column_idx];
+        if updates.iter().any(|(_, value)| *value == Value::Null) {
+            if column.is_primary_key {
+                return Err(Error::NullPrimaryKey);
+            }
+            if column.not_null {
+                return Err(Error::NullValue(column.name.clone()));
+            }
+        }
+        if !column.is_primary_key && !column.unique {
+            return Ok(());
+        }
+
+        let mut values: Vec<&Value> = column.data.iter().collect();
+        for (row_idx, value) in updates {
+            values[*row_idx] = value;
+        }
+        let mut seen = HashSet::new();
+        for value in values.into_iter().filter(|value| **value != Value::Null) {
+            if !seen.insert(value) {
+                return Err(if column.is_primary_key {
+                    Error::DuplicatePrimaryKey
+                } else {
+                    Error::DuplicateValue(column.name.clone(), value.to_string())
+                });
             }
         }
 
         Ok(())
     }
 }
+
+/// An expression operand checked against the table: a column index or a parsed literal.
+enum ResolvedOperand {
+    Column(usize),
+    Literal(Value),
+}
+
+impl ResolvedOperand {
+    fn value_at<'a>(&'a self, columns: &'a [Column], row_idx: usize) -> &'a Value {
+        match self {
+            ResolvedOperand::Column(column_idx) => &columns[*column_idx].data[row_idx],
+            ResolvedOperand::Literal(value) => value,
+        }
+    }
+}
+
+/// Applies the operation of an expression to two values of the same data type.
+fn apply_expr(expr: &Expr, left: &Value, right: &Value, column_name: &str) -> Result<Value, Error> {
+    let overflow =
+        || Error::InvalidExpression(format!("arithmetic overflow in column '{}'", column_name));
+
+    match (expr, left, right) {
+        (_, Value::Null, _) | (_, _, Value::Null) => Ok(Value::Null),
+        (Expr::Add(..), Value::Integer(a), Value::Integer(b)) => {
+            a.checked_add(*b).map(Value::Integer).ok_or_else(overflow)
+        }
+        (Expr::Sub(..), Value::Integer(a), Value::Integer(b)) => {
+            a.checked_sub(*b).map(Value::Integer).ok_or_else(overflow)
+        }
+        (Expr::Mul(..), Value::Integer(a), Value::Integer(b)) => {
+            a.checked_mul(*b).map(Value::Integer).ok_or_else(overflow)
+        }
+        (Expr::Add(..) | Expr::Sub(..) | Expr::Mul(..), Value::Float(a), Value::Float(b)) => {
+            let value = match expr {
+                Expr::Add(..) => a + b,
+                Expr::Sub(..) => a - b,
+                Expr::Mul(..) => a * b,
+            };
+            // Floats overflow to infinity instead of failing
+            Some(Value::Float(value))
+                .filter(|_| value.is_finite())
+                .ok_or_else(overflow)
+        }
+        (Expr::Add(..) | Expr::Sub(..), Value::Decimal(a, a_scale), Value::Decimal(b, b_scale)) => {
+            let (a, b, scale) =
+                align_decimals((*a, *a_scale), (*b, *b_scale)).ok_or_else(overflow)?;
+            let mantissa = match expr {
+                Expr::Add(..) => a.checked_add(b),
+                _ => a.checked_sub(b),
+            };
+            mantissa
+                .map(|mantissa| Value::Decimal(mantissa, scale))
+                .ok_or_else(overflow)
+        }
+        (Expr::Mul(..), Value::Decimal(a, a_scale), Value::Decimal(b, b_scale)) => a
+            .checked_mul(*b)
+            .map(|mantissa| Value::Decimal(mantissa, a_scale + b_scale))
+            .ok_or_else(overflow),
+        (Expr::Add(..), Value::Text(a), Value::Text(b)) => Ok(Value::Text(format!("{}{}", a, b))),
+        _ => Err(Error::InvalidExpression(format!(
+            "cannot apply {:?} to '{}' and '{}' in column '{}'",
+            expr, left, right, column_name
+        ))),
+    }
+}
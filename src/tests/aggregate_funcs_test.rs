@@ -0,0 +1,561 @@
+use crate::column::{Column, ColumnDataType, Value};
+use crate::database::Database;
+use crate::table::{table_errors::Error, AggregateFn, ColumnSummary, NestedCondition, Table};
+
+#[test]
+fn test_count_where() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    // Counting on an empty table returns 0
+    let condition =
+        NestedCondition::Condition("age".to_string(), ">".to_string(), "30".to_string());
+    assert_eq!(table.count_where(&condition).unwrap(), 0);
+
+    // Insert some initial data
+    table
+        .insert(vec!["1".to_string(), "Alice".to_string(), "25".to_string()])
+        .unwrap();
+    table
+        .insert(vec!["2".to_string(), "Bob".to_string(), "35".to_string()])
+        .unwrap();
+    table
+        .insert(vec![
+            "3".to_string(),
+            "Charlie".to_string(),
+            "45".to_string(),
+        ])
+        .unwrap();
+
+    assert_eq!(table.count_where(&condition).unwrap(), 2);
+
+    let condition = NestedCondition::Or(
+        Box::new(NestedCondition::Condition(
+            "name".to_string(),
+            "=".to_string(),
+            "Alice".to_string(),
+        )),
+        Box::new(NestedCondition::Condition(
+            "age".to_string(),
+            ">=".to_string(),
+            "45".to_string(),
+        )),
+    );
+    assert_eq!(table.count_where(&condition).unwrap(), 2);
+
+    // Test counting with a non-existing column in the condition
+    let condition =
+        NestedCondition::Condition("invalid".to_string(), "=".to_string(), "value".to_string());
+    let result = table.count_where(&condition);
+    assert!(matches!(result, Err(Error::NonExistingColumn(_))));
+
+    // Test counting with an invalid operator in the condition
+    let condition =
+        NestedCondition::Condition("age".to_string(), "invalid".to_string(), "30".to_string());
+    let result = table.count_where(&condition);
+    assert!(matches!(result, Err(Error::InvalidOperator(_))));
+
+    // Test counting through the database wrapper
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table("users", vec!["1".to_string(), "31".to_string()])
+        .unwrap();
+    db.insert_into_table("users", vec!["2".to_string(), "29".to_string()])
+        .unwrap();
+
+    let condition =
+        NestedCondition::Condition("age".to_string(), ">".to_string(), "30".to_string());
+    assert_eq!(db.count_in_table("users", &condition).unwrap(), 1);
+    assert!(db.count_in_table("missing", &condition).is_err());
+}
+
+#[test]
+fn test_group_by() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("team", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    for row in [
+        ["1", "red", "30", "80.0"],
+        ["2", "red", "30", "90.0"],
+        ["3", "red", "25", "70.0"],
+        ["4", "blue", "30", "60.0"],
+        ["5", "null", "30", "null"],
+        ["6", "null", "30", "50.0"],
+    ] {
+        table
+            .insert(row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    // Group by two columns, NULL team forms its own group
+    let grouped = table
+        .group_by(
+            vec!["team".to_string(), "age".to_string()],
+            vec![
+                ("score".to_string(), AggregateFn::Avg),
+                ("score".to_string(), AggregateFn::Count),
+                ("id".to_string(), AggregateFn::Max),
+            ],
+        )
+        .unwrap();
+
+    let column_names: Vec<&str> = grouped.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(
+        column_names,
+        vec!["team", "age", "avg_score", "count_score", "max_id"]
+    );
+    assert_eq!(
+        grouped.columns[0].data,
+        vec![
+            Value::Text("red".to_string()),
+            Value::Text("red".to_string()),
+            Value::Text("blue".to_string()),
+            Value::Null,
+        ]
+    );
+    assert_eq!(
+        grouped.columns[1].data,
+        vec![
+            Value::Integer(30),
+            Value::Integer(25),
+            Value::Integer(30),
+            Value::Integer(30),
+        ]
+    );
+    assert_eq!(
+        grouped.columns[2].data,
+        vec![
+            Value::Float(85.0),
+            Value::Float(70.0),
+            Value::Float(60.0),
+            Value::Float(50.0),
+        ]
+    );
+    assert_eq!(
+        grouped.columns[3].data,
+        vec![
+            Value::Integer(2),
+            Value::Integer(1),
+            Value::Integer(1),
+            Value::Integer(1),
+        ]
+    );
+    assert_eq!(
+        grouped.columns[4].data,
+        vec![
+            Value::Integer(2),
+            Value::Integer(3),
+            Value::Integer(4),
+            Value::Integer(6),
+        ]
+    );
+
+    // Sum and min over a single grouping column
+    let grouped = table
+        .group_by(
+            vec!["age".to_string()],
+            vec![
+                ("age".to_string(), AggregateFn::Sum),
+                ("team".to_string(), AggregateFn::Min),
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        grouped.columns[1].data,
+        vec![Value::Integer(150), Value::Integer(25)]
+    );
+    assert_eq!(
+        grouped.columns[2].data,
+        vec![
+            Value::Text("blue".to_string()),
+            Value::Text("red".to_string())
+        ]
+    );
+
+    // Test grouping by a non-existing column
+    let result = table.group_by(vec!["invalid".to_string()], vec![]);
+    assert!(matches!(result, Err(Error::NonExistingColumn(_))));
+
+    // Test summing a Text column
+    let result = table.group_by(
+        vec!["age".to_string()],
+        vec![("team".to_string(), AggregateFn::Sum)],
+    );
+    assert!(matches!(result, Err(Error::NonNumericColumn(_))));
+
+    // Output columns must be unambiguous
+    let result = table.group_by(
+        vec!["age".to_string()],
+        vec![
+            ("score".to_string(), AggregateFn::Avg),
+            ("score".to_string(), AggregateFn::Avg),
+        ],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::DuplicateOutputColumn(column_name)) if column_name == "avg_score"
+    ));
+}
+
+#[test]
+fn test_group_by_having() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("team", ColumnDataType::Text, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    for row in [
+        ["1", "red", "80.0"],
+        ["2", "red", "90.0"],
+        ["3", "blue", "70.0"],
+        ["4", "blue", "75.0"],
+        ["5", "green", "95.0"],
+    ] {
+        table
+            .insert(row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    let grouped = table
+        .group_by(
+            vec!["team".to_string()],
+            vec![
+                ("score".to_string(), AggregateFn::Avg),
+                ("id".to_string(), AggregateFn::Count),
+            ],
+        )
+        .unwrap();
+    assert_eq!(grouped.columns[1].name, "avg_score");
+    assert_eq!(grouped.columns[2].name, "count_id");
+
+    // Keep only the teams whose average score exceeds 80
+    let having = grouped
+        .filter(&NestedCondition::Condition(
+            "avg_score".to_string(),
+            ">".to_string(),
+            "80".to_string(),
+        ))
+        .unwrap();
+    assert_eq!(
+        having.columns[0].data,
+        vec![
+            Value::Text("red".to_string()),
+            Value::Text("green".to_string())
+        ]
+    );
+    assert_eq!(
+        having.columns[1].data,
+        vec![Value::Float(85.0), Value::Float(95.0)]
+    );
+
+    // Conditions can combine aggregates
+    let having = grouped
+        .filter(&NestedCondition::And(
+            Box::new(NestedCondition::Condition(
+                "avg_score".to_string(),
+                ">".to_string(),
+                "80".to_string(),
+            )),
+            Box::new(NestedCondition::Condition(
+                "count_id".to_string(),
+                ">=".to_string(),
+                "2".to_string(),
+            )),
+        ))
+        .unwrap();
+    assert_eq!(having.columns[0].data, vec![Value::Text("red".to_string())]);
+}
+
+#[test]
+fn test_group_by_sum_overflow() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("team", ColumnDataType::Text, None, false, None),
+            Column::new("amount", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    let near_max = (i64::MAX - 1).to_string();
+    for row in [
+        ["1", "red", &near_max],
+        ["2", "red", &near_max],
+        ["3", "blue", "5"],
+    ] {
+        table
+            .insert(row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    let result = table.group_by(
+        vec!["team".to_string()],
+        vec![("amount".to_string(), AggregateFn::Sum)],
+    );
+    assert!(matches!(result, Err(Error::InvalidExpression(_))));
+}
+
+#[test]
+fn test_value_counts() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("city", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    for (id, city) in [
+        ("1", "Paris"),
+        ("2", "Oslo"),
+        ("3", "null"),
+        ("4", "Oslo"),
+        ("5", "Rome"),
+        ("6", "Oslo"),
+        ("7", "null"),
+    ] {
+        table
+            .insert(vec![id.to_string(), city.to_string()])
+            .unwrap();
+    }
+
+    let counts = table.value_counts("city").unwrap();
+    assert_eq!(
+        counts,
+        vec![
+            (Value::Text("Oslo".to_string()), 3),
+            (Value::Null, 2),
+            (Value::Text("Paris".to_string()), 1),
+            (Value::Text("Rome".to_string()), 1),
+        ]
+    );
+    assert!(table.show_value_counts("city").is_ok());
+
+    // Test counting values of a non-existing column
+    let result = table.value_counts("invalid");
+    assert!(matches!(result, Err(Error::NonExistingColumn(_))));
+}
+
+#[test]
+fn test_exists() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    // A bad condition errors even when the table is empty
+    let condition =
+        NestedCondition::Condition("invalid".to_string(), "=".to_string(), "value".to_string());
+    assert!(matches!(
+        table.exists(&condition),
+        Err(Error::NonExistingColumn(_))
+    ));
+    let condition =
+        NestedCondition::Condition("id".to_string(), "invalid".to_string(), "1".to_string());
+    assert!(matches!(
+        table.exists(&condition),
+        Err(Error::InvalidOperator(_))
+    ));
+
+    let condition =
+        NestedCondition::Condition("name".to_string(), "=".to_string(), "Bob".to_string());
+    assert!(!table.exists(&condition).unwrap());
+
+    table
+        .insert(vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+    assert!(!table.exists(&condition).unwrap());
+
+    table
+        .insert(vec!["2".to_string(), "Bob".to_string()])
+        .unwrap();
+    assert!(table.exists(&condition).unwrap());
+
+    // Test checking through the database wrapper
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![Column::new("name", ColumnDataType::Text, None, false, None)],
+    )
+    .unwrap();
+    assert!(!db.exists_in_table("users", &condition).unwrap());
+    db.insert_into_table("users", vec!["Bob".to_string()])
+        .unwrap();
+    assert!(db.exists_in_table("users", &condition).unwrap());
+}
+
+#[test]
+fn test_column_summary() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    // Nothing to profile yet
+    let summary = table.column_summary("score").unwrap();
+    assert_eq!(
+        summary,
+        ColumnSummary {
+            count: 0,
+            null_count: 0,
+            distinct_count: 0,
+            min: None,
+            max: None,
+            mean: None,
+        }
+    );
+
+    let rows = [
+        ["1", "Charlie", "80.0"],
+        ["2", "alice", "null"],
+        ["3", "Bob", "90.0"],
+        ["4", "null", "80.0"],
+        ["5", "Bob", "100.0"],
+    ];
+    for row in rows {
+        table
+            .insert(row.iter().map(|value| value.to_string()).collect())
+            .unwrap();
+    }
+
+    // Numeric column, the NULL is left out of every statistic
+    let summary = table.column_summary("score").unwrap();
+    assert_eq!(
+        summary,
+        ColumnSummary {
+            count: 4,
+            null_count: 1,
+            distinct_count: 3,
+            min: Some(Value::Float(80.0)),
+            max: Some(Value::Float(100.0)),
+            mean: Some(Value::Float(87.5)),
+        }
+    );
+
+    // Text column, compared lexicographically and without a mean
+    let summary = table.column_summary("name").unwrap();
+    assert_eq!(
+        summary,
+        ColumnSummary {
+            count: 4,
+            null_count: 1,
+            distinct_count: 3,
+            min: Some(Value::Text("Bob".to_string())),
+            max: Some(Value::Text("alice".to_string())),
+            mean: None,
+        }
+    );
+
+    let summary = table.column_summary("id").unwrap();
+    assert_eq!(summary.distinct_count, 5);
+    assert_eq!(summary.mean, Some(Value::Float(3.0)));
+
+    assert!(matches!(
+        table.column_summary("invalid"),
+        Err(Error::NonExistingColumn(column_name)) if column_name == "invalid"
+    ));
+}
+
+#[test]
+fn test_with_cumulative() {
+    let mut table = Table::new(
+        "sales",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("amount", ColumnDataType::Integer, None, false, None),
+            Column::new("region", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for row in [
+        ["1", "10", "north"],
+        ["2", "5", "south"],
+        ["3", "null", "north"],
+        ["4", "-3", "east"],
+    ] {
+        table
+            .insert(row.iter().map(|value| value.to_string()).collect())
+            .unwrap();
+    }
+
+    let result = table.with_cumulative("amount", "running_amount").unwrap();
+    assert_eq!(result.columns.len(), 4);
+    assert_eq!(result.columns[3].name, "running_amount");
+    assert_eq!(result.columns[3].data_type, ColumnDataType::Integer);
+    // The NULL keeps its row, with the total so far
+    assert_eq!(
+        result.columns[3].data,
+        vec![
+            Value::Integer(10),
+            Value::Integer(15),
+            Value::Integer(15),
+            Value::Integer(12),
+        ]
+    );
+    assert_eq!(result.columns[1].data, table.columns[1].data);
+    assert_eq!(table.columns.len(), 3);
+
+    assert!(matches!(
+        table.with_cumulative("region", "running_region"),
+        Err(Error::NonNumericColumn(column_name)) if column_name == "region"
+    ));
+    assert!(matches!(
+        table.with_cumulative("amount", "region"),
+        Err(Error::ColumnAlreadyExists(column_name)) if column_name == "region"
+    ));
+    assert!(matches!(
+        table.with_cumulative("invalid", "running_invalid"),
+        Err(Error::NonExistingColumn(_))
+    ));
+
+    // A running total past i64::MAX is an error rather than a column of NULLs
+    table
+        .insert(vec![
+            "5".to_string(),
+            i64::MAX.to_string(),
+            "west".to_string(),
+        ])
+        .unwrap();
+    assert!(matches!(
+        table.with_cumulative("amount", "running_amount"),
+        Err(Error::InvalidExpression(_))
+    ));
+}
@@ -0,0 +1,196 @@
+use crate::column::{Column, ColumnDataType, ForeignKeyInfo, Value};
+use crate::table::{table_errors::Error, AggregateFn, Expr, NestedCondition, Operand, Table};
+
+#[test]
+fn test_column_builder() {
+    let id = Column::builder("id", ColumnDataType::Integer)
+        .primary_key()
+        .build();
+    assert!(id.is_primary_key);
+    assert!(!id.not_null);
+    assert!(!id.unique);
+    assert!(id.foreign_key.is_none());
+
+    let owner = Column::builder("owner_id", ColumnDataType::Integer)
+        .foreign_key(ForeignKeyInfo::new("users", "id"))
+        .build();
+    assert_eq!(owner.foreign_key, Some(ForeignKeyInfo::new("users", "id")));
+    assert!(!owner.is_primary_key);
+
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            id,
+            Column::builder("email", ColumnDataType::Text)
+                .not_null()
+                .unique()
+                .build(),
+            Column::builder("nickname", ColumnDataType::Text).build(),
+        ],
+    )
+    .unwrap();
+
+    table
+        .insert(vec![
+            "1".to_string(),
+            "alice@example.com".to_string(),
+            "null".to_string(),
+        ])
+        .unwrap();
+
+    // Test inserting a null value into a not-null column
+    let result = table.insert(vec!["2".to_string(), "null".to_string(), "bob".to_string()]);
+    assert!(matches!(result, Err(Error::NullValue(_))));
+
+    // Test inserting a duplicate value into a unique column
+    let result = table.insert_with_columns(
+        vec!["id".to_string(), "email".to_string()],
+        vec!["3".to_string(), "alice@example.com".to_string()],
+    );
+    assert!(matches!(result, Err(Error::DuplicateValue(_, _))));
+
+    // Rejected records leave the table untouched
+    assert_eq!(table.row_count(), 1);
+}
+
+#[test]
+fn test_decimal_values() {
+    // Parsing keeps the written digits, and Display reproduces them
+    for literal in ["19.99", "-0.5", "0.30", "100", "0.001"] {
+        assert_eq!(Value::parse_decimal(literal).unwrap().to_string(), literal);
+    }
+    assert_eq!(Value::parse_decimal("19.99"), Some(Value::Decimal(1999, 2)));
+    for literal in ["", ".", "1e5", "abc", "1.2.3", "--1"] {
+        assert_eq!(Value::parse_decimal(literal), None);
+    }
+
+    // Equality is exact and ignores the scale
+    assert_eq!(Value::Decimal(150, 2), Value::Decimal(15, 1));
+    assert_ne!(Value::Decimal(15, 1), Value::Float(1.5));
+
+    let mut table = Table::new(
+        "prices",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("price", ColumnDataType::Decimal, None, false, None),
+            Column::new("approx", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, price) in [("1", "0.1"), ("2", "2.50"), ("3", "null")] {
+        table
+            .insert(vec![id.to_string(), price.to_string(), price.to_string()])
+            .unwrap();
+    }
+
+    // 0.1 + 0.2 is 0.3 as a decimal, but not as a float
+    let first = || NestedCondition::Condition("id".to_string(), "=".to_string(), "1".to_string());
+    for column in ["price", "approx"] {
+        table
+            .update_expr(
+                column,
+                Expr::Add(
+                    Operand::Col(column.to_string()),
+                    Operand::Lit("0.2".to_string()),
+                ),
+                first(),
+            )
+            .unwrap();
+    }
+    let equals_point_three = |column: &str| {
+        NestedCondition::Condition(column.to_string(), "=".to_string(), "0.3".to_string())
+    };
+    assert_eq!(
+        table
+            .filter(&equals_point_three("price"))
+            .unwrap()
+            .row_count(),
+        1
+    );
+    assert_eq!(
+        table
+            .filter(&equals_point_three("approx"))
+            .unwrap()
+            .row_count(),
+        0
+    );
+
+    // Ordering and sums are exact as well
+    let greater =
+        NestedCondition::Condition("price".to_string(), ">".to_string(), "2.499".to_string());
+    assert_eq!(table.filter(&greater).unwrap().row_count(), 1);
+
+    let sums = table
+        .group_by(vec![], vec![("price".to_string(), AggregateFn::Sum)])
+        .unwrap();
+    assert_eq!(sums.columns[0].data, vec![Value::Decimal(280, 2)]);
+    assert_eq!(sums.columns[0].data[0].to_string(), "2.80");
+}
+
+#[test]
+fn test_cmp_value() {
+    use std::cmp::Ordering;
+
+    let ordered = [
+        Value::Null,
+        Value::Integer(-3),
+        Value::Integer(7),
+        Value::Float(f64::NEG_INFINITY),
+        Value::Float(-1.5),
+        Value::Float(2.0),
+        Value::Float(f64::NAN),
+        Value::Decimal(-5, 1),
+        Value::Decimal(125, 2),
+        Value::Text("".to_string()),
+        Value::Text("apple".to_string()),
+        Value::Text("banana".to_string()),
+    ];
+    for (i, left) in ordered.iter().enumerate() {
+        for (j, right) in ordered.iter().enumerate() {
+            assert_eq!(
+                left.cmp_value(right),
+                Some(i.cmp(&j)),
+                "{:?} vs {:?}",
+                left,
+                right
+            );
+        }
+    }
+
+    // Values that are equal with `==` compare as equal, and so do NaNs
+    assert_eq!(
+        Value::Decimal(15, 1).cmp_value(&Value::Decimal(150, 2)),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(
+        Value::Float(-0.0).cmp_value(&Value::Float(0.0)),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(
+        Value::Float(f64::NAN).cmp_value(&Value::Float(f64::NAN)),
+        Some(Ordering::Equal)
+    );
+
+    // Tables don't accept NaN, so sorting only has to put NULL after everything
+    let mut table = Table::new(
+        "test_table",
+        vec![Column::new(
+            "score",
+            ColumnDataType::Float,
+            None,
+            false,
+            None,
+        )],
+    )
+    .unwrap();
+    let result = table.insert(vec!["NaN".to_string()]);
+    assert!(matches!(result, Err(Error::NonFiniteFloat(_, _))));
+    for score in ["null", "2.5", "-1"] {
+        table.insert(vec![score.to_string()]).unwrap();
+    }
+    let sorted = table.order_by("score", false).unwrap();
+    assert_eq!(
+        sorted.columns[0].data,
+        [Value::Float(-1.0), Value::Float(2.5), Value::Null]
+    );
+}
@@ -0,0 +1,150 @@
+use crate::column::{Column, ColumnDataType, Value};
+use crate::table::{table_errors::Error, NestedCondition, Table};
+
+fn condition(column_name: &str, operator: &str, value: &str) -> NestedCondition {
+    NestedCondition::Condition(
+        column_name.to_string(),
+        operator.to_string(),
+        value.to_string(),
+    )
+}
+
+#[test]
+fn test_parse_condition() {
+    // AND binds tighter than OR, parentheses override it
+    assert_eq!(
+        NestedCondition::parse("age > 25 AND (score >= 85 OR name = 'Charlie')").unwrap(),
+        NestedCondition::And(
+            Box::new(condition("age", ">", "25")),
+            Box::new(NestedCondition::Or(
+                Box::new(condition("score", ">=", "85")),
+                Box::new(condition("name", "=", "Charlie")),
+            )),
+        )
+    );
+    assert_eq!(
+        NestedCondition::parse("a = 1 or b = 2 and c = 3").unwrap(),
+        NestedCondition::Or(
+            Box::new(condition("a", "=", "1")),
+            Box::new(NestedCondition::And(
+                Box::new(condition("b", "=", "2")),
+                Box::new(condition("c", "=", "3")),
+            )),
+        )
+    );
+
+    // Chains are left-associative
+    assert_eq!(
+        NestedCondition::parse("a = 1 AND b = 2 AND c = 3").unwrap(),
+        NestedCondition::And(
+            Box::new(NestedCondition::And(
+                Box::new(condition("a", "=", "1")),
+                Box::new(condition("b", "=", "2")),
+            )),
+            Box::new(condition("c", "=", "3")),
+        )
+    );
+
+    // NOT binds tighter than AND
+    assert_eq!(
+        NestedCondition::parse("NOT a != -1.5 AND NOT (b <= 2)").unwrap(),
+        NestedCondition::And(
+            Box::new(NestedCondition::Not(Box::new(condition("a", "!=", "-1.5")))),
+            Box::new(NestedCondition::Not(Box::new(condition("b", "<=", "2")))),
+        )
+    );
+
+    // NULL checks, quoted identifiers and escaped quotes in text
+    assert_eq!(
+        NestedCondition::parse("email is not null").unwrap(),
+        condition("email", "IS NOT NULL", "")
+    );
+    assert_eq!(
+        NestedCondition::parse("\"user name\" = 'O''Brien' OR \"and\" IS NULL").unwrap(),
+        NestedCondition::Or(
+            Box::new(condition("user name", "=", "O'Brien")),
+            Box::new(condition("and", "IS NULL", "")),
+        )
+    );
+    assert_eq!(
+        NestedCondition::parse("((score<10))").unwrap(),
+        condition("score", "<", "10")
+    );
+}
+
+#[test]
+fn test_parse_condition_errors() {
+    let position = |expr: &str| match NestedCondition::parse(expr) {
+        Err(Error::InvalidConditionSyntax(position, _)) => position,
+        result => panic!("{:?} parsed as {:?}", expr, result),
+    };
+
+    assert_eq!(position(""), 0);
+    assert_eq!(position("age >"), 5);
+    assert_eq!(position("age 25"), 4);
+    assert_eq!(position("age == 25"), 5);
+    assert_eq!(position("age > 25abc"), 6);
+    assert_eq!(position("age > 25 AND"), 12);
+    assert_eq!(position("age > 25 score < 3"), 9);
+    assert_eq!(position("(age > 25"), 9);
+    assert_eq!(position("age > 25)"), 8);
+    assert_eq!(position("name = 'Bob"), 7);
+    assert_eq!(position("name = Bob"), 7);
+    assert_eq!(position("and = 1"), 0);
+    assert_eq!(position("from = 1"), 0);
+    assert_eq!(position("age IS 5"), 7);
+}
+
+#[test]
+fn test_filter_with_parsed_condition() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    for row in [
+        ["1", "Alice", "30", "80.0"],
+        ["2", "Bob", "20", "95.0"],
+        ["3", "Charlie", "40", "70.0"],
+        ["4", "Dave", "35", "90.0"],
+        ["5", "Eve", "null", "60.0"],
+    ] {
+        table
+            .insert(row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    let ids = |expr: &str| {
+        let filtered = table
+            .filter(&NestedCondition::parse(expr).unwrap())
+            .unwrap();
+        filtered.columns[0].data.clone()
+    };
+
+    assert_eq!(
+        ids("age > 25 AND (score >= 85 OR name = 'Charlie')"),
+        vec![Value::Integer(3), Value::Integer(4)]
+    );
+
+    // NOT also keeps the records where the inner comparison fails because of a NULL
+    assert_eq!(
+        ids("NOT age > 25"),
+        vec![Value::Integer(2), Value::Integer(5)]
+    );
+    assert_eq!(
+        ids("NOT (age IS NULL OR score < 85)"),
+        vec![Value::Integer(2), Value::Integer(4)]
+    );
+
+    // Parsed conditions are still checked against the table
+    let result = table.filter(&NestedCondition::parse("height > 1").unwrap());
+    assert!(matches!(result, Err(Error::NonExistingColumn(_))));
+    let result = table.filter(&NestedCondition::parse("age = 'old'").unwrap());
+    assert!(matches!(result, Err(Error::InvalidConditionValue(_, _))));
+}
@@ -0,0 +1,3235 @@
+use crate::column::{Column, ColumnDataType, ForeignKeyInfo, Value};
+use crate::database::db_errors::Error;
+use crate::database::{AffectedRows, Database, IntegrityViolation, QueryResult, UpdateFromOutcome};
+use crate::table::{table_errors, NestedCondition};
+
+fn users_db() -> Database {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table("users", vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+    db.insert_into_table("users", vec!["2".to_string(), "Bob".to_string()])
+        .unwrap();
+    db
+}
+
+#[test]
+fn test_add_foreign_key_column() {
+    let mut db = users_db();
+    db.create_table(
+        "addresses",
+        vec![Column::new(
+            "address",
+            ColumnDataType::Text,
+            None,
+            false,
+            None,
+        )],
+    )
+    .unwrap();
+
+    // Test adding a column referencing a non-existing table
+    let result = db.add_foreign_key_column(
+        "addresses",
+        Column::new("user_id", ColumnDataType::Integer, None, false, None),
+        ForeignKeyInfo::new("missing", "id"),
+    );
+    assert!(matches!(result, Err(Error::ReferencedTableNotFound(_))));
+
+    // Test adding a column referencing a non-existing column
+    let result = db.add_foreign_key_column(
+        "addresses",
+        Column::new("user_id", ColumnDataType::Integer, None, false, None),
+        ForeignKeyInfo::new("users", "missing"),
+    );
+    assert!(matches!(result, Err(Error::ReferencedColumnNotFound(_, _))));
+
+    // Test adding a column referencing a non-primary key column
+    let result = db.add_foreign_key_column(
+        "addresses",
+        Column::new("user_id", ColumnDataType::Integer, None, false, None),
+        ForeignKeyInfo::new("users", "user_name"),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::ReferencedColumnNotPrimaryKey(_, _))
+    ));
+
+    // Test adding a valid foreign key column
+    db.add_foreign_key_column(
+        "addresses",
+        Column::new("user_id", ColumnDataType::Integer, None, false, None),
+        ForeignKeyInfo::new("users", "id"),
+    )
+    .unwrap();
+    assert_eq!(
+        db.get_table("users").unwrap().referenced_as_foreign_key,
+        vec![("addresses".to_string(), "user_id".to_string())]
+    );
+
+    // The new column is enforced on insert
+    let result = db.insert_into_table(
+        "addresses",
+        vec!["123 Main St.".to_string(), "3".to_string()],
+    );
+    assert!(matches!(result, Err(Error::ForeignKeyViolation(_, _, _))));
+    db.insert_into_table(
+        "addresses",
+        vec!["123 Main St.".to_string(), "1".to_string()],
+    )
+    .unwrap();
+
+    // Naming a column twice is rejected before the foreign key checks
+    let result = db.insert_with_columns_into_table(
+        "addresses",
+        vec![
+            "user_id".to_string(),
+            "address".to_string(),
+            "user_id".to_string(),
+        ],
+        vec!["3".to_string(), "1 Elm St.".to_string(), "1".to_string()],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::DuplicateColumnInInsert(column_name)))
+            if column_name == "user_id"
+    ));
+    assert_eq!(db.get_table("addresses").unwrap().row_count(), 1);
+
+    // Foreign key values that don't parse are reported like in the table itself
+    let result = db.insert_into_table(
+        "addresses",
+        vec!["123 Main St.".to_string(), "one".to_string()],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::ParseError {
+            column_name,
+            value,
+            expected: ColumnDataType::Integer,
+        })) if column_name == "user_id" && value == "one"
+    ));
+
+    // Test adding a column that already exists
+    let result = db.add_foreign_key_column(
+        "addresses",
+        Column::new("user_id", ColumnDataType::Integer, None, false, None),
+        ForeignKeyInfo::new("users", "id"),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::ColumnAlreadyExists(
+            _
+        )))
+    ));
+
+    // Existing rows can't be back-filled with NULL foreign keys
+    let result = db.add_foreign_key_column(
+        "addresses",
+        Column::new("owner_id", ColumnDataType::Integer, None, false, None),
+        ForeignKeyInfo::new("users", "id"),
+    );
+    assert!(matches!(result, Err(Error::NullForeignKey(_))));
+}
+
+#[test]
+fn test_name_validation() {
+    let mut db = users_db();
+
+    let result = db.create_table(
+        "",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::InvalidName(name, _))) if name.is_empty()
+    ));
+    let result = db.create_table(
+        "orders",
+        vec![Column::new(
+            "id\n",
+            ColumnDataType::Integer,
+            None,
+            true,
+            None,
+        )],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::InvalidName(name, _))) if name == "id\n"
+    ));
+
+    // A table without columns is an error instead of a panic
+    let result = db.create_table("orders", vec![]);
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::NoColumns))
+    ));
+
+    // The database can lower the length limit
+    db.set_max_name_length(6);
+    let result = db.create_table(
+        "orders",
+        vec![Column::new(
+            "order_id",
+            ColumnDataType::Integer,
+            None,
+            true,
+            None,
+        )],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::InvalidName(name, _))) if name == "order_id"
+    ));
+    db.create_table(
+        "orders",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+
+    let result = db.add_foreign_key_column(
+        "orders",
+        Column::new("user_id", ColumnDataType::Integer, None, false, None),
+        ForeignKeyInfo::new("users", "id"),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::InvalidName(name, _))) if name == "user_id"
+    ));
+    assert_eq!(db.get_table("orders").unwrap().columns.len(), 1);
+
+    let result = db.rename_column_in_table("orders", "id", "order_id");
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::InvalidName(name, _))) if name == "order_id"
+    ));
+    assert_eq!(db.get_table("orders").unwrap().columns[0].name, "id");
+}
+
+#[test]
+fn test_circular_foreign_key_reference() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "a",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+    db.create_table(
+        "b",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "a_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("a", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+
+    // a -> b would close the cycle a -> b -> a
+    let result = db.add_foreign_key_column(
+        "a",
+        Column::new("b_id", ColumnDataType::Integer, None, false, None),
+        ForeignKeyInfo::new("b", "id"),
+    );
+    match result {
+        Err(Error::CircularForeignKeyReference(cycle)) => {
+            assert_eq!(cycle, vec!["a", "b", "a"]);
+        }
+        other => panic!("Expected a circular reference error, got {:?}", other),
+    }
+
+    // Nothing was registered by the rejected column
+    assert!(db.get_table("a").unwrap().columns.len() == 1);
+    assert!(db
+        .get_table("b")
+        .unwrap()
+        .referenced_as_foreign_key
+        .is_empty());
+}
+
+#[test]
+fn test_select_from_table() {
+    let db = users_db();
+
+    // Test selecting every column without a condition
+    let rows = db.select_from_table("users", vec![], None).unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            vec![Value::Integer(1), Value::Text("Alice".to_string())],
+            vec![Value::Integer(2), Value::Text("Bob".to_string())],
+        ]
+    );
+
+    // Test selecting one column of the records matching a condition on another column
+    let condition = NestedCondition::Condition("id".to_string(), ">".to_string(), "1".to_string());
+    let rows = db
+        .select_from_table("users", vec!["user_name".to_string()], Some(condition))
+        .unwrap();
+    assert_eq!(rows, vec![vec![Value::Text("Bob".to_string())]]);
+
+    // Test selecting rows with lookup by column name
+    let rows = db.select_rows_from_table("users", vec![], None).unwrap();
+    assert_eq!(
+        rows[1].get("user_name"),
+        Some(&Value::Text("Bob".to_string()))
+    );
+
+    // Test selecting from a non-existing table
+    let result = db.select_from_table("missing", vec![], None);
+    assert!(matches!(result, Err(Error::TableNotFound(_))));
+
+    // Test selecting a non-existing column
+    let result = db.select_from_table("users", vec!["missing".to_string()], None);
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::NonExistingColumns(
+            _
+        )))
+    ));
+}
+
+#[test]
+fn test_self_referential_foreign_key() {
+    let mut db = Database::new("test_db".to_string());
+
+    // Test referencing a column of the table being created that is not its primary key
+    let result = db.create_table(
+        "employees",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new(
+                "manager_name",
+                ColumnDataType::Text,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("employees", "name")),
+            ),
+        ],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::ReferencedColumnNotPrimaryKey(_, _))
+    ));
+
+    db.create_table(
+        "employees",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new(
+                "manager_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("employees", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        db.get_table("employees").unwrap().referenced_as_foreign_key,
+        vec![("employees".to_string(), "manager_id".to_string())]
+    );
+
+    // A root row has no manager, and a child row references it
+    db.insert_into_table(
+        "employees",
+        vec!["1".to_string(), "Alice".to_string(), "null".to_string()],
+    )
+    .unwrap();
+    db.insert_into_table(
+        "employees",
+        vec!["2".to_string(), "Bob".to_string(), "1".to_string()],
+    )
+    .unwrap();
+
+    // Test referencing a manager that does not exist
+    let result = db.insert_into_table(
+        "employees",
+        vec!["3".to_string(), "Charlie".to_string(), "42".to_string()],
+    );
+    assert!(matches!(result, Err(Error::ForeignKeyViolation(_, _, _))));
+
+    assert_eq!(
+        db.get_table("employees").unwrap().columns[2].data,
+        vec![Value::Null, Value::Integer(1)]
+    );
+
+    // Conditions matching no row affect nothing, and don't cascade
+    let condition = NestedCondition::Condition("id".to_string(), "=".to_string(), "99".to_string());
+    let affected_rows = db
+        .update_with_nested_conditions_in_table(
+            "employees",
+            ("id".to_string(), "7".to_string()),
+            condition,
+        )
+        .unwrap();
+    assert_eq!(affected_rows, AffectedRows::default());
+    let condition = NestedCondition::Condition("id".to_string(), "=".to_string(), "99".to_string());
+    let affected_rows = db
+        .delete_with_nested_conditions_in_table("employees", condition)
+        .unwrap();
+    assert_eq!(affected_rows.total(), 0);
+
+    // Deleting the root cascades to the rows that report to it
+    let condition = NestedCondition::Condition("id".to_string(), "=".to_string(), "1".to_string());
+    let affected_rows = db
+        .delete_with_nested_conditions_in_table("employees", condition)
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 1
+        }
+    );
+    assert_eq!(db.get_table("employees").unwrap().row_count(), 0);
+}
+
+#[test]
+fn test_update_self_reference_to_null() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "employees",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "manager_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("employees", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+    for row in [["1", "null"], ["2", "1"], ["3", "1"]] {
+        db.insert_into_table("employees", row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    // "null" clears a manager through every update path, and is stored as NULL
+    db.update_with_nested_conditions_in_table(
+        "employees",
+        ("manager_id".to_string(), "null".to_string()),
+        NestedCondition::Condition("id".to_string(), "=".to_string(), "2".to_string()),
+    )
+    .unwrap();
+    db.update_by_pk_in_table(
+        "employees",
+        "3",
+        vec![("manager_id".to_string(), "NULL".to_string())],
+    )
+    .unwrap();
+    assert_eq!(
+        db.get_table("employees").unwrap().columns[1].data,
+        vec![Value::Null, Value::Null, Value::Null]
+    );
+    assert!(db.check_referential_integrity().is_empty());
+
+    db.update_column_in_table("employees", "manager_id", "1")
+        .unwrap();
+    db.update_column_in_table("employees", "manager_id", " Null ")
+        .unwrap();
+    assert_eq!(
+        db.get_table("employees").unwrap().columns[1].data,
+        vec![Value::Null, Value::Null, Value::Null]
+    );
+}
+
+#[test]
+fn test_deferred_foreign_key_checks() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("users", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+
+    // Child rows are accepted before their parents exist
+    db.insert_into_table_deferred("orders", vec!["1".to_string(), "1".to_string()])
+        .unwrap();
+    db.insert_into_table_deferred("orders", vec!["2".to_string(), "2".to_string()])
+        .unwrap();
+    db.insert_into_table("users", vec!["1".to_string()])
+        .unwrap();
+
+    // The missing parent is reported and the row stays in the table
+    let errors = db.validate_constraints().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&errors[0], Error::ForeignKeyViolation(value, _, _) if value == "2"));
+    assert_eq!(db.get_table("orders").unwrap().row_count(), 2);
+
+    db.insert_into_table("users", vec!["2".to_string()])
+        .unwrap();
+    assert!(db.validate_constraints().is_ok());
+}
+
+#[test]
+fn test_check_referential_integrity() {
+    let mut db = users_db();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("users", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table("orders", vec!["1".to_string(), "1".to_string()])
+        .unwrap();
+    assert!(db.check_referential_integrity().is_empty());
+
+    // Plant an orphan by editing the table directly
+    db.get_table_mut("orders")
+        .unwrap()
+        .insert(vec!["2".to_string(), "42".to_string()])
+        .unwrap();
+
+    let errors = db.check_referential_integrity();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        Error::ForeignKeyViolation(value, column, table)
+            if value == "42" && column == "user_id" && table == "users"
+    ));
+}
+
+#[test]
+fn test_write_ahead_log_replay() {
+    let path = std::env::temp_dir().join("rdms_wal_replay_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![
+            Column::builder("id", ColumnDataType::Integer)
+                .primary_key()
+                .build(),
+            Column::builder("user_name", ColumnDataType::Text)
+                .not_null()
+                .build(),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("users", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+    // Float defaults are logged exactly, not in their rounded display form
+    db.create_table(
+        "rates",
+        vec![Column::builder("rate", ColumnDataType::Float)
+            .default_value(Value::Float(0.125))
+            .build()],
+    )
+    .unwrap();
+    for (id, name) in [
+        ("1", "Alice"),
+        ("2", "Bob \"the builder\""),
+        ("3", "Charlie"),
+    ] {
+        db.insert_into_table("users", vec![id.to_string(), name.to_string()])
+            .unwrap();
+    }
+    db.insert_many_into_table(
+        "orders",
+        vec![
+            vec!["10".to_string(), "1".to_string()],
+            vec!["11".to_string(), "3".to_string()],
+        ],
+    )
+    .unwrap();
+
+    // A failed operation is not logged, whether it fails before or after its record is written
+    let log_len = std::fs::metadata(path).unwrap().len();
+    assert!(db
+        .insert_into_table("orders", vec!["12".to_string(), "42".to_string()])
+        .is_err());
+    assert!(db
+        .insert_into_table("users", vec!["1".to_string(), "Dave".to_string()])
+        .is_err());
+    assert!(db
+        .update_column_in_table("users", "user_name", "null")
+        .is_err());
+    assert_eq!(std::fs::metadata(path).unwrap().len(), log_len);
+
+    // The primary key update cascades to orders, and the delete cascades as well
+    let affected_rows = db
+        .update_with_nested_conditions_in_table(
+            "users",
+            ("id".to_string(), "5".to_string()),
+            NestedCondition::Condition(
+                "user_name".to_string(),
+                "=".to_string(),
+                "Alice".to_string(),
+            ),
+        )
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 1
+        }
+    );
+    let affected_rows = db
+        .delete_with_nested_conditions_in_table(
+            "users",
+            NestedCondition::Or(
+                Box::new(NestedCondition::Condition(
+                    "id".to_string(),
+                    "=".to_string(),
+                    "3".to_string(),
+                )),
+                Box::new(NestedCondition::Condition(
+                    "id".to_string(),
+                    "=".to_string(),
+                    "99".to_string(),
+                )),
+            ),
+        )
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 1
+        }
+    );
+    // Negated conditions are logged as well, this one matches no user
+    let affected_rows = db
+        .delete_with_nested_conditions_in_table(
+            "users",
+            NestedCondition::parse("NOT (id = 5 OR id = 2)").unwrap(),
+        )
+        .unwrap();
+    assert_eq!(affected_rows.total(), 0);
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    assert_eq!(replayed.name, "test_db");
+
+    let users = replayed.get_table("users").unwrap();
+    assert!(users.columns[1].not_null);
+    assert_eq!(
+        users.columns[0].data,
+        vec![Value::Integer(5), Value::Integer(2)]
+    );
+    assert_eq!(
+        users.columns[1].data,
+        vec![
+            Value::Text("Alice".to_string()),
+            Value::Text("Bob \"the builder\"".to_string()),
+        ]
+    );
+
+    let orders = replayed.get_table("orders").unwrap();
+    assert_eq!(orders.columns[0].data, vec![Value::Integer(10)]);
+    assert_eq!(orders.columns[1].data, vec![Value::Integer(5)]);
+    assert_eq!(
+        replayed.get_table("rates").unwrap().columns[0].default_value,
+        Some(Value::Float(0.125))
+    );
+
+    // A torn final record, as left by a crash mid-write, is ignored
+    let mut contents = std::fs::read_to_string(path).unwrap();
+    contents.push_str("{\"op\":\"insert\",\"table\":\"us");
+    std::fs::write(path, contents).unwrap();
+    let replayed = Database::replay_wal(path).unwrap();
+    assert_eq!(replayed.get_table("users").unwrap().row_count(), 2);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_insert_or_ignore_into_table() {
+    let mut db = users_db();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+
+    let row = |order_id: &str, user_id: &str| vec![order_id.to_string(), user_id.to_string()];
+
+    assert!(db
+        .insert_or_ignore_into_table("orders", row("1", "1"))
+        .unwrap());
+    assert!(!db
+        .insert_or_ignore_into_table("orders", row("1", "2"))
+        .unwrap());
+    assert_eq!(db.get_table("orders").unwrap().row_count(), 1);
+
+    // Foreign key violations still fail, even for a duplicate key
+    let result = db.insert_or_ignore_into_table("orders", row("1", "3"));
+    assert!(matches!(result, Err(Error::ForeignKeyViolation(_, _, _))));
+    let result = db.insert_or_ignore_into_table("missing", row("2", "1"));
+    assert!(matches!(result, Err(Error::TableNotFound(_))));
+
+    // Inserts pass the outcome of the table insert through
+    let outcome = db.insert_into_table("orders", row("2", "2")).unwrap();
+    assert_eq!(outcome.row_index, 1);
+    assert_eq!(outcome.primary_key, Some(Value::Integer(2)));
+    let outcome = db
+        .insert_with_columns_into_table(
+            "orders",
+            vec!["order_id".to_string(), "user_id".to_string()],
+            row("3", "1"),
+        )
+        .unwrap();
+    assert_eq!(outcome.row_index, 2);
+    assert_eq!(outcome.primary_key, Some(Value::Integer(3)));
+}
+
+#[test]
+fn test_insert_many_into_table() {
+    let mut db = users_db();
+    db.create_table(
+        "employees",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+            Column::new(
+                "manager_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("employees", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+
+    let rows = |rows: &[[&str; 3]]| -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|value| value.to_string()).collect())
+            .collect()
+    };
+
+    // A foreign key violation anywhere in the batch rejects the whole batch
+    let result =
+        db.insert_many_into_table("employees", rows(&[["1", "1", "null"], ["2", "3", "1"]]));
+    assert!(matches!(
+        result,
+        Err(Error::BatchRecord(1, error)) if matches!(*error, Error::ForeignKeyViolation(_, _, _))
+    ));
+    let result = db.insert_many_into_table("employees", rows(&[["1", "null", "null"]]));
+    assert!(matches!(
+        result,
+        Err(Error::BatchRecord(0, error)) if matches!(*error, Error::NullForeignKey(_))
+    ));
+    let result =
+        db.insert_many_into_table("employees", rows(&[["1", "1", "null"], ["1", "2", "1"]]));
+    assert!(matches!(
+        result,
+        Err(Error::BatchRecord(1, error))
+            if matches!(*error, Error::TableError(table_errors::Error::DuplicatePrimaryKey))
+    ));
+    assert_eq!(db.get_table("employees").unwrap().row_count(), 0);
+
+    // Rows may reference rows of the same batch, in any order
+    let inserted = db
+        .insert_many_into_table("employees", rows(&[["2", "2", "1"], ["1", "1", "null"]]))
+        .unwrap();
+    assert_eq!(inserted, 2);
+    assert_eq!(db.get_table("employees").unwrap().row_count(), 2);
+
+    let result = db.insert_many_into_table("missing", vec![]);
+    assert!(matches!(result, Err(Error::TableNotFound(_))));
+}
+
+#[test]
+fn test_truncate_table() {
+    let mut db = users_db();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+            Column::new(
+                "parent_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("orders", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    for row in [["1", "1", "null"], ["2", "1", "1"], ["3", "2", "null"]] {
+        db.insert_into_table("orders", row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    // Restrict refuses while other tables reference the table
+    let result = db.truncate_table("users", false);
+    assert!(matches!(
+        result,
+        Err(Error::ReferencedByTables(table, referencing)) if table == "users" && referencing == ["orders"]
+    ));
+    assert_eq!(db.get_table("users").unwrap().row_count(), 2);
+
+    // Self-references do not block a restricted truncate
+    let affected_rows = db.truncate_table("orders", false).unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 3,
+            cascaded: 0
+        }
+    );
+
+    db.insert_into_table(
+        "orders",
+        vec!["1".to_string(), "2".to_string(), "null".to_string()],
+    )
+    .unwrap();
+    let affected_rows = db.truncate_table("users", true).unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 2,
+            cascaded: 1
+        }
+    );
+    assert_eq!(db.get_table("users").unwrap().row_count(), 0);
+    assert_eq!(db.get_table("orders").unwrap().row_count(), 0);
+
+    let result = db.truncate_table("missing", true);
+    assert!(matches!(result, Err(Error::TableNotFound(_))));
+}
+
+#[test]
+fn test_update_by_pk_in_table() {
+    let mut db = users_db();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    for row in [["1", "1"], ["2", "1"], ["3", "2"]] {
+        db.insert_into_table("orders", row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    // Foreign keys of the assigned columns are checked
+    let result = db.update_by_pk_in_table(
+        "orders",
+        "3",
+        vec![("user_id".to_string(), "7".to_string())],
+    );
+    assert!(matches!(result, Err(Error::ForeignKeyViolation(_, _, _))));
+
+    // A new primary key is cascaded to the rows referencing the old one
+    let affected_rows = db
+        .update_by_pk_in_table("users", "1", vec![("id".to_string(), "10".to_string())])
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 2
+        }
+    );
+    assert_eq!(
+        db.get_table("orders").unwrap().columns[1].data,
+        vec![Value::Integer(10), Value::Integer(10), Value::Integer(2)]
+    );
+
+    let affected_rows = db
+        .update_by_pk_in_table(
+            "users",
+            "2",
+            vec![("user_name".to_string(), "Bo".to_string())],
+        )
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 0
+        }
+    );
+    let affected_rows = db
+        .update_by_pk_in_table("users", "3", vec![("id".to_string(), "4".to_string())])
+        .unwrap();
+    assert_eq!(affected_rows, AffectedRows::default());
+}
+
+#[test]
+fn test_replace_into_table() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "products",
+        vec![
+            Column::new("code", ColumnDataType::Decimal, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "product_code",
+                ColumnDataType::Decimal,
+                None,
+                false,
+                ForeignKeyInfo::new("products", "code").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    let row = |values: [&str; 2]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+    let affected_rows = db
+        .replace_into_table("products", row(["1.5", "Pen"]))
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 0
+        }
+    );
+    db.insert_into_table("orders", row(["1", "1.5"])).unwrap();
+
+    // Foreign keys of the new values are checked
+    let result = db.replace_into_table("orders", row(["1", "2.5"]));
+    assert!(matches!(result, Err(Error::ForeignKeyViolation(_, _, _))));
+
+    // The same key stored with another scale is cascaded to the referencing rows
+    let affected_rows = db
+        .replace_into_table("products", row(["1.50", "Ink pen"]))
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 1
+        }
+    );
+    assert_eq!(
+        db.get_table("orders").unwrap().columns[1].data[0].to_string(),
+        "1.50"
+    );
+
+    let affected_rows = db
+        .replace_into_table("products", row(["1.50", "Quill"]))
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 0
+        }
+    );
+    assert_eq!(db.get_table("products").unwrap().row_count(), 1);
+}
+
+#[test]
+fn test_update_from() {
+    let mut db = users_db();
+    db.create_table(
+        "addresses",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+            Column::new("city", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "staging_addresses",
+        vec![
+            Column::new("user_id", ColumnDataType::Integer, None, false, None),
+            Column::new("city", ColumnDataType::Text, None, false, None),
+            Column::new("zip", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    let row = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    for values in [["1", "1", "Paris"], ["2", "2", "Oslo"], ["3", "1", "Lima"]] {
+        db.insert_into_table("addresses", row(&values)).unwrap();
+    }
+    for values in [
+        ["1", "Rome", "100"],
+        ["1", "Cairo", "200"],
+        ["3", "Quito", "300"],
+    ] {
+        db.insert_into_table("staging_addresses", row(&values))
+            .unwrap();
+    }
+    let join_on = ("user_id".to_string(), "user_id".to_string());
+
+    // Every row of user 1 takes the first matching staging row, the others are untouched
+    let outcome = db
+        .update_from(
+            "addresses",
+            "staging_addresses",
+            join_on.clone(),
+            vec![("city".to_string(), "city".to_string())],
+        )
+        .unwrap();
+    assert_eq!(
+        outcome,
+        UpdateFromOutcome {
+            affected_rows: AffectedRows {
+                rows: 2,
+                cascaded: 0
+            },
+            unmatched: 1,
+        }
+    );
+    assert_eq!(
+        db.get_table("addresses").unwrap().columns[2].data,
+        vec![
+            Value::Text("Rome".to_string()),
+            Value::Text("Oslo".to_string()),
+            Value::Text("Rome".to_string()),
+        ]
+    );
+
+    // Assignments need matching data types and valid foreign keys
+    let result = db.update_from(
+        "addresses",
+        "staging_addresses",
+        join_on.clone(),
+        vec![("city".to_string(), "zip".to_string())],
+    );
+    assert!(matches!(result, Err(Error::MismatchedColumnTypes(_, _))));
+    let result = db.update_from(
+        "addresses",
+        "staging_addresses",
+        ("id".to_string(), "user_id".to_string()),
+        vec![("user_id".to_string(), "zip".to_string())],
+    );
+    assert!(matches!(result, Err(Error::ForeignKeyViolation(_, _, _))));
+
+    // Primary key updates are checked for duplicates and cascaded
+    let result = db.update_from(
+        "users",
+        "staging_addresses",
+        ("id".to_string(), "user_id".to_string()),
+        vec![
+            ("user_name".to_string(), "city".to_string()),
+            ("id".to_string(), "zip".to_string()),
+        ],
+    );
+    let outcome = result.unwrap();
+    assert_eq!(
+        outcome.affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 2
+        }
+    );
+    assert_eq!(outcome.unmatched, 1);
+    assert_eq!(
+        db.get_table("addresses").unwrap().columns[1].data,
+        vec![Value::Integer(100), Value::Integer(2), Value::Integer(100)]
+    );
+
+    db.insert_into_table("staging_addresses", row(&["7", "Bob", "100"]))
+        .unwrap();
+    let result = db.update_from(
+        "users",
+        "staging_addresses",
+        ("user_name".to_string(), "city".to_string()),
+        vec![("id".to_string(), "zip".to_string())],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::DuplicatePrimaryKey))
+    ));
+    assert_eq!(
+        db.get_table("users").unwrap().columns[0].data,
+        vec![Value::Integer(100), Value::Integer(2)]
+    );
+    assert!(matches!(
+        db.update_from("missing", "users", join_on, vec![]),
+        Err(Error::TableNotFound(_))
+    ));
+}
+
+#[test]
+fn test_case_insensitive_names() {
+    let path = std::env::temp_dir().join("rdms_wal_case_insensitive_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "Users",
+        vec![
+            Column::new("Id", ColumnDataType::Integer, None, true, None),
+            Column::new("Name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    assert!(db.get_table("users").is_none());
+
+    db.set_case_insensitive_names(true).unwrap();
+    db.insert_with_columns_into_table(
+        "Users",
+        vec!["name".to_string(), "ID".to_string()],
+        vec!["Alice".to_string(), "1".to_string()],
+    )
+    .unwrap();
+    let users = db.get_table("users").unwrap();
+    assert_eq!(users.name, "Users");
+    assert_eq!(users.columns[0].data, vec![Value::Integer(1)]);
+
+    // A table whose name only differs by case from an existing one is rejected
+    let result = db.create_table(
+        "USERS",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    );
+    assert!(matches!(result, Err(Error::TableAlreadyExists(_))));
+    drop(db);
+
+    // The setting is part of the log, so the replayed insert resolves the same way
+    let replayed = Database::replay_wal(path).unwrap();
+    let users = replayed.get_table("USERS").unwrap();
+    assert_eq!(
+        users.columns[1].data,
+        vec![Value::Text("Alice".to_string())]
+    );
+    let _ = std::fs::remove_file(path);
+
+    // Existing tables that only differ by case keep the setting from being enabled
+    let mut db = Database::new("test_db".to_string());
+    for name in ["t", "T"] {
+        db.create_table(
+            name,
+            vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+        )
+        .unwrap();
+    }
+    let result = db.set_case_insensitive_names(true);
+    assert!(matches!(result, Err(Error::AmbiguousTableName(name)) if name == "t"));
+    assert!(db.get_table("T").is_some());
+}
+
+#[test]
+fn test_copy_table() {
+    let path = std::env::temp_dir().join("rdms_wal_copy_table_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+    db.insert_into_table("users", vec!["1".to_string()])
+        .unwrap();
+    db.create_table(
+        "employees",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+            Column::new(
+                "manager_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("employees", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    for row in [["1", "1", "null"], ["2", "1", "1"]] {
+        db.insert_into_table("employees", row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    db.copy_table("employees", "employees_backup", true)
+        .unwrap();
+    let backup = db.get_table("employees_backup").unwrap();
+    let source = db.get_table("employees").unwrap();
+    for (column, source_column) in backup.columns.iter().zip(&source.columns) {
+        assert_eq!(column.data, source_column.data);
+    }
+    assert_eq!(
+        backup.columns[1].foreign_key,
+        Some(ForeignKeyInfo::new("users", "id"))
+    );
+    assert_eq!(
+        backup.columns[2].foreign_key,
+        Some(ForeignKeyInfo::new("employees_backup", "id"))
+    );
+    assert_eq!(
+        backup.referenced_as_foreign_key,
+        vec![("employees_backup".to_string(), "manager_id".to_string())]
+    );
+
+    // The foreign keys of the copy stay active, and cascades reach it
+    let result = db.insert_into_table(
+        "employees_backup",
+        vec!["3".to_string(), "9".to_string(), "null".to_string()],
+    );
+    assert!(matches!(result, Err(Error::ForeignKeyViolation(_, _, _))));
+    db.delete_with_nested_conditions_in_table(
+        "users",
+        NestedCondition::Condition("id".to_string(), "=".to_string(), "1".to_string()),
+    )
+    .unwrap();
+    assert_eq!(db.row_count_of_table("employees_backup").unwrap(), 0);
+
+    db.copy_table("users", "users_template", false).unwrap();
+    assert_eq!(db.row_count_of_table("users_template").unwrap(), 0);
+    assert!(matches!(
+        db.copy_table("users", "employees", true),
+        Err(Error::TableAlreadyExists(_))
+    ));
+    assert!(matches!(
+        db.copy_table("customers", "customers_copy", true),
+        Err(Error::TableNotFound(_))
+    ));
+
+    // A record whose foreign key is broken leaves no copy behind
+    db.insert_into_table("users", vec!["5".to_string()])
+        .unwrap();
+    db.create_table(
+        "orders",
+        vec![Column::new(
+            "user_id",
+            ColumnDataType::Integer,
+            None,
+            false,
+            ForeignKeyInfo::new("users", "id").into(),
+        )],
+    )
+    .unwrap();
+    db.insert_into_table_deferred("orders", vec!["8".to_string()])
+        .unwrap();
+    assert!(matches!(
+        db.copy_table("orders", "orders_copy", true),
+        Err(Error::BatchRecord(0, _))
+    ));
+    assert!(db.get_table("orders_copy").is_none());
+    assert_eq!(
+        db.get_table("users").unwrap().referenced_as_foreign_key,
+        vec![
+            ("employees".to_string(), "user_id".to_string()),
+            ("employees_backup".to_string(), "user_id".to_string()),
+            ("orders".to_string(), "user_id".to_string()),
+        ]
+    );
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    assert!(replayed.get_table("orders_copy").is_none());
+    assert_eq!(replayed.table_count(), 5);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_new_case_insensitive() {
+    let users_columns = || {
+        vec![
+            Column::new("Id", ColumnDataType::Integer, None, true, None),
+            Column::new("Age", ColumnDataType::Integer, None, false, None),
+        ]
+    };
+
+    // By default, names that only differ by case are different tables and columns
+    let mut db = Database::new("test_db".to_string());
+    db.create_table("Users", users_columns()).unwrap();
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("ID", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    assert_eq!(db.get_table("users").unwrap().columns[1].name, "ID");
+    assert!(db.get_table("USERS").is_none());
+    let result = db.create_table(
+        "orders",
+        vec![Column::new(
+            "user_id",
+            ColumnDataType::Integer,
+            None,
+            false,
+            Some(ForeignKeyInfo::new("USERS", "id")),
+        )],
+    );
+    assert!(matches!(result, Err(Error::ReferencedTableNotFound(_))));
+
+    let path = std::env::temp_dir().join("rdms_wal_new_case_insensitive_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new_case_insensitive("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table("Users", users_columns()).unwrap();
+    assert!(matches!(
+        db.create_table("USERS", users_columns()),
+        Err(Error::TableAlreadyExists(_))
+    ));
+    let result = db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("ID", ColumnDataType::Integer, None, false, None),
+        ],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::ColumnAlreadyExists(name))) if name == "ID"
+    ));
+
+    // Foreign keys are stored with the exact names they reference, so cascades find them
+    db.create_table(
+        "Orders",
+        vec![
+            Column::new("Id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "UserId",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("users", "ID")),
+            ),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        db.get_table("orders").unwrap().columns[1].foreign_key,
+        Some(ForeignKeyInfo::new("Users", "Id"))
+    );
+    db.insert_into_table("Users", vec!["1".to_string(), "30".to_string()])
+        .unwrap();
+    db.insert_into_table("Orders", vec!["10".to_string(), "1".to_string()])
+        .unwrap();
+    let affected = db
+        .delete_with_nested_conditions_in_table(
+            "Users",
+            NestedCondition::Condition("AGE".to_string(), ">".to_string(), "18".to_string()),
+        )
+        .unwrap();
+    assert_eq!(affected.total(), 2);
+    drop(db);
+
+    // The setting is logged with the database name, so the replay resolves names the same way
+    let replayed = Database::replay_wal(path).unwrap();
+    assert!(replayed.case_insensitive_names);
+    assert_eq!(replayed.get_table("orders").unwrap().name, "Orders");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_case_insensitive_writes() {
+    let path = std::env::temp_dir().join("rdms_wal_case_insensitive_writes_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new_case_insensitive("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "Users",
+        vec![
+            Column::new("Id", ColumnDataType::Integer, None, true, None),
+            Column::new("Age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "Orders",
+        vec![
+            Column::new("Id", ColumnDataType::Integer, None, true, None),
+            Column::new("UserId", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    // Every write resolves the table name first, and logs the resolved one
+    db.insert_into_table("users", vec!["1".to_string(), "30".to_string()])
+        .unwrap();
+    db.insert_many_into_table(
+        "USERS",
+        vec![
+            vec!["2".to_string(), "40".to_string()],
+            vec!["3".to_string(), "50".to_string()],
+        ],
+    )
+    .unwrap();
+    db.insert_into_table("orders", vec!["10".to_string(), "1".to_string()])
+        .unwrap();
+    db.insert_into_table("orders", vec!["11".to_string(), "3".to_string()])
+        .unwrap();
+    db.add_foreign_key("ORDERS", "userid", "users", "ID")
+        .unwrap();
+    assert_eq!(
+        db.get_table("Orders").unwrap().columns[1].foreign_key,
+        Some(ForeignKeyInfo::new("Users", "Id"))
+    );
+
+    let condition = |column: &str, value: &str| {
+        NestedCondition::Condition(column.to_string(), "=".to_string(), value.to_string())
+    };
+    let affected = db
+        .update_with_nested_conditions_in_table(
+            "users",
+            ("AGE".to_string(), "31".to_string()),
+            condition("id", "1"),
+        )
+        .unwrap();
+    assert_eq!(affected.rows, 1);
+    db.update_by_pk_in_table("uSeRs", "2", vec![("age".to_string(), "41".to_string())])
+        .unwrap();
+    db.update_column_in_table("orders", "USERID", "3").unwrap();
+    let affected = db
+        .delete_with_nested_conditions_in_table("users", condition("ID", "3"))
+        .unwrap();
+    assert_eq!(affected.total(), 3);
+    assert_eq!(db.row_count_of_table("ORDERS").unwrap(), 0);
+
+    assert!(matches!(
+        db.drop_table("users", false),
+        Err(Error::ReferencedByTables(table, _)) if table == "Users"
+    ));
+    db.drop_table("orders", false).unwrap();
+    assert!(!db.table_exists("Orders"));
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    assert_eq!(replayed.table_names(), vec!["Users"]);
+    assert_eq!(
+        replayed.select_from_table("users", vec![], None).unwrap(),
+        vec![
+            vec![Value::Integer(1), Value::Integer(31)],
+            vec![Value::Integer(2), Value::Integer(41)],
+        ]
+    );
+    let log = std::fs::read_to_string(path).unwrap();
+    assert!(!log.contains("\"users\"") && !log.contains("\"orders\""));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_execute_select() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, name, age) in [
+        ("1", "Alice", "30"),
+        ("2", "Bob", "20"),
+        ("3", "Charlie", "40"),
+    ] {
+        db.insert_into_table(
+            "users",
+            vec![id.to_string(), name.to_string(), age.to_string()],
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        db.execute_select("SELECT \"user name\", id FROM users WHERE age > 25 AND NOT id = 3")
+            .unwrap(),
+        vec![vec![Value::Text("Alice".to_string()), Value::Integer(1)]]
+    );
+    assert_eq!(
+        db.execute_select("select * from users where \"user name\" = 'Bob';")
+            .unwrap(),
+        vec![vec![
+            Value::Integer(2),
+            Value::Text("Bob".to_string()),
+            Value::Integer(20)
+        ]]
+    );
+    assert_eq!(db.execute_select("SELECT id FROM users").unwrap().len(), 3);
+
+    // Unknown tables and columns
+    assert!(matches!(
+        db.execute_select("SELECT * FROM orders"),
+        Err(Error::TableNotFound(table_name)) if table_name == "orders"
+    ));
+    assert!(matches!(
+        db.execute_select("SELECT id, height FROM users"),
+        Err(Error::TableError(table_errors::Error::NonExistingColumns(
+            _
+        )))
+    ));
+    assert!(matches!(
+        db.execute_select("SELECT id FROM users WHERE height > 1"),
+        Err(Error::TableError(table_errors::Error::NonExistingColumn(_)))
+    ));
+
+    // Syntax errors report the position in the statement
+    let position = |sql: &str| match db.execute_select(sql) {
+        Err(Error::InvalidStatementSyntax(position, _)) => position,
+        result => panic!("{:?} ran as {:?}", sql, result),
+    };
+    assert_eq!(position("DELETE FROM users"), 0);
+    assert_eq!(position("SELECT FROM users"), 7);
+    assert_eq!(position("SELECT id age FROM users"), 10);
+    assert_eq!(position("SELECT id, FROM users"), 11);
+    assert_eq!(position("SELECT * FROM"), 13);
+    assert_eq!(position("SELECT * FROM users age > 1"), 20);
+    assert_eq!(position("SELECT * FROM users WHERE age >"), 31);
+    assert_eq!(position("SELECT * FROM users WHERE age > 1 LIMIT 1"), 34);
+}
+
+#[test]
+fn test_append_table() {
+    let path = std::env::temp_dir().join("rdms_wal_append_table_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+    db.insert_many_into_table("users", vec![vec!["1".to_string()], vec!["2".to_string()]])
+        .unwrap();
+    for table_name in ["orders_january", "orders_february"] {
+        db.create_table(
+            table_name,
+            vec![
+                Column::new("order_id", ColumnDataType::Integer, None, true, None),
+                Column::new(
+                    "user_id",
+                    ColumnDataType::Integer,
+                    None,
+                    false,
+                    Some(ForeignKeyInfo::new("users", "id")),
+                ),
+            ],
+        )
+        .unwrap();
+    }
+    db.insert_into_table("orders_january", vec!["10".to_string(), "1".to_string()])
+        .unwrap();
+    db.insert_into_table("orders_february", vec!["11".to_string(), "2".to_string()])
+        .unwrap();
+
+    assert_eq!(
+        db.append_table("orders_january", "orders_february")
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        db.get_table("orders_january").unwrap().columns[0].data,
+        vec![Value::Integer(10), Value::Integer(11)]
+    );
+
+    // Appending again duplicates the primary key of the first appended row
+    assert!(matches!(
+        db.append_table("orders_january", "orders_february"),
+        Err(Error::BatchRecord(0, error))
+            if matches!(*error, Error::TableError(table_errors::Error::DuplicatePrimaryKey))
+    ));
+    assert!(matches!(
+        db.append_table("orders_january", "users"),
+        Err(Error::TableError(table_errors::Error::SchemaMismatch(_)))
+    ));
+    assert!(matches!(
+        db.append_table("orders_january", "orders_march"),
+        Err(Error::TableNotFound(table_name)) if table_name == "orders_march"
+    ));
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    assert_eq!(
+        replayed.get_table("orders_january").unwrap().columns[1].data,
+        vec![Value::Integer(1), Value::Integer(2)]
+    );
+    let _ = std::fs::remove_file(path);
+
+    // The foreign keys of the appended rows are checked against the referenced table
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+    db.insert_into_table("users", vec!["1".to_string()])
+        .unwrap();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("users", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "staged_orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_id", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.insert_many_into_table(
+        "staged_orders",
+        vec![
+            vec!["10".to_string(), "1".to_string()],
+            vec!["11".to_string(), "7".to_string()],
+        ],
+    )
+    .unwrap();
+    assert!(matches!(
+        db.append_table("orders", "staged_orders"),
+        Err(Error::BatchRecord(1, error)) if matches!(*error, Error::ForeignKeyViolation(..))
+    ));
+    assert_eq!(db.get_table("orders").unwrap().row_count(), 0);
+}
+
+#[test]
+fn test_create_table_like() {
+    let mut db = users_db();
+    db.create_table(
+        "employees",
+        vec![
+            Column::builder("id", ColumnDataType::Integer)
+                .primary_key()
+                .build(),
+            Column::builder("email", ColumnDataType::Text)
+                .not_null()
+                .unique()
+                .build(),
+            Column::builder("team", ColumnDataType::Text)
+                .default_value(Value::Text("core".to_string()))
+                .build(),
+            Column::builder("user_id", ColumnDataType::Integer)
+                .foreign_key(ForeignKeyInfo::new("users", "id"))
+                .build(),
+            Column::builder("manager_id", ColumnDataType::Integer)
+                .foreign_key(ForeignKeyInfo::new("employees", "id"))
+                .build(),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table(
+        "employees",
+        ["1", "a@example.com", "core", "1", "null"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect(),
+    )
+    .unwrap();
+
+    // Without foreign keys, only the columns and their other constraints are copied
+    db.create_table_like("staged_employees", "employees", false)
+        .unwrap();
+    let staged = db.get_table("staged_employees").unwrap();
+    assert_eq!(staged.row_count(), 0);
+    assert!(staged.referenced_as_foreign_key.is_empty());
+    let source = db.get_table("employees").unwrap();
+    for (column, source_column) in staged.columns.iter().zip(&source.columns) {
+        assert_eq!(column.name, source_column.name);
+        assert_eq!(column.data_type, source_column.data_type);
+        assert_eq!(column.is_primary_key, source_column.is_primary_key);
+        assert_eq!(column.not_null, source_column.not_null);
+        assert_eq!(column.unique, source_column.unique);
+        assert_eq!(column.default_value, source_column.default_value);
+        assert_eq!(column.foreign_key, None);
+    }
+
+    // The staging table takes batches and appends back into the source
+    db.insert_many_into_table(
+        "staged_employees",
+        vec![
+            vec!["2", "b@example.com", "ops", "2", "1"],
+            vec!["3", "c@example.com", "core", "1", "2"],
+        ]
+        .into_iter()
+        .map(|row| row.iter().map(|v| v.to_string()).collect())
+        .collect(),
+    )
+    .unwrap();
+    let result = db.insert_into_table(
+        "staged_employees",
+        ["4", "b@example.com", "ops", "2", "1"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect(),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::DuplicateValue(_, _)))
+    ));
+    assert_eq!(db.append_table("employees", "staged_employees").unwrap(), 2);
+    assert_eq!(db.row_count_of_table("employees").unwrap(), 3);
+
+    // With foreign keys, references are registered and self-references follow the new table
+    db.create_table_like("employees_copy", "employees", true)
+        .unwrap();
+    let copy = db.get_table("employees_copy").unwrap();
+    assert_eq!(
+        copy.columns[3].foreign_key,
+        Some(ForeignKeyInfo::new("users", "id"))
+    );
+    assert_eq!(
+        copy.columns[4].foreign_key,
+        Some(ForeignKeyInfo::new("employees_copy", "id"))
+    );
+    assert!(db
+        .get_table("users")
+        .unwrap()
+        .referenced_as_foreign_key
+        .contains(&("employees_copy".to_string(), "user_id".to_string())));
+    let result = db.insert_into_table(
+        "employees_copy",
+        ["1", "a@example.com", "core", "7", "null"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect(),
+    );
+    assert!(result.is_err());
+
+    assert!(matches!(
+        db.create_table_like("employees_copy", "employees", false),
+        Err(Error::TableAlreadyExists(_))
+    ));
+    assert!(matches!(
+        db.create_table_like("contractors", "freelancers", false),
+        Err(Error::TableNotFound(table_name)) if table_name == "freelancers"
+    ));
+}
+
+#[test]
+fn test_update_primary_key_cascade() {
+    let mut db = users_db();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("users", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+    db.insert_many_into_table(
+        "orders",
+        vec![
+            vec!["10".to_string(), "1".to_string()],
+            vec!["11".to_string(), "2".to_string()],
+            vec!["12".to_string(), "1".to_string()],
+        ],
+    )
+    .unwrap();
+    let user_ids = |db: &Database| db.get_table("orders").unwrap().columns[1].data.clone();
+
+    // A condition matching no row changes nothing and doesn't cascade
+    let affected_rows = db
+        .update_with_nested_conditions_in_table(
+            "users",
+            ("id".to_string(), "5".to_string()),
+            NestedCondition::parse("user_name = 'Nobody'").unwrap(),
+        )
+        .unwrap();
+    assert_eq!(affected_rows, AffectedRows::default());
+    assert_eq!(
+        user_ids(&db),
+        vec![Value::Integer(1), Value::Integer(2), Value::Integer(1)]
+    );
+
+    // Setting a key to the value it already has doesn't cascade either
+    let affected_rows = db
+        .update_with_nested_conditions_in_table(
+            "users",
+            ("id".to_string(), "2".to_string()),
+            NestedCondition::parse("user_name = 'Bob'").unwrap(),
+        )
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 0
+        }
+    );
+
+    // Only the rows referencing the changed key follow it
+    let affected_rows = db
+        .update_with_nested_conditions_in_table(
+            "users",
+            ("id".to_string(), "5".to_string()),
+            NestedCondition::parse("user_name = 'Alice'").unwrap(),
+        )
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 2
+        }
+    );
+    assert_eq!(
+        user_ids(&db),
+        vec![Value::Integer(5), Value::Integer(2), Value::Integer(5)]
+    );
+
+    // Several rows can't take the same key, and nothing changes then
+    let result = db.update_with_nested_conditions_in_table(
+        "users",
+        ("id".to_string(), "7".to_string()),
+        NestedCondition::parse("id > 0").unwrap(),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::DuplicatePrimaryKey))
+    ));
+    assert_eq!(
+        user_ids(&db),
+        vec![Value::Integer(5), Value::Integer(2), Value::Integer(5)]
+    );
+}
+
+#[test]
+fn test_add_column_to_table() {
+    let path = std::env::temp_dir().join("rdms_wal_add_column_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table("users", vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+    db.create_table(
+        "orders",
+        vec![Column::new(
+            "order_id",
+            ColumnDataType::Integer,
+            None,
+            true,
+            None,
+        )],
+    )
+    .unwrap();
+    db.insert_into_table("orders", vec!["10".to_string()])
+        .unwrap();
+
+    // The fill value of a foreign key column has to reference an existing row
+    let user_id = || {
+        Column::builder("user_id", ColumnDataType::Integer)
+            .foreign_key(ForeignKeyInfo::new("users", "id"))
+            .build()
+    };
+    assert!(matches!(
+        db.add_column_to_table("orders", user_id(), None),
+        Err(Error::NullForeignKey(_))
+    ));
+    assert!(matches!(
+        db.add_column_to_table("orders", user_id(), Some("2".to_string())),
+        Err(Error::ForeignKeyViolation(..))
+    ));
+    let result = db.add_column_to_table(
+        "orders",
+        Column::builder("shop_id", ColumnDataType::Integer)
+            .foreign_key(ForeignKeyInfo::new("shops", "id"))
+            .build(),
+        None,
+    );
+    assert!(matches!(result, Err(Error::ReferencedTableNotFound(_))));
+    db.add_column_to_table("orders", user_id(), Some("1".to_string()))
+        .unwrap();
+    db.add_column_to_table(
+        "users",
+        Column::new("age", ColumnDataType::Integer, None, false, None),
+        Some("30".to_string()),
+    )
+    .unwrap();
+
+    // The new foreign key is registered, so deletes cascade through it
+    assert_eq!(
+        db.get_table("users").unwrap().referenced_as_foreign_key,
+        vec![("orders".to_string(), "user_id".to_string())]
+    );
+    assert!(matches!(
+        db.add_column_to_table("missing", user_id(), None),
+        Err(Error::TableNotFound(_))
+    ));
+    drop(db);
+
+    let mut replayed = Database::replay_wal(path).unwrap();
+    assert_eq!(
+        replayed.get_table("orders").unwrap().columns[1].data,
+        vec![Value::Integer(1)]
+    );
+    assert_eq!(
+        replayed.get_table("users").unwrap().columns[2].data,
+        vec![Value::Integer(30)]
+    );
+    let affected_rows = replayed
+        .delete_with_nested_conditions_in_table("users", NestedCondition::parse("id = 1").unwrap())
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 1
+        }
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_update_from_cascades_every_changed_key() {
+    let mut db = users_db();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("users", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+    db.insert_many_into_table(
+        "orders",
+        vec![
+            vec!["10".to_string(), "1".to_string()],
+            vec!["11".to_string(), "2".to_string()],
+            vec!["12".to_string(), "1".to_string()],
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "new_ids",
+        vec![
+            Column::new("user_name", ColumnDataType::Text, None, true, None),
+            Column::new("id", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    // Alice moves to Bob's old key while Bob moves on, so each order has to move exactly once
+    db.insert_many_into_table(
+        "new_ids",
+        vec![
+            vec!["Alice".to_string(), "2".to_string()],
+            vec!["Bob".to_string(), "3".to_string()],
+        ],
+    )
+    .unwrap();
+    let outcome = db
+        .update_from(
+            "users",
+            "new_ids",
+            ("user_name".to_string(), "user_name".to_string()),
+            vec![("id".to_string(), "id".to_string())],
+        )
+        .unwrap();
+    assert_eq!(
+        outcome.affected_rows,
+        AffectedRows {
+            rows: 2,
+            cascaded: 3
+        }
+    );
+    assert_eq!(
+        db.get_table("users").unwrap().columns[0].data,
+        vec![Value::Integer(2), Value::Integer(3)]
+    );
+    assert_eq!(
+        db.get_table("orders").unwrap().columns[1].data,
+        vec![Value::Integer(2), Value::Integer(3), Value::Integer(2)]
+    );
+    assert!(db.check_referential_integrity().is_empty());
+}
+
+#[test]
+fn test_drop_column_from_table() {
+    let path = std::env::temp_dir().join("rdms_wal_drop_column_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = users_db();
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("users", "id")),
+            ),
+            Column::new("note", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table(
+        "orders",
+        vec!["10".to_string(), "1".to_string(), "gift".to_string()],
+    )
+    .unwrap();
+
+    // A referenced primary key names the foreign keys that block the drop
+    match db.drop_column_from_table("users", "id") {
+        Err(Error::ColumnReferencedBy(table_name, column_name, referencing_columns)) => {
+            assert_eq!(table_name, "users");
+            assert_eq!(column_name, "id");
+            assert_eq!(referencing_columns, vec!["orders.user_id".to_string()]);
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+    assert!(matches!(
+        db.drop_column_from_table("orders", "order_id"),
+        Err(Error::TableError(
+            table_errors::Error::CannotDropPrimaryKey(_)
+        ))
+    ));
+    assert!(matches!(
+        db.drop_column_from_table("orders", "missing"),
+        Err(Error::TableError(table_errors::Error::NonExistingColumn(_)))
+    ));
+
+    // Dropping the foreign key column stops users from tracking it
+    db.drop_column_from_table("orders", "note").unwrap();
+    db.drop_column_from_table("orders", "user_id").unwrap();
+    assert!(db
+        .get_table("users")
+        .unwrap()
+        .referenced_as_foreign_key
+        .is_empty());
+    db.delete_with_nested_conditions_in_table("users", NestedCondition::parse("id = 1").unwrap())
+        .unwrap();
+    assert_eq!(db.get_table("orders").unwrap().row_count(), 1);
+    drop(db);
+
+    // The log starts after the users were created, so replay it onto a fresh copy of them
+    let mut replayed = users_db();
+    replayed.replay_wal_onto(path).unwrap();
+    let orders = replayed.get_table("orders").unwrap();
+    assert_eq!(orders.columns.len(), 1);
+    assert_eq!(replayed.get_table("users").unwrap().row_count(), 1);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_rename_column_in_table() {
+    let path = std::env::temp_dir().join("rdms_wal_rename_column_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "addresses",
+        vec![
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                true,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+            Column::new("address", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table("users", vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+    db.insert_into_table(
+        "addresses",
+        vec!["1".to_string(), "123 Main St.".to_string()],
+    )
+    .unwrap();
+
+    // The foreign key of addresses follows the renamed primary key of users
+    db.rename_column_in_table("users", "id", "user_key")
+        .unwrap();
+    let addresses = db.get_table("addresses").unwrap();
+    assert_eq!(
+        addresses.columns[0].foreign_key,
+        Some(ForeignKeyInfo::new("users", "user_key"))
+    );
+    assert_eq!(
+        addresses.primary_key().unwrap().foreign_key,
+        Some(ForeignKeyInfo::new("users", "user_key"))
+    );
+
+    // users keeps tracking the renamed foreign key column of addresses
+    db.rename_column_in_table("addresses", "user_id", "owner_id")
+        .unwrap();
+    assert_eq!(
+        db.get_table("users").unwrap().referenced_as_foreign_key,
+        vec![("addresses".to_string(), "owner_id".to_string())]
+    );
+
+    assert!(matches!(
+        db.rename_column_in_table("users", "user_name", "user_key"),
+        Err(Error::TableError(table_errors::Error::ColumnAlreadyExists(
+            _
+        )))
+    ));
+    assert!(matches!(
+        db.rename_column_in_table("missing", "id", "key"),
+        Err(Error::TableNotFound(_))
+    ));
+
+    // Inserts are checked and updates cascade through the renamed columns
+    let result = db.insert_into_table(
+        "addresses",
+        vec!["2".to_string(), "456 Elm St.".to_string()],
+    );
+    assert!(matches!(result, Err(Error::ForeignKeyViolation(..))));
+    db.update_by_pk_in_table(
+        "users",
+        "1",
+        vec![("user_key".to_string(), "5".to_string())],
+    )
+    .unwrap();
+    assert_eq!(
+        db.get_table("addresses").unwrap().columns[0].data,
+        vec![Value::Integer(5)]
+    );
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    let addresses = replayed.get_table("addresses").unwrap();
+    assert_eq!(addresses.columns[0].name, "owner_id");
+    assert_eq!(addresses.columns[0].data, vec![Value::Integer(5)]);
+    assert!(replayed.check_referential_integrity().is_empty());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_rename_table() {
+    let path = std::env::temp_dir().join("rdms_wal_rename_table_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "addresses",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    for (id, name) in [("1", "Alice"), ("2", "Bob")] {
+        db.insert_into_table("users", vec![id.to_string(), name.to_string()])
+            .unwrap();
+    }
+    for (id, user_id) in [("1", "1"), ("2", "1"), ("3", "2")] {
+        db.insert_into_table("addresses", vec![id.to_string(), user_id.to_string()])
+            .unwrap();
+    }
+
+    db.rename_table("users", "customers").unwrap();
+    assert!(db.get_table("users").is_none());
+    assert_eq!(db.get_table("customers").unwrap().name, "customers");
+    assert_eq!(
+        db.get_table("addresses").unwrap().columns[1].foreign_key,
+        Some(ForeignKeyInfo::new("customers", "id"))
+    );
+
+    db.rename_table("addresses", "shipping_addresses").unwrap();
+    assert_eq!(
+        db.get_table("customers").unwrap().referenced_as_foreign_key,
+        vec![("shipping_addresses".to_string(), "user_id".to_string())]
+    );
+
+    assert!(matches!(
+        db.rename_table("customers", "shipping_addresses"),
+        Err(Error::TableAlreadyExists(_))
+    ));
+    assert!(matches!(
+        db.rename_table("users", "people"),
+        Err(Error::TableNotFound(_))
+    ));
+
+    // The cascade finds the renamed tables
+    let affected_rows = db
+        .delete_with_nested_conditions_in_table(
+            "customers",
+            NestedCondition::Condition("id".to_string(), "=".to_string(), "1".to_string()),
+        )
+        .unwrap();
+    assert_eq!(
+        affected_rows,
+        AffectedRows {
+            rows: 1,
+            cascaded: 2
+        }
+    );
+    assert_eq!(
+        db.get_table("shipping_addresses").unwrap().columns[0].data,
+        vec![Value::Integer(3)]
+    );
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    assert!(replayed.get_table("users").is_none());
+    assert_eq!(
+        replayed.get_table("shipping_addresses").unwrap().columns[1].data,
+        vec![Value::Integer(2)]
+    );
+    assert!(replayed.check_referential_integrity().is_empty());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_drop_table() {
+    let path = std::env::temp_dir().join("rdms_wal_drop_table_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+    db.insert_into_table("users", vec!["1".to_string()])
+        .unwrap();
+    db.create_table(
+        "teams",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+    db.insert_into_table("teams", vec!["1".to_string()])
+        .unwrap();
+    db.create_table(
+        "members",
+        vec![
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                true,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+            Column::new(
+                "team_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("teams", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table("members", vec!["1".to_string(), "1".to_string()])
+        .unwrap();
+
+    let result = db.drop_table("users", false);
+    assert!(matches!(
+        result,
+        Err(Error::ReferencedByTables(table_name, referencing_tables))
+            if table_name == "users" && referencing_tables == vec!["members".to_string()]
+    ));
+    assert!(db.get_table("users").is_some());
+
+    // Cascading keeps the referencing table and its values, without the foreign key
+    db.drop_table("users", true).unwrap();
+    assert!(db.get_table("users").is_none());
+    let members = db.get_table("members").unwrap();
+    assert_eq!(members.columns[0].foreign_key, None);
+    assert_eq!(members.primary_key().unwrap().foreign_key, None);
+    assert_eq!(members.columns[0].data, vec![Value::Integer(1)]);
+    db.insert_into_table("members", vec!["7".to_string(), "1".to_string()])
+        .unwrap();
+
+    // A table without references can be dropped, and unregisters its own foreign keys
+    db.drop_table("members", false).unwrap();
+    assert!(db
+        .get_table("teams")
+        .unwrap()
+        .referenced_as_foreign_key
+        .is_empty());
+    db.drop_table("teams", false).unwrap();
+    assert!(matches!(
+        db.drop_table("teams", false),
+        Err(Error::TableNotFound(_))
+    ));
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    assert!(replayed.tables.is_empty());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_tables_in_dependency_order() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "c",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+    for (table_name, referenced) in [("b", "c"), ("a", "b")] {
+        db.create_table(
+            table_name,
+            vec![
+                Column::new("id", ColumnDataType::Integer, None, true, None),
+                Column::new(
+                    "parent_id",
+                    ColumnDataType::Integer,
+                    None,
+                    false,
+                    ForeignKeyInfo::new(referenced, "id").into(),
+                ),
+            ],
+        )
+        .unwrap();
+    }
+
+    // a references b, which references c
+    let names: Vec<&str> = db
+        .tables_in_dependency_order()
+        .unwrap()
+        .iter()
+        .map(|table| table.name())
+        .collect();
+    assert_eq!(names, vec!["c", "b", "a"]);
+
+    // A cycle can only come from changing the tables directly
+    db.tables.get_mut("c").unwrap().columns.push(Column::new(
+        "a_id",
+        ColumnDataType::Integer,
+        None,
+        false,
+        ForeignKeyInfo::new("a", "id").into(),
+    ));
+    match db.tables_in_dependency_order() {
+        Err(Error::CircularForeignKeyReference(cycle)) => {
+            assert_eq!(cycle, vec!["a", "b", "c", "a"]);
+        }
+        other => panic!("Expected a circular reference error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_drop_primary_key_on_table() {
+    let path = std::env::temp_dir().join("rdms_wal_drop_primary_key_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "addresses",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table("users", vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+    db.insert_into_table("addresses", vec!["1".to_string(), "1".to_string()])
+        .unwrap();
+
+    // The foreign key of addresses would dangle
+    let result = db.drop_primary_key_on_table("users");
+    assert!(matches!(
+        result,
+        Err(Error::ColumnReferencedBy(table_name, column_name, referencing_columns))
+            if table_name == "users"
+                && column_name == "id"
+                && referencing_columns == vec!["addresses.user_id"]
+    ));
+    assert!(db.get_table("users").unwrap().primary_key().is_some());
+
+    db.drop_primary_key_on_table("addresses").unwrap();
+    assert!(db.get_table("addresses").unwrap().primary_key().is_none());
+    db.update_column_in_table("addresses", "id", "7").unwrap();
+    assert!(matches!(
+        db.drop_primary_key_on_table("addresses"),
+        Err(Error::TableError(table_errors::Error::NoPrimaryKey))
+    ));
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    let addresses = replayed.get_table("addresses").unwrap();
+    assert!(addresses.primary_key().is_none());
+    assert_eq!(addresses.columns[0].data, vec![Value::Integer(7)]);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_add_foreign_key() {
+    let path = std::env::temp_dir().join("rdms_wal_add_foreign_key_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_id", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, name) in [("1", "Alice"), ("2", "Bob")] {
+        db.insert_into_table("users", vec![id.to_string(), name.to_string()])
+            .unwrap();
+    }
+    for (id, user_id) in [("1", "1"), ("2", "3"), ("3", "2"), ("4", "5")] {
+        db.insert_into_table("orders", vec![id.to_string(), user_id.to_string()])
+            .unwrap();
+    }
+
+    // The same validations as create_table
+    assert!(matches!(
+        db.add_foreign_key("orders", "user_id", "customers", "id"),
+        Err(Error::ReferencedTableNotFound(_))
+    ));
+    assert!(matches!(
+        db.add_foreign_key("orders", "user_id", "users", "key"),
+        Err(Error::ReferencedColumnNotFound(..))
+    ));
+    assert!(matches!(
+        db.add_foreign_key("orders", "user_id", "users", "user_name"),
+        Err(Error::ReferencedColumnNotPrimaryKey(..))
+    ));
+
+    // Every row whose value is missing from users is reported
+    let result = db.add_foreign_key("orders", "user_id", "users", "id");
+    assert!(matches!(
+        result,
+        Err(Error::ForeignKeyViolations(column_name, reference_table, rows))
+            if column_name == "user_id" && reference_table == "users" && rows == vec![1, 3]
+    ));
+    assert!(db.get_table("orders").unwrap().columns[1]
+        .foreign_key
+        .is_none());
+    assert!(db
+        .get_table("users")
+        .unwrap()
+        .referenced_as_foreign_key
+        .is_empty());
+
+    for id in ["2", "4"] {
+        db.delete_with_nested_conditions_in_table(
+            "orders",
+            NestedCondition::Condition("id".to_string(), "=".to_string(), id.to_string()),
+        )
+        .unwrap();
+    }
+    db.add_foreign_key("orders", "user_id", "users", "id")
+        .unwrap();
+    assert_eq!(
+        db.get_table("orders").unwrap().columns[1].foreign_key,
+        Some(ForeignKeyInfo::new("users", "id"))
+    );
+    assert!(matches!(
+        db.add_foreign_key("orders", "user_id", "users", "id"),
+        Err(Error::ForeignKeyAlreadyExists(..))
+    ));
+
+    // The new foreign key is enforced and cascades
+    let result = db.insert_into_table("orders", vec!["5".to_string(), "9".to_string()]);
+    assert!(matches!(result, Err(Error::ForeignKeyViolation(..))));
+    db.delete_with_nested_conditions_in_table(
+        "users",
+        NestedCondition::Condition("id".to_string(), "=".to_string(), "1".to_string()),
+    )
+    .unwrap();
+    assert_eq!(
+        db.get_table("orders").unwrap().columns[0].data,
+        vec![Value::Integer(3)]
+    );
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    assert_eq!(
+        replayed
+            .get_table("users")
+            .unwrap()
+            .referenced_as_foreign_key,
+        vec![("orders".to_string(), "user_id".to_string())]
+    );
+    assert_eq!(
+        replayed.get_table("orders").unwrap().columns[0].data,
+        vec![Value::Integer(3)]
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_drop_foreign_key() {
+    let path = std::env::temp_dir().join("rdms_wal_drop_foreign_key_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    for (id, name) in [("1", "Alice"), ("2", "Bob")] {
+        db.insert_into_table("users", vec![id.to_string(), name.to_string()])
+            .unwrap();
+    }
+    for (id, user_id) in [("1", "1"), ("2", "2")] {
+        db.insert_into_table("orders", vec![id.to_string(), user_id.to_string()])
+            .unwrap();
+    }
+
+    db.drop_foreign_key("orders", "user_id").unwrap();
+    assert!(db.get_table("orders").unwrap().columns[1]
+        .foreign_key
+        .is_none());
+    assert!(db
+        .get_table("users")
+        .unwrap()
+        .referenced_as_foreign_key
+        .is_empty());
+
+    // Deleting a formerly referenced user no longer cascades
+    let affected_rows = db
+        .delete_with_nested_conditions_in_table(
+            "users",
+            NestedCondition::Condition("id".to_string(), "=".to_string(), "1".to_string()),
+        )
+        .unwrap();
+    assert_eq!(affected_rows.cascaded, 0);
+    assert_eq!(db.get_table("orders").unwrap().row_count(), 2);
+    db.insert_into_table("orders", vec!["3".to_string(), "9".to_string()])
+        .unwrap();
+
+    assert!(matches!(
+        db.drop_foreign_key("orders", "user_id"),
+        Err(Error::ForeignKeyNotFound(table_name, column_name))
+            if table_name == "orders" && column_name == "user_id"
+    ));
+    assert!(matches!(
+        db.drop_foreign_key("orders", "missing"),
+        Err(Error::TableError(table_errors::Error::NonExistingColumn(_)))
+    ));
+    drop(db);
+
+    let replayed = Database::replay_wal(path).unwrap();
+    assert!(replayed.get_table("orders").unwrap().columns[1]
+        .foreign_key
+        .is_none());
+    assert_eq!(replayed.get_table("orders").unwrap().row_count(), 3);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_foreign_key_type_mismatch() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+    )
+    .unwrap();
+
+    let result = db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Text,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+        ],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::ForeignKeyTypeMismatch(column_name, ColumnDataType::Integer, ColumnDataType::Text))
+            if column_name == "user_id"
+    ));
+    assert!(db.get_table("orders").is_none());
+    assert!(db
+        .get_table("users")
+        .unwrap()
+        .referenced_as_foreign_key
+        .is_empty());
+
+    // Self-references are checked against the table being created
+    let result = db.create_table(
+        "employees",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "manager_id",
+                ColumnDataType::Float,
+                None,
+                false,
+                ForeignKeyInfo::new("employees", "id").into(),
+            ),
+        ],
+    );
+    assert!(matches!(result, Err(Error::ForeignKeyTypeMismatch(..))));
+
+    // And so are foreign keys added later
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_id", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    assert!(matches!(
+        db.add_foreign_key("orders", "user_id", "users", "id"),
+        Err(Error::ForeignKeyTypeMismatch(..))
+    ));
+}
+
+#[test]
+fn test_add_unique_constraint_to_table() {
+    let path = std::env::temp_dir().join("rdms_wal_add_unique_constraint_test.log");
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = Database::new("test_db".to_string());
+    db.enable_wal(path).unwrap();
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("email", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, email) in [("1", "a@x.org"), ("2", "a@x.org")] {
+        db.insert_into_table("users", vec![id.to_string(), email.to_string()])
+            .unwrap();
+    }
+
+    assert!(matches!(
+        db.add_unique_constraint_to_table("users", "email"),
+        Err(Error::TableError(table_errors::Error::DuplicateValues(..)))
+    ));
+    db.update_by_pk_in_table(
+        "users",
+        "2",
+        vec![("email".to_string(), "b@x.org".to_string())],
+    )
+    .unwrap();
+    db.add_unique_constraint_to_table("users", "email").unwrap();
+    assert!(matches!(
+        db.add_unique_constraint_to_table("missing", "email"),
+        Err(Error::TableNotFound(_))
+    ));
+    drop(db);
+
+    let mut replayed = Database::replay_wal(path).unwrap();
+    assert!(replayed.get_table("users").unwrap().columns[1].unique);
+    let result = replayed.insert_into_table("users", vec!["3".to_string(), "b@x.org".to_string()]);
+    assert!(matches!(
+        result,
+        Err(Error::TableError(table_errors::Error::DuplicateValue(..)))
+    ));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_delete_returning_from_table() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    for (id, name) in [("1", "Alice"), ("2", "Bob"), ("3", "Carol")] {
+        db.insert_into_table("users", vec![id.to_string(), name.to_string()])
+            .unwrap();
+    }
+    for (id, user_id) in [("10", "1"), ("11", "2"), ("12", "1")] {
+        db.insert_into_table("orders", vec![id.to_string(), user_id.to_string()])
+            .unwrap();
+    }
+
+    let deleted = db
+        .delete_returning_from_table(
+            "users",
+            NestedCondition::parse("id <= 2 AND user_name != 'Bob' OR id = 3").unwrap(),
+        )
+        .unwrap();
+    assert_eq!(
+        deleted,
+        vec![
+            (
+                "users".to_string(),
+                vec![
+                    vec![Value::Integer(1), Value::Text("Alice".to_string())],
+                    vec![Value::Integer(3), Value::Text("Carol".to_string())],
+                ]
+            ),
+            (
+                "orders".to_string(),
+                vec![
+                    vec![Value::Integer(10), Value::Integer(1)],
+                    vec![Value::Integer(12), Value::Integer(1)],
+                ]
+            ),
+        ]
+    );
+    assert_eq!(db.get_table("users").unwrap().row_count(), 1);
+    assert_eq!(
+        db.get_table("orders").unwrap().columns[0].data,
+        vec![Value::Integer(11)]
+    );
+}
+
+#[test]
+fn test_catalog() {
+    let mut db = users_db();
+    db.create_table(
+        "audit_log",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("action", ColumnDataType::Text, None, false, None),
+            Column::new("at", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(db.table_count(), 2);
+    assert_eq!(db.table_names(), vec!["audit_log", "users"]);
+    assert_eq!(
+        db.tables()
+            .map(|table| table.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["audit_log", "users"]
+    );
+    assert!(db.table_exists("users"));
+    assert!(!db.table_exists("Users"));
+    db.set_case_insensitive_names(true).unwrap();
+    assert!(db.table_exists("Users"));
+
+    assert_eq!(
+        db.render_description(),
+        "Database: test_db\n\
+         \n\
+         table     rows columns\n\
+         audit_log    0       3\n\
+         users        2       2\n"
+    );
+}
+
+#[test]
+fn test_query() {
+    let mut db = users_db();
+    db.insert_into_table("users", vec!["3".to_string(), "Carol".to_string()])
+        .unwrap();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_id", ColumnDataType::Integer, None, false, None),
+            Column::new("total", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+    for row in [
+        ["10", "1", "25.0"],
+        ["11", "2", "5.0"],
+        ["12", "1", "40.0"],
+        ["13", "null", "99.0"],
+    ] {
+        db.insert_into_table("orders", row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+    let condition = |column_name: &str, operator: &str, value: &str| {
+        NestedCondition::Condition(
+            column_name.to_string(),
+            operator.to_string(),
+            value.to_string(),
+        )
+    };
+    let on = || ("id".to_string(), "user_id".to_string());
+
+    // Filtering on both sides, with the shared column name qualified
+    let result = db
+        .query(
+            "users",
+            "orders",
+            on(),
+            Some(NestedCondition::And(
+                Box::new(condition("total", ">", "10")),
+                Box::new(NestedCondition::Not(Box::new(condition(
+                    "orders.id",
+                    "=",
+                    "12",
+                )))),
+            )),
+            vec![
+                "user_name".to_string(),
+                "orders.id".to_string(),
+                "total".to_string(),
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        result,
+        QueryResult {
+            columns: vec![
+                "user_name".to_string(),
+                "orders.id".to_string(),
+                "total".to_string()
+            ],
+            rows: vec![vec![
+                Value::Text("Alice".to_string()),
+                Value::Integer(10),
+                Value::Float(25.0),
+            ]],
+        }
+    );
+
+    // Without a projection every column is returned, and unmatched or NULL keys are skipped
+    let result = db.query("users", "orders", on(), None, vec![]).unwrap();
+    assert_eq!(
+        result.columns,
+        vec!["users.id", "user_name", "orders.id", "user_id", "total"]
+    );
+    assert_eq!(result.rows.len(), 3);
+    assert_eq!(
+        result.rows[2],
+        vec![
+            Value::Integer(2),
+            Value::Text("Bob".to_string()),
+            Value::Integer(11),
+            Value::Integer(2),
+            Value::Float(5.0),
+        ]
+    );
+
+    assert!(matches!(
+        db.query("users", "orders", on(), None, vec!["id".to_string()]),
+        Err(Error::AmbiguousJoinColumn(column_name, _, _)) if column_name == "id"
+    ));
+    assert!(matches!(
+        db.query(
+            "users",
+            "orders",
+            on(),
+            Some(condition("total", ">", "high")),
+            vec![]
+        ),
+        Err(Error::TableError(
+            table_errors::Error::InvalidConditionValue(_, _)
+        ))
+    ));
+    assert!(matches!(
+        db.query("users", "missing", on(), None, vec![]),
+        Err(Error::TableNotFound(_))
+    ));
+}
+
+#[test]
+fn test_query_self_join() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "employees",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new(
+                "manager_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("employees", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+    for row in [
+        ["1", "Alice", "null"],
+        ["2", "Bob", "1"],
+        ["3", "Carol", "1"],
+        ["4", "Dave", "2"],
+    ] {
+        db.insert_into_table("employees", row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+    let on = || ("manager_id".to_string(), "id".to_string());
+
+    // Every employee with their manager, the sides named left and right
+    let result = db
+        .query(
+            "employees",
+            "employees",
+            on(),
+            Some(NestedCondition::Condition(
+                "right.name".to_string(),
+                "=".to_string(),
+                "Alice".to_string(),
+            )),
+            vec!["left.name".to_string(), "right.name".to_string()],
+        )
+        .unwrap();
+    assert_eq!(
+        result.rows,
+        vec![
+            vec![
+                Value::Text("Bob".to_string()),
+                Value::Text("Alice".to_string())
+            ],
+            vec![
+                Value::Text("Carol".to_string()),
+                Value::Text("Alice".to_string())
+            ],
+        ]
+    );
+
+    let result = db
+        .query("employees", "employees", on(), None, vec![])
+        .unwrap();
+    assert_eq!(
+        result.columns,
+        vec![
+            "left.id",
+            "left.name",
+            "left.manager_id",
+            "right.id",
+            "right.name",
+            "right.manager_id",
+        ]
+    );
+    assert_eq!(result.rows.len(), 3);
+
+    let result = db.query(
+        "employees",
+        "employees",
+        on(),
+        None,
+        vec!["name".to_string()],
+    );
+    match result {
+        Err(error @ Error::AmbiguousJoinColumn(..)) => assert_eq!(
+            error.to_string(),
+            "Column 'name' is in both 'left' and 'right', qualify it as 'left.name' or 'right.name'"
+        ),
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn test_snapshot() {
+    let mut db = users_db();
+    let snapshot = db.snapshot();
+    let original_rows = vec![
+        vec![Value::Integer(1), Value::Text("Alice".to_string())],
+        vec![Value::Integer(2), Value::Text("Bob".to_string())],
+    ];
+
+    db.insert_into_table("users", vec!["3".to_string(), "Carol".to_string()])
+        .unwrap();
+    db.update_column_in_table("users", "user_name", "Bobby")
+        .unwrap();
+    let condition = NestedCondition::Condition(
+        "user_name".to_string(),
+        "=".to_string(),
+        "Bobby".to_string(),
+    );
+    assert_eq!(
+        db.select_from_table("users", vec![], Some(condition.clone()))
+            .unwrap()
+            .len(),
+        3
+    );
+    assert!(snapshot
+        .select_from_table("users", vec![], Some(condition))
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        snapshot.select_from_table("users", vec![], None).unwrap(),
+        original_rows
+    );
+
+    db.truncate_table("users", false).unwrap();
+    assert_eq!(db.get_table("users").unwrap().row_count(), 0);
+    assert_eq!(
+        snapshot.select_from_table("users", vec![], None).unwrap(),
+        original_rows
+    );
+}
+
+#[test]
+fn test_validate() {
+    let mut db = users_db();
+    db.create_table(
+        "orders",
+        vec![
+            Column::new("order_id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                Some(ForeignKeyInfo::new("users", "id")),
+            ),
+        ],
+    )
+    .unwrap();
+    db.insert_into_table("orders", vec!["1".to_string(), "1".to_string()])
+        .unwrap();
+    assert!(db.is_valid());
+
+    // Plant an orphan and a stale back-reference by editing the tables directly
+    db.get_table_mut("orders")
+        .unwrap()
+        .insert(vec!["2".to_string(), "42".to_string()])
+        .unwrap();
+    db.get_table_mut("users")
+        .unwrap()
+        .referenced_as_foreign_key
+        .push(("orders".to_string(), "order_id".to_string()));
+
+    assert!(!db.is_valid());
+    assert_eq!(
+        db.validate(),
+        vec![
+            IntegrityViolation::MissingReference {
+                table: "orders".to_string(),
+                column: "user_id".to_string(),
+                row_idx: 1,
+                value: Value::Integer(42),
+                reference_table: "users".to_string(),
+            },
+            IntegrityViolation::UnmatchedReferencedBy {
+                table: "users".to_string(),
+                referencing_table: "orders".to_string(),
+                referencing_column: "order_id".to_string(),
+            },
+        ]
+    );
+
+    // A foreign key left pointing to a dropped table
+    db.get_table_mut("orders").unwrap().columns[1]
+        .data
+        .truncate(1);
+    db.get_table_mut("orders").unwrap().columns[0]
+        .data
+        .truncate(1);
+    db.tables.remove("users");
+    assert_eq!(
+        db.validate(),
+        vec![IntegrityViolation::MissingReferencedColumn {
+            table: "orders".to_string(),
+            column: "user_id".to_string(),
+            reference_table: "users".to_string(),
+            reference_column: "id".to_string(),
+        }]
+    );
+    // The same scan reports foreign key violations as errors
+    assert!(matches!(
+        &db.check_referential_integrity()[..],
+        [Error::ReferencedColumnNotFound(table, column)] if table == "users" && column == "id"
+    ));
+}
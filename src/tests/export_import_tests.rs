@@ -1,5 +1,5 @@
-use crate::column::{ColumnDataType, Value};
-use crate::table::{table_errors::Error, Table};
+use crate::column::{Column, ColumnDataType, Value};
+use crate::table::{table_errors::Error, OutputFormat, Table};
 
 #[test]
 fn test_import_table() {
@@ -81,3 +81,256 @@ fn test_import_table() {
     let result = Table::import_table("test_files/data/test_data.txt", "pdf");
     assert!(matches!(result, Err(Error::InvalidFormat(_))));
 }
+
+#[test]
+fn test_export_import_with_delimiter() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    table
+        .insert(vec![
+            "1".to_string(),
+            "Alice, Jr.".to_string(),
+            "85.5".to_string(),
+        ])
+        .unwrap();
+    table
+        .insert(vec!["2".to_string(), "Bob".to_string(), "null".to_string()])
+        .unwrap();
+
+    let file_name = std::env::temp_dir().join("rdms_test_delimiter.tsv");
+    let file_name = file_name.to_str().unwrap();
+
+    // Round-trip through a tab-separated file
+    table.export_table_with_delimiter(file_name, '\t').unwrap();
+    let imported = Table::import_table_with_delimiter(file_name, '\t').unwrap();
+
+    assert_eq!(imported.columns.len(), table.columns.len());
+    for (original_column, imported_column) in table.columns.iter().zip(imported.columns.iter()) {
+        assert_eq!(original_column.name, imported_column.name);
+        assert_eq!(original_column.data_type, imported_column.data_type);
+        assert_eq!(
+            original_column.is_primary_key,
+            imported_column.is_primary_key
+        );
+        assert_eq!(original_column.data, imported_column.data);
+    }
+
+    // The default comma import can't split the tab-separated file
+    let result = Table::import_table(file_name, "csv");
+    assert!(matches!(result, Err(Error::InvalidFormat(_))));
+
+    std::fs::remove_file(file_name).unwrap();
+}
+
+#[test]
+fn test_write_to() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    table.write_to(&mut buffer, OutputFormat::Csv).unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "id,name\nInteger,Text\nprim_key,nt_prim_key\n1,Alice\n"
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    table.write_to(&mut buffer, OutputFormat::Grid).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), format!("{}\n", table));
+
+    // The TXT format written to a buffer matches the exported file
+    let path = std::env::temp_dir().join("rdms_write_to_test.txt");
+    let path = path.to_str().unwrap();
+    table.export_table(path, "txt").unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+    table.write_to(&mut buffer, OutputFormat::Txt).unwrap();
+    assert_eq!(buffer, std::fs::read(path).unwrap());
+    std::fs::remove_file(path).unwrap();
+
+    // Unknown export formats are rejected
+    let result = table.export_table(path, "xml");
+    assert!(matches!(result, Err(Error::InvalidFormat(_))));
+}
+
+#[test]
+fn test_export_txt_widths() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "Bartholomew".to_string()])
+        .unwrap();
+
+    // Every column is as wide as its longest cell, data types and key information included
+    let mut buffer: Vec<u8> = Vec::new();
+    table.write_to(&mut buffer, OutputFormat::Txt).unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "      id        name \n\
+         Integer  Text        \n\
+         prim_key nt_prim_key \n\
+         --------------------\n       \
+         1 Bartholomew \n"
+    );
+}
+
+#[test]
+fn test_to_markdown() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "a|b".to_string(), "85.5".to_string()])
+        .unwrap();
+    table
+        .insert(vec![
+            "2".to_string(),
+            "null".to_string(),
+            "null".to_string(),
+        ])
+        .unwrap();
+
+    let expected = "| id | name | score |\n\
+                    |---:|:---|---:|\n\
+                    | 1 | a\\|b | 85.50 |\n\
+                    | 2 | NULL | NULL |";
+    assert_eq!(table.to_markdown(), expected);
+
+    let mut buffer: Vec<u8> = Vec::new();
+    table.write_to(&mut buffer, OutputFormat::Markdown).unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        format!("{}\n", expected)
+    );
+}
+
+#[test]
+fn test_export_import_decimals() {
+    let mut table = Table::new(
+        "prices",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("price", ColumnDataType::Decimal, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, price) in [("1", "19.99"), ("2", "0.10"), ("3", "null")] {
+        table
+            .insert(vec![id.to_string(), price.to_string()])
+            .unwrap();
+    }
+
+    // Both formats keep the written digits, without going through a float
+    for format in ["csv", "txt"] {
+        let file_name = std::env::temp_dir().join(format!("rdms_test_decimals.{}", format));
+        let file_name = file_name.to_str().unwrap();
+
+        table.export_table(file_name, format).unwrap();
+        let contents = std::fs::read_to_string(file_name).unwrap();
+        assert!(contents.contains("Decimal"));
+        assert!(contents.contains("0.10"));
+
+        let imported = Table::import_table(file_name, format).unwrap();
+        assert_eq!(imported.columns[1].data_type, ColumnDataType::Decimal);
+        assert_eq!(
+            imported.columns[1].data,
+            vec![Value::Decimal(1999, 2), Value::Decimal(10, 2), Value::Null]
+        );
+        assert_eq!(imported.columns[1].data[1].to_string(), "0.10");
+
+        std::fs::remove_file(file_name).unwrap();
+    }
+}
+
+#[test]
+fn test_import_header_whitespace_and_empty_columns() {
+    let file_name = std::env::temp_dir().join("rdms_test_header.csv");
+    let file_name = file_name.to_str().unwrap();
+
+    // Whitespace around header tokens is ignored
+    std::fs::write(
+        file_name,
+        " id , name \nInteger, Text \nprim_key ,nt_prim_key\n1,Alice\n",
+    )
+    .unwrap();
+    let table = Table::import_table(file_name, "csv").unwrap();
+    assert_eq!(table.columns[0].name, "id");
+    assert_eq!(table.columns[1].name, "name");
+    assert_eq!(table.columns[1].data_type, ColumnDataType::Text);
+    assert!(table.columns[0].is_primary_key);
+
+    // A trailing comma on the header line is an empty column name
+    std::fs::write(
+        file_name,
+        "id,name,\nInteger,Text\nprim_key,nt_prim_key\n1,Alice\n",
+    )
+    .unwrap();
+    let result = Table::import_table(file_name, "csv");
+    assert!(
+        matches!(result, Err(Error::InvalidFormat(message)) if message.contains("Empty column name"))
+    );
+
+    // Imported column names follow the same naming rules as created ones
+    std::fs::write(
+        file_name,
+        "id,first\tname\nInteger,Text\nprim_key,nt_prim_key\n1,Alice\n",
+    )
+    .unwrap();
+    let result = Table::import_table(file_name, "csv");
+    assert!(matches!(result, Err(Error::InvalidName(name, _)) if name == "first\tname"));
+
+    // Header lines of different lengths don't silently drop columns
+    std::fs::write(
+        file_name,
+        "id,name,age\nInteger,Text\nprim_key,nt_prim_key\n1,Alice\n",
+    )
+    .unwrap();
+    let result = Table::import_table(file_name, "csv");
+    assert!(matches!(result, Err(Error::InvalidFormat(_))));
+
+    // A value that doesn't fit its column reports the column, the value and the expected type
+    std::fs::write(
+        file_name,
+        "id,score\nInteger,Float\nprim_key,nt_prim_key\n1,high\n",
+    )
+    .unwrap();
+    let result = Table::import_table(file_name, "csv");
+    assert!(matches!(
+        result,
+        Err(Error::ParseError {
+            column_name,
+            value,
+            expected: ColumnDataType::Float,
+        }) if column_name == "score" && value == "high"
+    ));
+
+    std::fs::remove_file(file_name).unwrap();
+}
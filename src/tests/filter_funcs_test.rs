@@ -1,4 +1,4 @@
-use crate::column::{Column, ColumnDataType};
+use crate::column::{Column, ColumnDataType, Value};
 use crate::table::{table_errors::Error, NestedCondition, Table};
 
 #[test]
@@ -170,6 +170,30 @@ fn test_filter_and_project() {
     );
     assert!(result.is_ok());
 
+    // Filter and project with aliased columns
+    let nested_condition =
+        NestedCondition::Condition("age".to_string(), ">".to_string(), "25".to_string());
+    let result = table.filter_and_project_with_aliases(
+        vec![
+            ("id".to_string(), Some("user_id".to_string())),
+            ("name".to_string(), None),
+        ],
+        nested_condition,
+    );
+    assert!(result.is_ok());
+
+    // Filter and project with a duplicate alias
+    let nested_condition =
+        NestedCondition::Condition("age".to_string(), ">".to_string(), "25".to_string());
+    let result = table.filter_and_project_with_aliases(
+        vec![
+            ("id".to_string(), Some("name".to_string())),
+            ("name".to_string(), None),
+        ],
+        nested_condition,
+    );
+    assert!(matches!(result, Err(Error::DuplicateOutputColumn(_))));
+
     // Filter and project with non-existing columns
     let nested_condition =
         NestedCondition::Condition("age".to_string(), ">".to_string(), "25".to_string());
@@ -190,3 +214,201 @@ fn test_filter_and_project() {
         table.filter_and_project(vec!["id".to_string(), "name".to_string()], nested_condition);
     assert!(matches!(result, Err(Error::InvalidOperator(_))));
 }
+
+#[test]
+fn test_filter() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, age) in [("1", "25"), ("2", "30"), ("3", "35")] {
+        table.insert(vec![id.to_string(), age.to_string()]).unwrap();
+    }
+
+    let condition =
+        NestedCondition::Condition("age".to_string(), ">".to_string(), "25".to_string());
+    let filtered = table.filter(&condition).unwrap();
+    assert_eq!(
+        filtered.columns[0].data,
+        vec![Value::Integer(2), Value::Integer(3)]
+    );
+    assert_eq!(filtered.primary_key_column.as_ref().unwrap().name, "id");
+    assert_eq!(filtered.to_string(), "id age \n------\n 2  30 \n 3  35 ");
+
+    // The original table is left untouched
+    assert_eq!(table.columns[0].data.len(), 3);
+
+    let condition =
+        NestedCondition::Condition("invalid".to_string(), "=".to_string(), "1".to_string());
+    assert!(matches!(
+        table.filter(&condition),
+        Err(Error::NonExistingColumn(_))
+    ));
+}
+
+#[test]
+fn test_filter_empty_table() {
+    assert!(matches!(Table::new("empty", vec![]), Err(Error::NoColumns)));
+
+    let table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    let condition =
+        || NestedCondition::Condition("age".to_string(), ">".to_string(), "25".to_string());
+
+    let filtered = table.filter(&condition()).unwrap();
+    assert_eq!(filtered.row_count(), 0);
+    assert_eq!(filtered.columns.len(), 2);
+    table.filter_with_nested_conditions(condition()).unwrap();
+    table
+        .filter_and_project(vec!["id".to_string()], condition())
+        .unwrap();
+    assert_eq!(
+        table
+            .clone()
+            .delete_with_nested_conditions(&condition())
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_filter_with_short_first_column() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["Alice".to_string(), "30".to_string()])
+        .unwrap();
+
+    // Column 0 holds fewer values than the table has records
+    table.columns[1].data.push(Value::Integer(40));
+    table.columns[1].data.push(Value::Integer(20));
+    assert_eq!(table.row_count(), 3);
+
+    // Every record is evaluated, and the missing values read as NULL
+    let condition = NestedCondition::parse("age > 25").unwrap();
+    let filtered = table.filter(&condition).unwrap();
+    assert_eq!(
+        filtered.columns[1].data,
+        vec![Value::Integer(30), Value::Integer(40)]
+    );
+    assert_eq!(
+        filtered.columns[0].data,
+        vec![Value::Text("Alice".to_string()), Value::Null]
+    );
+    assert!(filtered.validate().is_ok());
+
+    let condition = NestedCondition::parse("name IS NULL").unwrap();
+    assert_eq!(table.count_where(&condition).unwrap(), 2);
+    assert!(table
+        .filter_and_project(vec!["age".to_string()], condition.clone())
+        .is_ok());
+    assert!(table.filter_with_nested_conditions(condition).is_ok());
+}
+
+#[test]
+fn test_delete_returning() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    for row in [
+        ["1", "Alice", "30"],
+        ["2", "Bob", "17"],
+        ["3", "Charlie", "null"],
+        ["4", "Dave", "12"],
+    ] {
+        table
+            .insert(row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+    table.create_index("name").unwrap();
+    let before = table.clone();
+
+    let condition = NestedCondition::parse("age < 18 OR age IS NULL").unwrap();
+    let deleted = table.delete_returning(&condition).unwrap();
+
+    // The full records, in their original order
+    let record = |row_idx: usize| -> Vec<Value> {
+        before
+            .columns
+            .iter()
+            .map(|column| column.data[row_idx].clone())
+            .collect()
+    };
+    assert_eq!(deleted, vec![record(1), record(2), record(3)]);
+    assert_eq!(table.columns[0].data, vec![Value::Integer(1)]);
+    assert_eq!(
+        table
+            .filter_indexed(&NestedCondition::parse("name = 'Alice'").unwrap())
+            .unwrap()
+            .row_count(),
+        1
+    );
+
+    // The records can be inserted back
+    for record in deleted {
+        table
+            .insert(record.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+    assert_eq!(table.row_count(), 4);
+
+    assert_eq!(table.delete_returning(&condition).unwrap().len(), 3);
+    assert!(table.delete_returning(&condition).unwrap().is_empty());
+    let result = table.delete_returning(&NestedCondition::parse("height > 1").unwrap());
+    assert!(matches!(result, Err(Error::NonExistingColumn(_))));
+}
+
+#[test]
+fn test_search() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("email", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    for row in [
+        ["1", "Alice", "alice@example.com"],
+        ["2", "Bob", "bob@mail.org"],
+        ["3", "Charlie", "null"],
+        ["12", "Dave", "dave@Example.com"],
+    ] {
+        table
+            .insert(row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    // Either text column can match, while the integer ids are skipped
+    assert_eq!(table.search("example.com"), vec![0]);
+    assert_eq!(table.search("li"), vec![0, 2]);
+    assert_eq!(table.search_ci("EXAMPLE"), vec![0, 3]);
+    assert_eq!(table.search_ci("bob"), vec![1]);
+    assert!(table.search("12").is_empty());
+    assert!(table.search("zebra").is_empty());
+    assert!(table.search_ci("zebra").is_empty());
+}
@@ -0,0 +1,177 @@
+use crate::column::{Column, ColumnDataType, Value};
+use crate::table::helpers::satisfies_condition;
+use crate::table::operators::{apply_operator, Operator};
+use crate::table::{table_errors::Error, NestedCondition, Table};
+use std::cmp::Ordering;
+
+#[test]
+fn test_null_conditions() {
+    // Comparing NULL with a regular operator never matches
+    for operator in [
+        Operator::Equal,
+        Operator::NotEqual,
+        Operator::LessThan,
+        Operator::GreaterThan,
+        Operator::LessThanOrEqual,
+        Operator::GreaterThanOrEqual,
+    ] {
+        assert!(!satisfies_condition(
+            &Value::Null,
+            ColumnDataType::Integer,
+            "30",
+            &operator
+        ));
+    }
+
+    // IS NULL / IS NOT NULL ignore the condition value
+    assert!(satisfies_condition(
+        &Value::Null,
+        ColumnDataType::Integer,
+        "",
+        &Operator::IsNull
+    ));
+    assert!(!satisfies_condition(
+        &Value::Integer(30),
+        ColumnDataType::Integer,
+        "",
+        &Operator::IsNull
+    ));
+    assert!(satisfies_condition(
+        &Value::Text("Bob".to_string()),
+        ColumnDataType::Text,
+        "",
+        &Operator::IsNotNull
+    ));
+    assert!(!satisfies_condition(
+        &Value::Null,
+        ColumnDataType::Text,
+        "",
+        &Operator::IsNotNull
+    ));
+
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "30".to_string()])
+        .unwrap();
+    table
+        .insert(vec!["2".to_string(), "25".to_string()])
+        .unwrap();
+    table
+        .insert(vec!["3".to_string(), "null".to_string()])
+        .unwrap();
+
+    // `age != 30` skips the NULL age, `age IS NULL` finds it
+    let condition =
+        NestedCondition::Condition("age".to_string(), "!=".to_string(), "30".to_string());
+    assert_eq!(table.count_where(&condition).unwrap(), 1);
+    let condition =
+        NestedCondition::Condition("age".to_string(), "IS NULL".to_string(), String::new());
+    assert_eq!(table.count_where(&condition).unwrap(), 1);
+    let condition =
+        NestedCondition::Condition("age".to_string(), "is not null".to_string(), String::new());
+    assert_eq!(table.count_where(&condition).unwrap(), 2);
+}
+
+#[test]
+fn test_numeric_condition_literals() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "30".to_string(), "85.0".to_string()])
+        .unwrap();
+    table
+        .insert(vec!["2".to_string(), "31".to_string(), "92.5".to_string()])
+        .unwrap();
+
+    // Integral float literal on an Integer column
+    let condition =
+        NestedCondition::Condition("age".to_string(), "=".to_string(), "30.0".to_string());
+    assert_eq!(table.count_where(&condition).unwrap(), 1);
+
+    // Non-integral float literal on an Integer column compares numerically
+    let condition =
+        NestedCondition::Condition("age".to_string(), "<".to_string(), "30.5".to_string());
+    assert_eq!(table.count_where(&condition).unwrap(), 1);
+    let condition =
+        NestedCondition::Condition("age".to_string(), "=".to_string(), "30.5".to_string());
+    assert_eq!(table.count_where(&condition).unwrap(), 0);
+
+    // Integer literal on a Float column
+    let condition =
+        NestedCondition::Condition("score".to_string(), "=".to_string(), "85".to_string());
+    assert_eq!(table.count_where(&condition).unwrap(), 1);
+
+    // Non-numeric literals are rejected instead of panicking
+    let condition =
+        NestedCondition::Condition("age".to_string(), "=".to_string(), "thirty".to_string());
+    assert!(matches!(
+        table.count_where(&condition),
+        Err(Error::InvalidConditionValue(_, _))
+    ));
+    let condition =
+        NestedCondition::Condition("score".to_string(), ">".to_string(), "high".to_string());
+    assert!(matches!(
+        table.filter_with_nested_conditions(condition),
+        Err(Error::InvalidConditionValue(_, _))
+    ));
+}
+
+#[test]
+fn test_apply_operator() {
+    let cases = [
+        (Operator::Equal, [false, true, false, false]),
+        (Operator::NotEqual, [true, false, true, true]),
+        (Operator::LessThan, [true, false, false, false]),
+        (Operator::GreaterThan, [false, false, true, false]),
+        (Operator::LessThanOrEqual, [true, true, false, false]),
+        (Operator::GreaterThanOrEqual, [false, true, true, false]),
+    ];
+    let orderings = [
+        Some(Ordering::Less),
+        Some(Ordering::Equal),
+        Some(Ordering::Greater),
+        None,
+    ];
+
+    for (operator, expected) in cases {
+        for (ordering, expected) in orderings.iter().zip(expected) {
+            assert_eq!(apply_operator(*ordering, &operator), expected);
+        }
+    }
+
+    // Integers compared against a float literal, and NaN floats, go through the same helper
+    let int = Value::Integer(30);
+    assert!(satisfies_condition(
+        &int,
+        ColumnDataType::Integer,
+        "30.5",
+        &Operator::LessThan
+    ));
+    let nan = Value::Float(f64::NAN);
+    assert!(!satisfies_condition(
+        &nan,
+        ColumnDataType::Float,
+        "NaN",
+        &Operator::Equal
+    ));
+    assert!(satisfies_condition(
+        &nan,
+        ColumnDataType::Float,
+        "NaN",
+        &Operator::NotEqual
+    ));
+}
@@ -0,0 +1,316 @@
+use crate::column::{Column, ColumnDataType, Value};
+use crate::table::{table_errors::Error, NestedCondition, Table};
+
+fn condition(column: &str, operator: &str, value: &str) -> NestedCondition {
+    NestedCondition::Condition(column.to_string(), operator.to_string(), value.to_string())
+}
+
+fn assert_same_rows(table: &Table, condition: &NestedCondition) {
+    let indexed = table.filter_indexed(condition).unwrap();
+    let scanned = table.filter(condition).unwrap();
+    for (indexed_column, scanned_column) in indexed.columns.iter().zip(&scanned.columns) {
+        assert_eq!(indexed_column.data, scanned_column.data);
+    }
+}
+
+#[test]
+fn test_filter_indexed() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("city", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, city, age) in [
+        ("1", "Paris", "25"),
+        ("2", "Oslo", "30"),
+        ("3", "Paris", "null"),
+        ("4", "Lima", "30"),
+    ] {
+        table
+            .insert(vec![id.to_string(), city.to_string(), age.to_string()])
+            .unwrap();
+    }
+
+    table.create_index("city").unwrap();
+    table.create_index("age").unwrap();
+    assert!(table.has_index("city"));
+
+    let conditions = [
+        condition("city", "=", "Paris"),
+        condition("city", "=", "Rome"),
+        condition("age", "=", "30"),
+        condition("age", "=", "30.0"),
+        condition("age", "is null", ""),
+        condition("age", ">", "25"),
+        condition("id", "=", "2"),
+    ];
+    for condition in &conditions {
+        assert_same_rows(&table, condition);
+    }
+
+    let paris = table
+        .filter_indexed(&condition("city", "=", "Paris"))
+        .unwrap();
+    assert_eq!(
+        paris.columns[0].data,
+        vec![Value::Integer(1), Value::Integer(3)]
+    );
+
+    // The indexes follow inserts, updates and deletes
+    table
+        .insert(vec!["5".to_string(), "Paris".to_string(), "41".to_string()])
+        .unwrap();
+    let updated = table
+        .update_with_nested_conditions(
+            ("city".to_string(), "Oslo".to_string()),
+            condition("id", "=", "1"),
+        )
+        .unwrap();
+    assert_eq!(updated, 1);
+    let deleted = table
+        .delete_with_nested_conditions(&condition("id", "=", "2"))
+        .unwrap();
+    assert_eq!(deleted, 1);
+    for condition in &conditions {
+        assert_same_rows(&table, condition);
+    }
+    let paris = table
+        .filter_indexed(&condition("city", "=", "Paris"))
+        .unwrap();
+    assert_eq!(
+        paris.columns[0].data,
+        vec![Value::Integer(3), Value::Integer(5)]
+    );
+
+    // Updates only move the changed records, leaving the indexes as a rebuild would
+    table
+        .update_by_pk(
+            "4",
+            vec![
+                ("city".to_string(), "Paris".to_string()),
+                ("age".to_string(), "null".to_string()),
+            ],
+        )
+        .unwrap();
+    let mut rebuilt = table.clone();
+    rebuilt.rebuild_indexes();
+    assert_eq!(table.indexes, rebuilt.indexes);
+    for condition in &conditions {
+        assert_same_rows(&table, condition);
+    }
+
+    // Invalid conditions are reported even when an index exists
+    let result = table.filter_indexed(&condition("age", "=", "abc"));
+    assert!(matches!(result, Err(Error::InvalidConditionValue(_, _))));
+
+    table.drop_index("city").unwrap();
+    assert!(!table.has_index("city"));
+    assert_same_rows(&table, &condition("city", "=", "Paris"));
+
+    assert!(matches!(
+        table.create_index("invalid"),
+        Err(Error::NonExistingColumn(_))
+    ));
+}
+
+#[test]
+fn test_case_insensitive_index() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("Id", ColumnDataType::Integer, None, true, None),
+            Column::new("City", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    table.case_insensitive_names = true;
+    for (id, city) in [("1", "Paris"), ("2", "Oslo"), ("3", "Paris")] {
+        table
+            .insert(vec![id.to_string(), city.to_string()])
+            .unwrap();
+    }
+
+    // The index is kept under the declared name, whichever spelling created it
+    table.create_index("CITY").unwrap();
+    assert!(table.indexes.contains_key("City"));
+    assert!(table.has_index("city"));
+    assert!(!table.has_index("town"));
+    table
+        .insert(vec!["4".to_string(), "Paris".to_string()])
+        .unwrap();
+    assert_eq!(
+        table
+            .filter_indexed(&condition("city", "=", "Paris"))
+            .unwrap()
+            .row_count(),
+        3
+    );
+
+    table.drop_index("cItY").unwrap();
+    assert!(!table.has_index("City"));
+    assert!(table.indexes.is_empty());
+}
+
+#[test]
+fn test_truncate() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("city", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    table.create_index("city").unwrap();
+    for (id, city) in [("1", "Paris"), ("2", "Oslo")] {
+        table
+            .insert(vec![id.to_string(), city.to_string()])
+            .unwrap();
+    }
+
+    assert_eq!(table.truncate(), 2);
+    assert_eq!(table.row_count(), 0);
+    assert_eq!(table.columns.len(), 2);
+    assert!(table.has_index("city"));
+    assert_eq!(
+        table
+            .filter_indexed(&condition("city", "=", "Paris"))
+            .unwrap()
+            .row_count(),
+        0
+    );
+
+    // Previously used primary keys are free again
+    table
+        .insert(vec!["1".to_string(), "Lima".to_string()])
+        .unwrap();
+    assert_eq!(table.truncate(), 1);
+    assert_eq!(table.truncate(), 0);
+}
+
+#[test]
+fn test_compact() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("city", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    table.create_index("city").unwrap();
+    for id in 0..1000 {
+        table
+            .insert(vec![id.to_string(), format!("city_{}", id % 20)])
+            .unwrap();
+    }
+
+    table
+        .delete_with_nested_conditions(&condition("id", ">=", "10"))
+        .unwrap();
+    table.compact();
+    assert_eq!(table.row_count(), 10);
+    for column in &table.columns {
+        assert_eq!(column.data.capacity(), 10);
+    }
+    assert!(table.primary_key_column.as_ref().unwrap().data.is_empty());
+
+    // The rebuilt index only knows the remaining records
+    assert_eq!(table.indexes["city"].len(), 10);
+    let filtered = table
+        .filter_indexed(&condition("city", "=", "city_3"))
+        .unwrap();
+    assert_eq!(filtered.columns[0].data, vec![Value::Integer(3)]);
+    assert_eq!(
+        table
+            .filter_indexed(&condition("city", "=", "city_13"))
+            .unwrap()
+            .row_count(),
+        0
+    );
+
+    // Inserting after compacting still checks the primary key and updates the index
+    let result = table.insert(vec!["3".to_string(), "Oslo".to_string()]);
+    assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+    table
+        .insert(vec!["10".to_string(), "city_3".to_string()])
+        .unwrap();
+    assert_eq!(
+        table
+            .filter_indexed(&condition("city", "=", "city_3"))
+            .unwrap()
+            .row_count(),
+        2
+    );
+
+    // Truncating keeps the memory until the table is compacted
+    table.truncate();
+    assert!(table.columns[0].data.capacity() >= 11);
+    table.compact();
+    assert_eq!(table.columns[0].data.capacity(), 0);
+    assert!(table.indexes["city"].is_empty());
+}
+
+#[test]
+fn test_filter_primary_key_range() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("city", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, city) in [("3", "Paris"), ("1", "Oslo"), ("4", "Lima"), ("2", "Rome")] {
+        table
+            .insert(vec![id.to_string(), city.to_string()])
+            .unwrap();
+    }
+
+    let ids = |op: &str, value: &str| {
+        table.filter_primary_key_range(op, value).unwrap().columns[0]
+            .data
+            .clone()
+    };
+    let integers = |ids: &[i64]| ids.iter().map(|&id| Value::Integer(id)).collect::<Vec<_>>();
+
+    // Matching records keep the table order, and the boundary is only included by >= and <=
+    assert_eq!(ids(">", "2"), integers(&[3, 4]));
+    assert_eq!(ids(">=", "2"), integers(&[3, 4, 2]));
+    assert_eq!(ids("<", "3"), integers(&[1, 2]));
+    assert_eq!(ids("<=", "3"), integers(&[3, 1, 2]));
+    assert_eq!(ids(">", "4"), integers(&[]));
+    assert_eq!(ids("<", "1"), integers(&[]));
+    assert_eq!(ids(">=", "-5"), integers(&[3, 1, 4, 2]));
+
+    // Non-integer boundaries go through the regular filter
+    assert_eq!(ids(">", "2.5"), integers(&[3, 4]));
+    assert!(matches!(
+        table.filter_primary_key_range(">", "abc"),
+        Err(Error::InvalidConditionValue(_, _))
+    ));
+    assert!(matches!(
+        table.filter_primary_key_range("=", "2"),
+        Err(Error::InvalidOperator(_))
+    ));
+
+    let no_key = Table::new(
+        "no_key",
+        vec![Column::new(
+            "id",
+            ColumnDataType::Integer,
+            None,
+            false,
+            None,
+        )],
+    )
+    .unwrap();
+    assert!(matches!(
+        no_key.filter_primary_key_range(">", "1"),
+        Err(Error::NoPrimaryKey)
+    ));
+}
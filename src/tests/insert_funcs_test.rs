@@ -1,5 +1,5 @@
-use crate::column::{Column, ColumnDataType};
-use crate::table::{table_errors::Error, Table};
+use crate::column::{Column, ColumnDataType, Value};
+use crate::table::{table_errors::Error, InsertOutcome, NestedCondition, Row, Table};
 
 #[test]
 fn test_insert() {
@@ -19,7 +19,13 @@ fn test_insert() {
         "Alice".to_string(),
         "85.5".to_string(),
     ]);
-    assert!(result.is_ok());
+    assert_eq!(
+        result.unwrap(),
+        InsertOutcome {
+            row_index: 0,
+            primary_key: Some(Value::Integer(1)),
+        }
+    );
 
     // Test inserting a record with mismatched column count
     let result = table.insert(vec!["2".to_string(), "Bob".to_string()]);
@@ -31,7 +37,14 @@ fn test_insert() {
         "Charlie".to_string(),
         "invalid".to_string(),
     ]);
-    assert!(matches!(result, Err(Error::ParseError(2, _))));
+    assert!(matches!(
+        result,
+        Err(Error::ParseError {
+            column_name,
+            value,
+            expected: ColumnDataType::Float,
+        }) if column_name == "score" && value == "invalid"
+    ));
 
     // Test inserting a record with null primary key
     let result = table.insert(vec![
@@ -42,9 +55,11 @@ fn test_insert() {
     assert!(matches!(result, Err(Error::NullPrimaryKey)));
 
     // Test inserting a record with duplicate primary key
-    table
+    let outcome = table
         .insert(vec!["5".to_string(), "Eve".to_string(), "92.0".to_string()])
         .unwrap();
+    assert_eq!(outcome.row_index, 1);
+    assert_eq!(outcome.primary_key, Some(Value::Integer(5)));
     let result = table.insert(vec![
         "5".to_string(),
         "Frank".to_string(),
@@ -93,7 +108,11 @@ fn test_insert_with_columns() {
     );
     assert!(matches!(
         result,
-        Err(Error::ParseError(_, value_str)) if value_str == "invalid"
+        Err(Error::ParseError {
+            column_name,
+            value,
+            expected: ColumnDataType::Float,
+        }) if column_name == "score" && value == "invalid"
     ));
 
     // Test inserting a record without providing the primary key column
@@ -126,4 +145,572 @@ fn test_insert_with_columns() {
         vec!["5".to_string(), "Eve".to_string(), "92.0".to_string()],
     );
     assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+
+    // Test inserting a record naming a column twice
+    let result = table.insert_with_columns(
+        vec!["id".to_string(), "name".to_string(), "id".to_string()],
+        vec!["6".to_string(), "Frank".to_string(), "7".to_string()],
+    );
+    assert!(matches!(
+        result,
+        Err(Error::DuplicateColumnInInsert(column_name)) if column_name == "id"
+    ));
+    assert_eq!(table.row_count(), 2);
+}
+
+#[test]
+fn test_insert_or_ignore() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::builder("email", ColumnDataType::Text)
+                .unique()
+                .build(),
+        ],
+    )
+    .unwrap();
+
+    let row = |id: &str, email: &str| vec![id.to_string(), email.to_string()];
+
+    assert!(table.insert_or_ignore(row("1", "a@example.com")).unwrap());
+
+    // Duplicate primary keys and unique values are ignored and leave the table unchanged
+    assert!(!table.insert_or_ignore(row("1", "b@example.com")).unwrap());
+    assert!(!table.insert_or_ignore(row("2", "a@example.com")).unwrap());
+    assert_eq!(table.row_count(), 1);
+
+    assert!(table.insert_or_ignore(row("2", "b@example.com")).unwrap());
+    assert_eq!(table.row_count(), 2);
+
+    // Other errors are still reported
+    let result = table.insert_or_ignore(row("x", "c@example.com"));
+    assert!(matches!(result, Err(Error::ParseError { .. })));
+    let result = table.insert_or_ignore(row("null", "c@example.com"));
+    assert!(matches!(result, Err(Error::NullPrimaryKey)));
+}
+
+#[test]
+fn test_insert_many() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::builder("email", ColumnDataType::Text)
+                .unique()
+                .build(),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec![
+            "1".to_string(),
+            "a@example.com".to_string(),
+            "25".to_string(),
+        ])
+        .unwrap();
+
+    let rows = |rows: &[[&str; 3]]| -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|value| value.to_string()).collect())
+            .collect()
+    };
+
+    // Every failing batch reports its first rejected record and leaves the table unchanged
+    let failing_batches = [
+        rows(&[["2", "b@example.com", "30"], ["x", "c@example.com", "35"]]),
+        rows(&[["2", "b@example.com", "30"], ["1", "c@example.com", "35"]]),
+        rows(&[["2", "b@example.com", "30"], ["2", "c@example.com", "35"]]),
+        rows(&[["2", "b@example.com", "30"], ["3", "b@example.com", "35"]]),
+        rows(&[
+            ["2", "b@example.com", "30"],
+            ["null", "c@example.com", "35"],
+        ]),
+    ];
+    for batch in failing_batches {
+        let error = table.insert_many(batch).unwrap_err();
+        assert_eq!(error.row_idx, 1);
+        assert!(matches!(
+            error.error,
+            Error::ParseError { .. }
+                | Error::DuplicatePrimaryKey
+                | Error::DuplicateValue(_, _)
+                | Error::NullPrimaryKey
+        ));
+        assert_eq!(table.row_count(), 1);
+    }
+    let error = table.insert_many(vec![vec!["2".to_string()]]).unwrap_err();
+    assert!(matches!(error.error, Error::MismatchedColumnCount));
+
+    // A valid batch is inserted as a whole, and indexes see the new records
+    table.create_index("age").unwrap();
+    let inserted = table
+        .insert_many(rows(&[
+            ["2", "b@example.com", "30"],
+            ["3", "null", "30"],
+            ["4", "null", "35"],
+        ]))
+        .unwrap();
+    assert_eq!(inserted, 3);
+    assert_eq!(table.row_count(), 4);
+    let condition =
+        NestedCondition::Condition("age".to_string(), "=".to_string(), "30".to_string());
+    assert_eq!(table.filter_indexed(&condition).unwrap().row_count(), 2);
+}
+
+#[test]
+fn test_replace() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::builder("email", ColumnDataType::Text)
+                .unique()
+                .build(),
+        ],
+    )
+    .unwrap();
+    let row = |values: [&str; 3]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+    assert!(!table.replace(row(["1", "Alice", "a@x"])).unwrap());
+    assert!(!table.replace(row(["2", "Bob", "b@x"])).unwrap());
+
+    // Every column of the old record is replaced, and the new one moves to the end
+    assert!(table.replace(row(["1", "Alicia", "null"])).unwrap());
+    assert_eq!(table.row_count(), 2);
+    assert_eq!(
+        table.columns[0].data,
+        vec![Value::Integer(2), Value::Integer(1)]
+    );
+    assert_eq!(
+        table.columns[1].data,
+        vec![
+            Value::Text("Bob".to_string()),
+            Value::Text("Alicia".to_string())
+        ]
+    );
+    assert_eq!(table.columns[2].data[1], Value::Null);
+
+    // A rejected record leaves the old one in place
+    assert!(table.replace(row(["2", "Bobby", "b@x"])).unwrap());
+    assert!(matches!(
+        table.replace(row(["1", "Alicia", "b@x"])),
+        Err(Error::DuplicateValue(_, _))
+    ));
+    assert_eq!(
+        table.columns[0].data,
+        vec![Value::Integer(1), Value::Integer(2)]
+    );
+    assert_eq!(
+        table.columns[2].data,
+        vec![Value::Null, Value::Text("b@x".to_string())]
+    );
+    assert!(matches!(
+        table.replace(row(["x", "Alicia", "null"])),
+        Err(Error::ParseError { .. })
+    ));
+
+    let mut no_key = Table::new(
+        "no_key",
+        vec![Column::new(
+            "id",
+            ColumnDataType::Integer,
+            None,
+            false,
+            None,
+        )],
+    )
+    .unwrap();
+    assert!(matches!(
+        no_key.replace(vec!["1".to_string()]),
+        Err(Error::NoPrimaryKey)
+    ));
+}
+
+#[test]
+fn test_insert_values() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("price", ColumnDataType::Decimal, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    let outcome = table
+        .insert_values(vec![
+            Value::Integer(1),
+            Value::Text("Pen".to_string()),
+            Value::Decimal(199, 2),
+        ])
+        .unwrap();
+    assert_eq!(
+        outcome,
+        InsertOutcome {
+            row_index: 0,
+            primary_key: Some(Value::Integer(1)),
+        }
+    );
+    table
+        .insert_values(vec![Value::Integer(2), Value::Null, Value::Null])
+        .unwrap();
+
+    // A value of the wrong variant is rejected without inserting anything
+    let result = table.insert_values(vec![
+        Value::Integer(3),
+        Value::Text("Ink".to_string()),
+        Value::Float(1.99),
+    ]);
+    assert!(matches!(
+        result,
+        Err(Error::TypeMismatch(column_name, ColumnDataType::Decimal)) if column_name == "price"
+    ));
+    assert_eq!(table.row_count(), 2);
+
+    // The primary key checks of `insert` still apply
+    let result = table.insert_values(vec![Value::Integer(2), Value::Null, Value::Null]);
+    assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+    let result = table.insert_values(vec![Value::Null, Value::Null, Value::Null]);
+    assert!(matches!(result, Err(Error::NullPrimaryKey)));
+    let result = table.insert_values(vec![Value::Integer(4)]);
+    assert!(matches!(result, Err(Error::MismatchedColumnCount)));
+}
+
+#[test]
+fn test_insert_typed() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    // Some("null") is the text, None is NULL
+    table
+        .insert_typed(vec![Some("1".to_string()), Some("null".to_string()), None])
+        .unwrap();
+    table
+        .insert_typed(vec![Some("2".to_string()), None, Some("30".to_string())])
+        .unwrap();
+    assert_eq!(
+        table.columns[1].data,
+        vec![Value::Text("null".to_string()), Value::Null]
+    );
+    assert_eq!(table.columns[2].data, vec![Value::Null, Value::Integer(30)]);
+
+    // The text "null" is still what `insert` reads as NULL
+    table
+        .insert(vec![
+            "3".to_string(),
+            "null".to_string(),
+            "null".to_string(),
+        ])
+        .unwrap();
+    assert_eq!(table.columns[1].data[2], Value::Null);
+
+    let result = table.insert_typed(vec![Some("4".to_string()), None, Some("null".to_string())]);
+    assert!(matches!(
+        result,
+        Err(Error::ParseError { column_name, .. }) if column_name == "age"
+    ));
+    let result = table.insert_typed(vec![None, None, None]);
+    assert!(matches!(result, Err(Error::NullPrimaryKey)));
+    let result = table.insert_typed(vec![Some("1".to_string()), None, None]);
+    assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+    let result = table.insert_typed(vec![Some("5".to_string())]);
+    assert!(matches!(result, Err(Error::MismatchedColumnCount)));
+    assert_eq!(table.row_count(), 3);
+}
+
+#[test]
+fn test_insert_non_finite_float() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    for value in ["nan", "NaN", "inf", "-infinity"] {
+        let result = table.insert(vec!["1".to_string(), value.to_string()]);
+        assert!(matches!(
+            result,
+            Err(Error::NonFiniteFloat(column_name, rejected))
+                if column_name == "score" && rejected == value
+        ));
+    }
+    for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let result = table.insert_values(vec![Value::Integer(1), Value::Float(value)]);
+        assert!(matches!(result, Err(Error::NonFiniteFloat(_, _))));
+    }
+    assert_eq!(table.row_count(), 0);
+
+    // The largest finite floats are still accepted and compare as usual
+    table
+        .insert(vec!["1".to_string(), "1.7976931348623157e308".to_string()])
+        .unwrap();
+    table
+        .insert(vec!["2".to_string(), "-1.7976931348623157e308".to_string()])
+        .unwrap();
+    let filtered = table
+        .filter(&NestedCondition::Condition(
+            "score".to_string(),
+            ">".to_string(),
+            "0".to_string(),
+        ))
+        .unwrap();
+    assert_eq!(filtered.columns[1].data, vec![Value::Float(f64::MAX)]);
+}
+
+#[test]
+fn test_insert_with_columns_defaults() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::builder("id", ColumnDataType::Integer)
+                .primary_key()
+                .default_value(Value::Integer(0))
+                .build(),
+            Column::builder("name", ColumnDataType::Text)
+                .not_null()
+                .build(),
+            Column::builder("status", ColumnDataType::Text)
+                .not_null()
+                .default_value(Value::Text("active".to_string()))
+                .build(),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    // Omitted columns take their default, or NULL without one
+    table
+        .insert_with_columns(
+            vec!["id".to_string(), "name".to_string()],
+            vec!["1".to_string(), "Alice".to_string()],
+        )
+        .unwrap();
+    assert_eq!(table.columns[2].data[0], Value::Text("active".to_string()));
+    assert_eq!(table.columns[3].data[0], Value::Null);
+
+    // Explicitly provided values win over the default
+    table
+        .insert_with_columns(
+            vec!["id".to_string(), "name".to_string(), "status".to_string()],
+            vec!["2".to_string(), "Bob".to_string(), "banned".to_string()],
+        )
+        .unwrap();
+    assert_eq!(table.columns[2].data[1], Value::Text("banned".to_string()));
+
+    // The primary key may be omitted since it has a default
+    table
+        .insert_with_columns(
+            vec!["name".to_string(), "score".to_string()],
+            vec!["Carol".to_string(), "7.5".to_string()],
+        )
+        .unwrap();
+    assert_eq!(table.columns[0].data[2], Value::Integer(0));
+    assert_eq!(table.columns[3].data[2], Value::Float(7.5));
+
+    // Defaults go through the same primary key checks as provided values
+    let result = table.insert_with_columns(vec!["name".to_string()], vec!["Dave".to_string()]);
+    assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+
+    // A not-null column without a default must be provided
+    let result = table.insert_with_columns(vec!["id".to_string()], vec!["3".to_string()]);
+    assert!(matches!(result, Err(Error::NullValue(_))));
+    assert_eq!(table.row_count(), 3);
+}
+
+#[test]
+fn test_append() {
+    let columns = || {
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("amount", ColumnDataType::Float, None, false, None),
+        ]
+    };
+    let mut january = Table::new("sales_january", columns()).unwrap();
+    january
+        .insert(vec![
+            "1".to_string(),
+            "Alice".to_string(),
+            "10.5".to_string(),
+        ])
+        .unwrap();
+
+    // The appended table may order its columns differently
+    let mut february = Table::new(
+        "sales_february",
+        vec![
+            Column::new("amount", ColumnDataType::Float, None, false, None),
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    february
+        .insert(vec!["20.0".to_string(), "2".to_string(), "Bob".to_string()])
+        .unwrap();
+    february
+        .insert(vec![
+            "null".to_string(),
+            "3".to_string(),
+            "Text".to_string(),
+        ])
+        .unwrap();
+
+    assert_eq!(january.append(&february).unwrap(), 2);
+    assert_eq!(
+        january.columns[0].data,
+        vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]
+    );
+    assert_eq!(january.columns[2].data[1], Value::Float(20.0));
+    assert_eq!(january.columns[2].data[2], Value::Null);
+    assert_eq!(february.row_count(), 2);
+
+    // Primary keys stay unique across the merged records, and nothing is appended then
+    let mut march = Table::new("sales_march", columns()).unwrap();
+    march
+        .insert(vec![
+            "4".to_string(),
+            "Carol".to_string(),
+            "1.0".to_string(),
+        ])
+        .unwrap();
+    march
+        .insert(vec!["2".to_string(), "Dave".to_string(), "2.0".to_string()])
+        .unwrap();
+    assert!(matches!(
+        january.append(&march),
+        Err(Error::DuplicatePrimaryKey)
+    ));
+    assert_eq!(january.row_count(), 3);
+
+    // Every differing column is reported
+    let other = Table::new(
+        "other",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Integer, None, false, None),
+            Column::new("region", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    match january.append(&other) {
+        Err(Error::SchemaMismatch(differences)) => assert_eq!(
+            differences,
+            vec![
+                "column 'name' is Text in this table but Integer in the appended table",
+                "column 'amount' is missing from the appended table",
+                "column 'region' does not exist in this table",
+            ]
+        ),
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    // Values missing from a short column of the appended table read as NULL
+    let mut april = Table::new("sales_april", columns()).unwrap();
+    april
+        .insert(vec!["5".to_string(), "Erin".to_string(), "3.0".to_string()])
+        .unwrap();
+    april.columns[0].data.push(Value::Integer(6));
+    april.columns[1].data.push(Value::Text("Frank".to_string()));
+    assert_eq!(january.append(&april).unwrap(), 2);
+    assert_eq!(january.columns[0].data[4], Value::Integer(6));
+    assert_eq!(january.columns[2].data[4], Value::Null);
+}
+
+#[test]
+fn test_insert_row() {
+    let mut table = Table::new(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::builder("age", ColumnDataType::Integer)
+                .default_value(Value::Integer(18))
+                .build(),
+        ],
+    )
+    .unwrap();
+
+    // Columns may come in any order, and omitted ones take their default value
+    let row = Row::new()
+        .with("name", Value::Text("Alice".to_string()))
+        .with("id", Value::Integer(1));
+    let outcome = table.insert_row(row).unwrap();
+    assert_eq!(outcome.primary_key, Some(Value::Integer(1)));
+    table
+        .insert_row(Row::from(vec![
+            ("id".to_string(), Value::Integer(2)),
+            ("name".to_string(), Value::Null),
+            ("age".to_string(), Value::Integer(40)),
+        ]))
+        .unwrap();
+
+    // Round trip through select_rows
+    let rows = table.select_rows(vec![], None).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get("name"), Some(&Value::Text("Alice".to_string())));
+    assert_eq!(rows[0].get("age"), Some(&Value::Integer(18)));
+    assert_eq!(rows[1].get("name"), Some(&Value::Null));
+    assert_eq!(rows[1].get("height"), None);
+
+    let mut copy = Table::new(
+        "users_copy",
+        table
+            .columns
+            .iter()
+            .map(|c| Column::new(&c.name, c.data_type, None, c.name == "id", None))
+            .collect(),
+    )
+    .unwrap();
+    for row in rows.clone() {
+        copy.insert_row(row).unwrap();
+    }
+    assert_eq!(copy.select_rows(vec![], None).unwrap(), rows);
+
+    let condition = NestedCondition::parse("age > 20").unwrap();
+    let rows = table
+        .select_rows(vec!["age".to_string(), "id".to_string()], Some(condition))
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![Row::new()
+            .with("age", Value::Integer(40))
+            .with("id", Value::Integer(2))]
+    );
+    assert_eq!(rows[0].entries()[1], ("id".to_string(), Value::Integer(2)));
+
+    // Rejected rows
+    let result = table.insert_row(Row::new().with("name", Value::Text("Bob".to_string())));
+    assert!(matches!(result, Err(Error::PrimaryKeyNotProvided(_))));
+    let result = table.insert_row(Row::new().with("id", Value::Text("3".to_string())));
+    assert!(matches!(result, Err(Error::TypeMismatch(column_name, _)) if column_name == "id"));
+    let result = table.insert_row(
+        Row::new()
+            .with("id", Value::Integer(3))
+            .with("height", Value::Integer(180)),
+    );
+    assert!(matches!(result, Err(Error::NonExistingColumns(_))));
+    let result = table.insert_row(
+        Row::new()
+            .with("id", Value::Integer(3))
+            .with("id", Value::Integer(4)),
+    );
+    assert!(matches!(result, Err(Error::DuplicateColumnInInsert(_))));
+    let result = table.insert_row(Row::new().with("id", Value::Integer(1)));
+    assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+    assert_eq!(table.row_count(), 2);
 }
@@ -1,5 +1,14 @@
+mod aggregate_funcs_test;
+mod column_test;
+mod condition_parser_test;
+mod database_test;
 mod export_import_tests;
 mod filter_funcs_test;
+mod helpers_test;
+mod index_funcs_test;
 mod insert_funcs_test;
+mod schema_test;
+mod set_funcs_test;
+mod sort_funcs_test;
 mod update_funcs_test;
 mod utils_test;
@@ -0,0 +1,351 @@
+use crate::column::{Column, ColumnDataType, ForeignKeyInfo, Value};
+use crate::database::db_errors::Error;
+use crate::database::Database;
+use crate::table::schema::{ColumnChange, ColumnSchema, TableSchema};
+
+#[test]
+fn test_schema() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "addresses",
+        vec![
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                true,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+            Column::new("address", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    // Table-level schema lists the columns in table order
+    let schema = db.get_table("addresses").unwrap().schema();
+    assert_eq!(
+        schema,
+        TableSchema {
+            name: "addresses".to_string(),
+            columns: vec![
+                ColumnSchema {
+                    name: "user_id".to_string(),
+                    data_type: ColumnDataType::Integer,
+                    is_primary_key: true,
+                    foreign_key: Some(ForeignKeyInfo::new("users", "id")),
+                    not_null: false,
+                    unique: false,
+                },
+                ColumnSchema {
+                    name: "address".to_string(),
+                    data_type: ColumnDataType::Text,
+                    is_primary_key: false,
+                    foreign_key: None,
+                    not_null: false,
+                    unique: false,
+                },
+            ],
+        }
+    );
+
+    // Display reproduces the describe output
+    assert_eq!(
+        schema.to_string(),
+        db.get_table("addresses").unwrap().render_description()
+    );
+    assert_eq!(
+        schema.to_string(),
+        "Table: addresses\n\n\
+         user_id address \n\
+         ---------------\n\
+         Integer Text    \n\
+         prim_key nt_prim_key \n\
+         -> users.id -       "
+    );
+
+    // Not-null constraints get their own line, before the unique ones
+    db.add_column_to_table(
+        "users",
+        Column::builder("email", ColumnDataType::Text)
+            .not_null()
+            .unique()
+            .build(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        db.get_table("users").unwrap().render_description(),
+        "Table: users\n\n\
+         id        user_name email     \n\
+         -----------------------------\n\
+         Integer   Text      Text      \n\
+         prim_key  nt_prim_key nt_prim_key \n\
+         -         -         not_null  \n\
+         -         -         unique    "
+    );
+
+    // Database-level schema covers every table
+    let schemas = db.schemas();
+    assert_eq!(schemas.len(), 2);
+    assert_eq!(schemas["users"].columns[1].name, "user_name");
+    assert_eq!(schemas["users"].columns[1].data_type, ColumnDataType::Text);
+    assert!(schemas["users"].columns[0].is_primary_key);
+    assert_eq!(schemas["addresses"], schema);
+
+    assert_eq!(db.schema_of("addresses").unwrap(), schema);
+    assert!(matches!(
+        db.schema_of("missing"),
+        Err(Error::TableNotFound(name)) if name == "missing"
+    ));
+}
+
+#[test]
+fn test_table_stats() {
+    let mut db = Database::new("test_db".to_string());
+    assert!(db.table_names().is_empty());
+
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "addresses",
+        vec![Column::new(
+            "address",
+            ColumnDataType::Text,
+            None,
+            false,
+            None,
+        )],
+    )
+    .unwrap();
+
+    for (id, name) in [("1", "Alice"), ("2", "Bob"), ("3", "Charlie")] {
+        db.insert_into_table("users", vec![id.to_string(), name.to_string()])
+            .unwrap();
+    }
+    db.insert_into_table("addresses", vec!["123 Main St.".to_string()])
+        .unwrap();
+
+    assert_eq!(db.table_names(), vec!["addresses", "users"]);
+    assert_eq!(
+        db.table_stats(),
+        vec![("addresses".to_string(), 1), ("users".to_string(), 3)]
+    );
+    assert_eq!(db.row_count_of_table("users").unwrap(), 3);
+
+    // The read-only accessors expose the same data
+    let names: Vec<&str> = db.tables().map(|table| table.name()).collect();
+    assert_eq!(names, vec!["addresses", "users"]);
+    let users = db.get_table("users").unwrap();
+    assert_eq!(users.columns().len(), 2);
+    assert_eq!(users.columns()[1].data.len(), 3);
+    assert_eq!(users.primary_key().unwrap().name, "id");
+    assert_eq!(users.primary_key().unwrap().data.len(), 3);
+    assert!(db.get_table("addresses").unwrap().primary_key().is_none());
+    assert!(matches!(
+        db.row_count_of_table("missing"),
+        Err(Error::TableNotFound(_))
+    ));
+}
+
+#[test]
+fn test_to_ddl() {
+    let mut db = Database::new("test_db".to_string());
+    db.create_table(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "manager_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+            Column::builder("email", ColumnDataType::Text)
+                .not_null()
+                .unique()
+                .build(),
+        ],
+    )
+    .unwrap();
+    db.create_table(
+        "accounts",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+            Column::new(
+                "balance",
+                ColumnDataType::Decimal,
+                Value::parse_decimal("0.00"),
+                false,
+                None,
+            ),
+            Column::new(
+                "account name",
+                ColumnDataType::Text,
+                Some(Value::Text("O'Brien".to_string())),
+                false,
+                None,
+            ),
+            Column::new(
+                "rate",
+                ColumnDataType::Float,
+                Some(Value::Float(0.125)),
+                false,
+                None,
+            ),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        db.get_table("accounts").unwrap().to_ddl(),
+        "CREATE TABLE accounts (\n    \
+         id INTEGER PRIMARY KEY,\n    \
+         user_id INTEGER,\n    \
+         balance NUMERIC DEFAULT 0.00,\n    \
+         \"account name\" TEXT DEFAULT 'O''Brien',\n    \
+         rate REAL DEFAULT 0.125,\n    \
+         FOREIGN KEY (user_id) REFERENCES users(id)\n\
+         );"
+    );
+
+    // accounts comes first by name but references users, whose self-reference doesn't count
+    assert_eq!(
+        db.to_ddl(),
+        "CREATE TABLE users (\n    \
+         id INTEGER PRIMARY KEY,\n    \
+         manager_id INTEGER,\n    \
+         email TEXT NOT NULL UNIQUE,\n    \
+         FOREIGN KEY (manager_id) REFERENCES users(id)\n\
+         );\n"
+            .to_string()
+            + &db.get_table("accounts").unwrap().to_ddl()
+    );
+}
+
+#[test]
+fn test_schema_diff() {
+    let mut staging = Database::new("staging".to_string());
+    let mut production = Database::new("production".to_string());
+    for db in [&mut staging, &mut production] {
+        db.create_table(
+            "teams",
+            vec![Column::new("id", ColumnDataType::Integer, None, true, None)],
+        )
+        .unwrap();
+    }
+    staging
+        .create_table(
+            "users",
+            vec![
+                Column::new("id", ColumnDataType::Integer, None, true, None),
+                Column::new("age", ColumnDataType::Integer, None, false, None),
+                Column::new("email", ColumnDataType::Text, None, false, None),
+                Column::new("team_id", ColumnDataType::Integer, None, false, None),
+            ],
+        )
+        .unwrap();
+    production
+        .create_table(
+            "users",
+            vec![
+                Column::builder("email", ColumnDataType::Text)
+                    .not_null()
+                    .unique()
+                    .build(),
+                Column::new("id", ColumnDataType::Float, None, true, None),
+                Column::new(
+                    "team_id",
+                    ColumnDataType::Integer,
+                    None,
+                    false,
+                    ForeignKeyInfo::new("teams", "id").into(),
+                ),
+                Column::new("name", ColumnDataType::Text, None, false, None),
+            ],
+        )
+        .unwrap();
+    production
+        .create_table(
+            "logs",
+            vec![Column::new("line", ColumnDataType::Text, None, false, None)],
+        )
+        .unwrap();
+
+    let users_staging = staging.schema_of("users").unwrap();
+    let diff = users_staging.diff(&production.schema_of("users").unwrap());
+    assert_eq!(diff.added_columns.len(), 1);
+    assert_eq!(diff.added_columns[0].name, "name");
+    assert_eq!(diff.removed_columns[0].name, "age");
+    assert_eq!(
+        diff.changed_columns,
+        vec![
+            (
+                "id".to_string(),
+                vec![ColumnChange::DataType(
+                    ColumnDataType::Integer,
+                    ColumnDataType::Float
+                )]
+            ),
+            (
+                "email".to_string(),
+                vec![
+                    ColumnChange::NotNull(false, true),
+                    ColumnChange::Unique(false, true)
+                ]
+            ),
+            (
+                "team_id".to_string(),
+                vec![ColumnChange::ForeignKey(
+                    None,
+                    Some(ForeignKeyInfo::new("teams", "id"))
+                )]
+            ),
+        ]
+    );
+    assert_eq!(
+        diff.to_string(),
+        "+ column name (Text)\n\
+         - column age (Integer)\n\
+         ~ column id: type Integer -> Float\n\
+         ~ column email: not null no -> yes, unique no -> yes\n\
+         ~ column team_id: foreign key none -> teams(id)"
+    );
+
+    // Column order doesn't count
+    let diff = users_staging.diff(&users_staging);
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "No differences");
+
+    // Tables only in one database have all their columns added or removed
+    let diffs = staging.diff_schemas(&production);
+    let table_names: Vec<&str> = diffs.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(table_names, vec!["logs", "users"]);
+    assert_eq!(diffs[0].1.added_columns[0].name, "line");
+    assert_eq!(
+        production.diff_schemas(&staging)[0].1.removed_columns[0].name,
+        "line"
+    );
+    assert!(staging.diff_schemas(&staging).is_empty());
+}
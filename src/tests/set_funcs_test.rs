@@ -0,0 +1,269 @@
+use crate::column::{Column, ColumnDataType, Value};
+use crate::table::{table_errors::Error, Table};
+
+fn table_with_rows(name: &str, rows: &[(&str, &str)]) -> Table {
+    let mut table = Table::new(
+        name,
+        vec![
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("city", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (name, city) in rows {
+        table
+            .insert(vec![name.to_string(), city.to_string()])
+            .unwrap();
+    }
+    table
+}
+
+fn names(table: &Table) -> Vec<Value> {
+    table.columns[0].data.clone()
+}
+
+fn text(values: &[&str]) -> Vec<Value> {
+    values.iter().map(|v| Value::Text(v.to_string())).collect()
+}
+
+#[test]
+fn test_set_operations() {
+    let left = table_with_rows(
+        "left",
+        &[
+            ("Alice", "Paris"),
+            ("Bob", "null"),
+            ("Alice", "Paris"),
+            ("Carol", "Rome"),
+        ],
+    );
+    // Same columns in another order, where NULL matches NULL
+    let mut right = Table::new(
+        "right",
+        vec![
+            Column::new("city", ColumnDataType::Text, None, false, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (city, name) in [
+        ("null", "Bob"),
+        ("Oslo", "Dave"),
+        ("Rome", "Alice"),
+        ("Oslo", "Dave"),
+    ] {
+        right
+            .insert(vec![city.to_string(), name.to_string()])
+            .unwrap();
+    }
+
+    let union = left.union(&right).unwrap();
+    assert_eq!(union.name, "left");
+    assert_eq!(
+        names(&union),
+        text(&["Alice", "Bob", "Carol", "Dave", "Alice"])
+    );
+    assert_eq!(union.columns[1].data[1], Value::Null);
+    assert_eq!(union.columns[1].data[4], Value::Text("Rome".to_string()));
+
+    let intersection = left.intersect(&right).unwrap();
+    assert_eq!(names(&intersection), text(&["Bob"]));
+
+    let difference = left.except(&right).unwrap();
+    assert_eq!(names(&difference), text(&["Alice", "Carol"]));
+    assert_eq!(
+        right.except(&left).unwrap().columns[1].data,
+        text(&["Dave", "Alice"])
+    );
+
+    // The inputs are left unchanged
+    assert_eq!(left.row_count(), 4);
+    assert_eq!(right.row_count(), 4);
+
+    // Set operations with an empty table
+    let empty = table_with_rows("empty", &[]);
+    assert_eq!(
+        names(&empty.union(&left).unwrap()),
+        text(&["Alice", "Bob", "Carol"])
+    );
+    assert_eq!(left.intersect(&empty).unwrap().row_count(), 0);
+    assert_eq!(left.except(&empty).unwrap().row_count(), 3);
+}
+
+#[test]
+fn test_set_operations_constraints() {
+    let columns = || {
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ]
+    };
+    let mut left = Table::new("left", columns()).unwrap();
+    let mut right = Table::new("right", columns()).unwrap();
+    left.insert(vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+    right
+        .insert(vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+    right
+        .insert(vec!["2".to_string(), "Bob".to_string()])
+        .unwrap();
+
+    // Identical records are kept once
+    let union = left.union(&right).unwrap();
+    assert_eq!(
+        union.columns[0].data,
+        vec![Value::Integer(1), Value::Integer(2)]
+    );
+    assert!(union.primary_key().is_none());
+
+    // Different records with the same primary key are both kept
+    right
+        .insert(vec!["3".to_string(), "Carol".to_string()])
+        .unwrap();
+    left.insert(vec!["3".to_string(), "Dave".to_string()])
+        .unwrap();
+    let union = left.union(&right).unwrap();
+    assert_eq!(
+        union.columns[0].data,
+        vec![
+            Value::Integer(1),
+            Value::Integer(3),
+            Value::Integer(2),
+            Value::Integer(3),
+        ]
+    );
+    assert!(!union.columns[0].is_primary_key);
+
+    let other = table_with_rows("other", &[]);
+    assert!(matches!(left.union(&other), Err(Error::SchemaMismatch(_))));
+    assert!(matches!(
+        left.intersect(&other),
+        Err(Error::SchemaMismatch(_))
+    ));
+    assert!(matches!(left.except(&other), Err(Error::SchemaMismatch(_))));
+}
+
+#[test]
+fn test_union_all() {
+    let columns = || {
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::builder("name", ColumnDataType::Text)
+                .not_null()
+                .build(),
+        ]
+    };
+    let mut left = Table::new("left", columns()).unwrap();
+    let mut right = Table::new("right", columns()).unwrap();
+    for (id, name) in [("1", "Alice"), ("2", "Bob")] {
+        left.insert(vec![id.to_string(), name.to_string()]).unwrap();
+    }
+    for (id, name) in [("2", "Bob"), ("3", "Carol")] {
+        right
+            .insert(vec![id.to_string(), name.to_string()])
+            .unwrap();
+    }
+
+    // Every record is kept, in order, including the shared primary key
+    let union = left.union_all(&right).unwrap();
+    assert_eq!(
+        union.columns[0].data,
+        vec![
+            Value::Integer(1),
+            Value::Integer(2),
+            Value::Integer(2),
+            Value::Integer(3),
+        ]
+    );
+    assert_eq!(
+        union.columns[1].data,
+        text(&["Alice", "Bob", "Bob", "Carol"])
+    );
+    assert!(union.primary_key().is_none());
+    assert!(!union.columns[1].not_null);
+    assert_eq!(left.row_count(), 2);
+
+    // union drops the repeated records, but not the clashing keys
+    assert_eq!(left.union(&right).unwrap().row_count(), 3);
+    right
+        .insert(vec!["1".to_string(), "Dave".to_string()])
+        .unwrap();
+    let union = left.union(&right).unwrap();
+    assert_eq!(union.row_count(), 4);
+    assert!(union.primary_key().is_none());
+    assert!(!union.columns[1].not_null);
+    assert_eq!(left.union_all(&right).unwrap().row_count(), 5);
+
+    let other = table_with_rows("other", &[]);
+    assert!(matches!(
+        left.union_all(&other),
+        Err(Error::SchemaMismatch(_))
+    ));
+}
+
+#[test]
+fn test_snapshot_difference_and_intersection() {
+    let snapshot = |name: &str, rows: &[(&str, &str)]| {
+        let mut table = Table::new(
+            name,
+            vec![
+                Column::new("id", ColumnDataType::Integer, None, true, None),
+                Column::new("price", ColumnDataType::Decimal, None, false, None),
+            ],
+        )
+        .unwrap();
+        for (id, price) in rows {
+            table
+                .insert(vec![id.to_string(), price.to_string()])
+                .unwrap();
+        }
+        table
+    };
+    let monday = snapshot("monday", &[("1", "9.99"), ("2", "5.00"), ("3", "1.50")]);
+    // 5.0 equals 5.00, only the price of 3 changed and 4 is new
+    let tuesday = snapshot(
+        "tuesday",
+        &[("1", "9.99"), ("2", "5.0"), ("3", "1.75"), ("4", "2.00")],
+    );
+
+    let ids = |table: Table| table.columns[0].data.clone();
+    assert_eq!(
+        ids(tuesday.difference(&monday).unwrap()),
+        vec![Value::Integer(3), Value::Integer(4)]
+    );
+    assert_eq!(
+        ids(monday.difference(&tuesday).unwrap()),
+        vec![Value::Integer(3)]
+    );
+    assert_eq!(
+        ids(monday.intersect(&tuesday).unwrap()),
+        vec![Value::Integer(1), Value::Integer(2)]
+    );
+    assert_eq!(
+        tuesday.difference(&monday).unwrap().columns[1].data,
+        tuesday.except(&monday).unwrap().columns[1].data
+    );
+
+    let other = table_with_rows("other", &[]);
+    assert!(matches!(
+        monday.difference(&other),
+        Err(Error::SchemaMismatch(_))
+    ));
+}
+
+#[test]
+fn test_set_operations_with_short_column() {
+    // The city column of the last record is missing, so it reads as NULL
+    let mut left = table_with_rows("left", &[("Alice", "Paris")]);
+    left.columns[0].data.push(Value::Text("Bob".to_string()));
+    let right = table_with_rows("right", &[("Bob", "null"), ("Carol", "Rome")]);
+
+    assert_eq!(
+        names(&left.union(&right).unwrap()),
+        text(&["Alice", "Bob", "Carol"])
+    );
+    assert_eq!(names(&left.intersect(&right).unwrap()), text(&["Bob"]));
+    assert_eq!(names(&left.except(&right).unwrap()), text(&["Alice"]));
+    assert_eq!(names(&right.except(&left).unwrap()), text(&["Carol"]));
+}
@@ -0,0 +1,89 @@
+use crate::column::{Column, ColumnDataType, Value};
+use crate::table::{table_errors::Error, Table};
+
+fn scores_table() -> Table {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, score) in [
+        ("1", "85.5"),
+        ("2", "null"),
+        ("3", "92.0"),
+        ("4", "75.0"),
+        ("5", "92.0"),
+    ] {
+        table
+            .insert(vec![id.to_string(), score.to_string()])
+            .unwrap();
+    }
+    table
+}
+
+fn ids(table: &Table) -> Vec<Value> {
+    table.columns[0].data.clone()
+}
+
+#[test]
+fn test_order_by() {
+    let table = scores_table();
+
+    // NULLs go last in both directions, ties keep their original order
+    let sorted = table.order_by("score", false).unwrap();
+    assert_eq!(
+        ids(&sorted),
+        vec![
+            Value::Integer(4),
+            Value::Integer(1),
+            Value::Integer(3),
+            Value::Integer(5),
+            Value::Integer(2),
+        ]
+    );
+
+    let sorted = table.order_by("score", true).unwrap();
+    assert_eq!(
+        ids(&sorted),
+        vec![
+            Value::Integer(3),
+            Value::Integer(5),
+            Value::Integer(1),
+            Value::Integer(4),
+            Value::Integer(2),
+        ]
+    );
+
+    let result = table.order_by("invalid", false);
+    assert!(matches!(result, Err(Error::NonExistingColumn(_))));
+}
+
+#[test]
+fn test_top_n() {
+    let table = scores_table();
+
+    let top = table.top_n("score", 3, true).unwrap();
+    assert_eq!(
+        ids(&top),
+        vec![Value::Integer(3), Value::Integer(5), Value::Integer(1)]
+    );
+    assert_eq!(top.primary_key_column.as_ref().unwrap().name, "id");
+
+    let bottom = table.top_n("score", 2, false).unwrap();
+    assert_eq!(ids(&bottom), vec![Value::Integer(4), Value::Integer(1)]);
+
+    // Asking for more records than exist returns them all, NULLs last
+    let all = table.top_n("score", 10, true).unwrap();
+    assert_eq!(ids(&all), ids(&table.order_by("score", true).unwrap()));
+
+    assert_eq!(
+        table.top_n("score", 0, true).unwrap().columns[0].data.len(),
+        0
+    );
+
+    let result = table.top_n("invalid", 3, true);
+    assert!(matches!(result, Err(Error::NonExistingColumn(_))));
+}
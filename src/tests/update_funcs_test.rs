@@ -1,5 +1,5 @@
-use crate::column::{Column, ColumnDataType};
-use crate::table::{table_errors::Error, NestedCondition, Table};
+use crate::column::{Column, ColumnDataType, Value};
+use crate::table::{table_errors::Error, Expr, NestedCondition, Operand, Table};
 
 #[test]
 fn test_update_column() {
@@ -42,7 +42,14 @@ fn test_update_column() {
 
     // Test updating with an invalid value for the column data type
     let result = table.update_column("score", "invalid");
-    assert!(matches!(result, Err(Error::ParseError(0, _))));
+    assert!(matches!(
+        result,
+        Err(Error::ParseError {
+            column_name,
+            value,
+            expected: ColumnDataType::Float,
+        }) if column_name == "score" && value == "invalid"
+    ));
 
     // Test updating the primary key column
     let result = table.update_column("id", "100");
@@ -89,7 +96,16 @@ fn test_update_with_nested_conditions() {
         ("user_name".to_string(), "Sam".to_string()),
         nested_condition,
     );
-    assert!(result.is_ok());
+    assert_eq!(result.unwrap(), 2);
+
+    // Test updating with a condition that matches no record
+    let nested_condition =
+        NestedCondition::Condition("age".to_string(), "=".to_string(), "99".to_string());
+    let result = table.update_with_nested_conditions(
+        ("user_name".to_string(), "Sam".to_string()),
+        nested_condition,
+    );
+    assert_eq!(result.unwrap(), 0);
 
     // Test updating with nested conditions (AND and OR)
     let nested_condition = NestedCondition::And(
@@ -115,7 +131,7 @@ fn test_update_with_nested_conditions() {
         ("user_name".to_string(), "Sam".to_string()),
         nested_condition,
     );
-    assert!(result.is_ok());
+    assert_eq!(result.unwrap(), 2);
 
     // Test updating with a non-existing column in the condition
     let nested_condition =
@@ -149,12 +165,498 @@ fn test_update_with_nested_conditions() {
         NestedCondition::Condition("user_id".to_string(), "=".to_string(), "3".to_string());
     let result = table
         .update_with_nested_conditions(("age".to_string(), "Sam".to_string()), nested_condition);
-    assert!(matches!(result, Err(Error::ParseError(1, _))));
+    assert!(matches!(
+        result,
+        Err(Error::ParseError {
+            column_name,
+            value,
+            expected: ColumnDataType::Integer,
+        }) if column_name == "age" && value == "Sam"
+    ));
 
     // Test updating the primary key column with duplicate values
+    let previous_ids = table.columns[0].data.clone();
     let nested_condition =
         NestedCondition::Condition("age".to_string(), "=".to_string(), "30".to_string());
     let result = table
         .update_with_nested_conditions(("user_id".to_string(), "1".to_string()), nested_condition);
     assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+
+    // The primary key column is restored after the failed update
+    assert_eq!(table.columns[0].data, previous_ids);
+}
+
+#[test]
+fn test_update_expr() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+
+    for (id, name, age, score) in [
+        ("1", "Alice", "25", "85.5"),
+        ("2", "Bob", "35", "92.0"),
+        ("3", "Charlie", "40", "null"),
+    ] {
+        table
+            .insert(vec![
+                id.to_string(),
+                name.to_string(),
+                age.to_string(),
+                score.to_string(),
+            ])
+            .unwrap();
+    }
+
+    let over_30 =
+        || NestedCondition::Condition("age".to_string(), ">".to_string(), "30".to_string());
+    let col = |name: &str| Operand::Col(name.to_string());
+    let lit = |value: &str| Operand::Lit(value.to_string());
+
+    // Numeric increments apply to matching records only, and NULL stays NULL
+    let updated = table
+        .update_expr("score", Expr::Add(col("score"), lit("5.0")), over_30())
+        .unwrap();
+    assert_eq!(updated, 2);
+    assert_eq!(
+        table.columns[3].data,
+        vec![Value::Float(85.5), Value::Float(97.0), Value::Null]
+    );
+
+    table
+        .update_expr("age", Expr::Mul(col("age"), lit("2")), over_30())
+        .unwrap();
+    table
+        .update_expr("age", Expr::Sub(col("age"), col("id")), over_30())
+        .unwrap();
+    assert_eq!(
+        table.columns[2].data,
+        vec![Value::Integer(25), Value::Integer(68), Value::Integer(77)]
+    );
+
+    // Text values are concatenated
+    table
+        .update_expr("name", Expr::Add(col("name"), lit(" (senior)")), over_30())
+        .unwrap();
+    assert_eq!(
+        table.columns[1].data,
+        vec![
+            Value::Text("Alice".to_string()),
+            Value::Text("Bob (senior)".to_string()),
+            Value::Text("Charlie (senior)".to_string()),
+        ]
+    );
+
+    // Type mismatches are rejected before any record is modified
+    let before = table
+        .columns
+        .iter()
+        .map(|c| c.data.clone())
+        .collect::<Vec<_>>();
+    for (column_name, expr) in [
+        ("age", Expr::Add(col("age"), lit("1.5"))),
+        ("age", Expr::Add(col("score"), lit("1"))),
+        ("name", Expr::Sub(col("name"), lit("x"))),
+        ("score", Expr::Add(col("name"), lit("1.0"))),
+        ("score", Expr::Add(col("score"), lit("inf"))),
+        ("score", Expr::Mul(col("score"), lit("1e308"))),
+    ] {
+        let result = table.update_expr(column_name, expr, over_30());
+        assert!(matches!(result, Err(Error::InvalidExpression(_))));
+    }
+    let result = table.update_expr(
+        "age",
+        Expr::Mul(col("age"), lit(&i64::MAX.to_string())),
+        over_30(),
+    );
+    assert!(matches!(result, Err(Error::InvalidExpression(_))));
+    let result = table.update_expr("missing", Expr::Add(col("age"), lit("1")), over_30());
+    assert!(matches!(result, Err(Error::NonExistingColumn(_))));
+    assert_eq!(
+        table
+            .columns
+            .iter()
+            .map(|c| c.data.clone())
+            .collect::<Vec<_>>(),
+        before
+    );
+
+    // Primary key updates are checked for duplicates
+    let result = table.update_expr(
+        "id",
+        Expr::Add(col("id"), lit("1")),
+        NestedCondition::Condition("id".to_string(), "=".to_string(), "1".to_string()),
+    );
+    assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+    assert_eq!(
+        table.columns[0].data,
+        vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]
+    );
+}
+
+#[test]
+fn test_update_by_pk() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, name, age) in [("1", "Alice", "25"), ("2", "Bob", "30")] {
+        table
+            .insert(vec![id.to_string(), name.to_string(), age.to_string()])
+            .unwrap();
+    }
+    let assign = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(column, value)| (column.to_string(), value.to_string()))
+            .collect()
+    };
+
+    let found = table
+        .update_by_pk("2", assign(&[("name", "Robert"), ("age", "31")]))
+        .unwrap();
+    assert!(found);
+    assert_eq!(
+        table.columns[1].data,
+        vec![
+            Value::Text("Alice".to_string()),
+            Value::Text("Robert".to_string())
+        ]
+    );
+    assert_eq!(
+        table.columns[2].data,
+        vec![Value::Integer(25), Value::Integer(31)]
+    );
+
+    // The primary key can change, but not to the key of another record
+    assert!(table.update_by_pk("2", assign(&[("id", "5")])).unwrap());
+    assert!(table.update_by_pk("5", assign(&[("id", "5")])).unwrap());
+    assert!(matches!(
+        table.update_by_pk("5", assign(&[("age", "40"), ("id", "1")])),
+        Err(Error::DuplicatePrimaryKey)
+    ));
+    assert_eq!(
+        table.columns[0].data,
+        vec![Value::Integer(1), Value::Integer(5)]
+    );
+    assert_eq!(
+        table.columns[2].data,
+        vec![Value::Integer(25), Value::Integer(31)]
+    );
+
+    // A missing key only updates nothing, but invalid assignments are still reported
+    assert!(!table
+        .update_by_pk("3", assign(&[("name", "Carol")]))
+        .unwrap());
+    assert!(matches!(
+        table.update_by_pk("3", assign(&[("missing", "x")])),
+        Err(Error::NonExistingColumn(_))
+    ));
+    assert!(matches!(
+        table.update_by_pk("1", assign(&[("age", "old")])),
+        Err(Error::ParseError { .. })
+    ));
+    assert!(matches!(
+        table.update_by_pk("one", assign(&[("age", "40")])),
+        Err(Error::InvalidConditionValue(_, _))
+    ));
+}
+
+#[test]
+fn test_update_by_primary_key() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["5".to_string(), "Alice".to_string()])
+        .unwrap();
+
+    let found = table
+        .update_by_primary_key("5", vec![("name".to_string(), "Bob".to_string())])
+        .unwrap();
+    assert!(found);
+    assert_eq!(table.columns[1].data, vec![Value::Text("Bob".to_string())]);
+    assert!(!table
+        .update_by_primary_key("6", vec![("name".to_string(), "Carol".to_string())])
+        .unwrap());
+
+    // The primary key itself can't be changed this way
+    assert!(matches!(
+        table.update_by_primary_key(
+            "5",
+            vec![
+                ("name".to_string(), "Carol".to_string()),
+                ("id".to_string(), "6".to_string())
+            ]
+        ),
+        Err(Error::CannotUpdatePrimaryKey(column_name)) if column_name == "id"
+    ));
+    assert_eq!(table.columns[1].data, vec![Value::Text("Bob".to_string())]);
+
+    let mut no_key = Table::new(
+        "no_key",
+        vec![Column::new("name", ColumnDataType::Text, None, false, None)],
+    )
+    .unwrap();
+    assert!(matches!(
+        no_key.update_by_primary_key("5", vec![]),
+        Err(Error::NoPrimaryKey)
+    ));
+}
+
+#[test]
+fn test_replace_values() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("state", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+            Column::builder("code", ColumnDataType::Text)
+                .unique()
+                .build(),
+        ],
+    )
+    .unwrap();
+    for (id, state, age, code) in [
+        ("1", "N.Y.", "25", "a"),
+        ("2", "CA", "30", "b"),
+        ("3", "N.Y.", "35", "c"),
+        ("4", "N.Y.", "40", "d"),
+    ] {
+        table
+            .insert(vec![
+                id.to_string(),
+                state.to_string(),
+                age.to_string(),
+                code.to_string(),
+            ])
+            .unwrap();
+    }
+    let states = |table: &Table| -> Vec<Value> { table.columns[1].data.clone() };
+
+    let condition =
+        NestedCondition::Condition("age".to_string(), ">".to_string(), "30".to_string());
+    assert_eq!(
+        table
+            .replace_values("state", "N.Y.", "NY", Some(condition))
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        table.replace_values("state", "N.Y.", "NY", None).unwrap(),
+        1
+    );
+    assert_eq!(
+        table.replace_values("state", "N.Y.", "NY", None).unwrap(),
+        0
+    );
+    assert_eq!(table.replace_values("state", "NY", "NY", None).unwrap(), 0);
+    assert_eq!(
+        states(&table),
+        ["NY", "CA", "NY", "NY"].map(|state| Value::Text(state.to_string()))
+    );
+    assert_eq!(table.replace_values("age", "30", "31", None).unwrap(), 1);
+    assert_eq!(table.columns[2].data[1], Value::Integer(31));
+
+    // Unique and primary key columns can't end up with duplicates
+    assert!(matches!(
+        table.replace_values("code", "a", "b", None),
+        Err(Error::DuplicateValue(_, _))
+    ));
+    assert_eq!(table.replace_values("code", "a", "z", None).unwrap(), 1);
+    assert!(matches!(
+        table.replace_values("id", "1", "2", None),
+        Err(Error::DuplicatePrimaryKey)
+    ));
+    assert_eq!(table.replace_values("id", "1", "5", None).unwrap(), 1);
+
+    assert!(matches!(
+        table.replace_values("age", "x", "31", None),
+        Err(Error::ParseError { .. })
+    ));
+    assert!(matches!(
+        table.replace_values("missing", "a", "b", None),
+        Err(Error::NonExistingColumn(_))
+    ));
+}
+
+#[test]
+fn test_update_constraints() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::builder("id", ColumnDataType::Integer)
+                .primary_key()
+                .build(),
+            Column::builder("email", ColumnDataType::Text)
+                .unique()
+                .build(),
+            Column::builder("score", ColumnDataType::Integer)
+                .not_null()
+                .build(),
+            Column::new("bonus", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    for row in [
+        ["1", "a@x.org", "10", "null"],
+        ["2", "b@x.org", "20", "5"],
+        ["3", "null", "30", "null"],
+    ] {
+        table
+            .insert(row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+    let before = table
+        .columns
+        .iter()
+        .map(|c| c.data.clone())
+        .collect::<Vec<_>>();
+    let all = || NestedCondition::Condition("id".to_string(), ">".to_string(), "0".to_string());
+    let id_is =
+        |id: &str| NestedCondition::Condition("id".to_string(), "=".to_string(), id.to_string());
+
+    // A single value for every record of a unique column
+    let result = table.update_column("email", "same@x.org");
+    assert!(matches!(
+        result,
+        Err(Error::DuplicateValue(column_name, value))
+            if column_name == "email" && value == "same@x.org"
+    ));
+    let result =
+        table.update_with_nested_conditions(("email".to_string(), "a@x.org".to_string()), all());
+    assert!(matches!(result, Err(Error::DuplicateValue(..))));
+    let result = table
+        .update_with_nested_conditions(("email".to_string(), "a@x.org".to_string()), id_is("2"));
+    assert!(matches!(result, Err(Error::DuplicateValue(..))));
+
+    // NULL operands make NULL results, which the primary key and not-null columns reject
+    let col = |name: &str| Operand::Col(name.to_string());
+    let result = table.update_expr("score", Expr::Add(col("score"), col("bonus")), all());
+    assert!(matches!(result, Err(Error::NullValue(column_name)) if column_name == "score"));
+    let result = table.update_expr("id", Expr::Add(col("id"), col("bonus")), all());
+    assert!(matches!(result, Err(Error::NullPrimaryKey)));
+
+    // Several assignments to the same record are applied together or not at all
+    let result = table.update_by_pk(
+        "1",
+        vec![
+            ("score".to_string(), "99".to_string()),
+            ("email".to_string(), "b@x.org".to_string()),
+        ],
+    );
+    assert!(matches!(result, Err(Error::DuplicateValue(..))));
+
+    assert_eq!(
+        table
+            .columns
+            .iter()
+            .map(|c| c.data.clone())
+            .collect::<Vec<_>>(),
+        before
+    );
+
+    // Updates that keep the values distinct still apply, and a record may keep its own value
+    table
+        .update_with_nested_conditions(("email".to_string(), "c@x.org".to_string()), id_is("3"))
+        .unwrap();
+    table
+        .update_by_pk("1", vec![("email".to_string(), "a@x.org".to_string())])
+        .unwrap();
+    table.update_column("score", "0").unwrap();
+    assert_eq!(
+        table.columns[2].data,
+        vec![Value::Integer(0), Value::Integer(0), Value::Integer(0)]
+    );
+}
+
+#[test]
+fn test_update_null_into_not_null() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::builder("id", ColumnDataType::Integer)
+                .primary_key()
+                .build(),
+            Column::builder("email", ColumnDataType::Text)
+                .not_null()
+                .build(),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "a@x.org".to_string()])
+        .unwrap();
+    table
+        .insert(vec!["2".to_string(), "b@x.org".to_string()])
+        .unwrap();
+    let before = table.columns[1].data.clone();
+
+    let result = table.update_column("email", "null");
+    assert!(matches!(result, Err(Error::NullValue(column_name)) if column_name == "email"));
+    assert_eq!(table.columns[1].data, before);
+
+    let result = table.update_with_nested_conditions(
+        ("email".to_string(), "NULL".to_string()),
+        NestedCondition::Condition("id".to_string(), "=".to_string(), "2".to_string()),
+    );
+    assert!(matches!(result, Err(Error::NullValue(column_name)) if column_name == "email"));
+    assert_eq!(table.columns[1].data, before);
+}
+
+#[test]
+fn test_update_expr_case_insensitive() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("Id", ColumnDataType::Integer, None, true, None),
+            Column::new("Score", ColumnDataType::Integer, None, false, None),
+            Column::new("Bonus", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    table.case_insensitive_names = true;
+    for row in [["1", "10", "5"], ["2", "20", "null"]] {
+        table
+            .insert(row.iter().map(|v| v.to_string()).collect())
+            .unwrap();
+    }
+
+    let affected = table
+        .update_expr(
+            "SCORE",
+            Expr::Add(
+                Operand::Col("score".to_string()),
+                Operand::Col("bonus".to_string()),
+            ),
+            NestedCondition::Condition("id".to_string(), "=".to_string(), "1".to_string()),
+        )
+        .unwrap();
+    assert_eq!(affected, 1);
+    assert_eq!(
+        table.columns[1].data,
+        vec![Value::Integer(15), Value::Integer(20)]
+    );
+    assert_eq!(table.non_null_count("BONUS").unwrap(), 1);
+    assert!(matches!(
+        table.non_null_count("points"),
+        Err(Error::NonExistingColumn(_))
+    ));
 }
@@ -1,5 +1,5 @@
-use crate::column::{Column, ColumnDataType};
-use crate::table::{table_errors::Error, Table};
+use crate::column::{Column, ColumnDataType, ForeignKeyInfo, Value};
+use crate::table::{table_errors::Error, NestedCondition, ShowOptions, Table};
 
 #[test]
 fn test_project() {
@@ -46,6 +46,7 @@ fn test_project() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_column_count() {
     let mut table = Table::new(
         "test_table",
@@ -94,6 +95,16 @@ fn test_column_count() {
     // Test counting for a non-existing column
     let result = table.column_count(Some("invalid".to_string()));
     assert!(matches!(result, Err(Error::NonExistingColumn(_))));
+
+    // The deprecated wrapper agrees with the methods it delegates to
+    assert_eq!(table.row_count(), 4);
+    assert_eq!(table.non_null_count("score").unwrap(), 3);
+    assert_eq!(table.non_null_count("id").unwrap(), 4);
+    assert!(matches!(
+        table.non_null_count("invalid"),
+        Err(Error::NonExistingColumn(_))
+    ));
+    assert!(matches!(Table::new("empty", vec![]), Err(Error::NoColumns)));
 }
 
 #[test]
@@ -158,3 +169,929 @@ fn test_copy() {
         copied_table.columns[0].data.len()
     );
 }
+
+#[test]
+fn test_head_and_tail() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new(
+                "owner_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("owners", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    table
+        .referenced_as_foreign_key
+        .push(("children".to_string(), "parent_id".to_string()));
+
+    for id in 1..=5 {
+        table
+            .insert(vec![id.to_string(), (id * 10).to_string()])
+            .unwrap();
+    }
+
+    let head = table.head(2);
+    assert_eq!(
+        head.columns[0].data,
+        vec![Value::Integer(1), Value::Integer(2)]
+    );
+    assert_eq!(
+        head.columns[1].data,
+        vec![Value::Integer(10), Value::Integer(20)]
+    );
+
+    let tail = table.tail(2);
+    assert_eq!(
+        tail.columns[0].data,
+        vec![Value::Integer(4), Value::Integer(5)]
+    );
+
+    // Asking for more rows than exist returns everything
+    assert_eq!(table.head(10).columns[0].data.len(), 5);
+    assert_eq!(table.tail(10).columns[0].data.len(), 5);
+    assert_eq!(table.head(0).columns[0].data.len(), 0);
+
+    // Schema metadata is preserved
+    assert!(tail.columns[0].is_primary_key);
+    assert_eq!(tail.primary_key_column.as_ref().unwrap().name, "id");
+    assert_eq!(
+        tail.columns[1].foreign_key,
+        Some(ForeignKeyInfo::new("owners", "id"))
+    );
+    assert_eq!(
+        tail.referenced_as_foreign_key,
+        table.referenced_as_foreign_key
+    );
+
+    // The primary key is still enforced on the result
+    let mut head = head;
+    let result = head.insert(vec!["1".to_string(), "10".to_string()]);
+    assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+}
+
+#[test]
+fn test_sample() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for id in 0..20 {
+        table
+            .insert(vec![id.to_string(), format!("user_{}", id)])
+            .unwrap();
+    }
+
+    // The same seed selects the same rows
+    let sample = table.sample(5, 42);
+    assert_eq!(sample.columns[0].data.len(), 5);
+    assert_eq!(sample.columns[0].data, table.sample(5, 42).columns[0].data);
+
+    // Rows are chosen without replacement and stay aligned across columns
+    let mut ids: Vec<i64> = sample.columns[0]
+        .data
+        .iter()
+        .map(|v| match v {
+            Value::Integer(id) => *id,
+            _ => panic!("Expected an integer id"),
+        })
+        .collect();
+    for (id, name) in ids.iter().zip(sample.columns[1].data.iter()) {
+        assert_eq!(*name, Value::Text(format!("user_{}", id)));
+    }
+    ids.dedup();
+    assert_eq!(ids.len(), 5);
+
+    // Asking for more rows than exist returns everything
+    let sample = table.sample(50, 7);
+    assert_eq!(sample.columns[0].data, table.columns[0].data);
+
+    // Column metadata is kept
+    assert!(sample.columns[0].is_primary_key);
+    assert_eq!(sample.primary_key_column.as_ref().unwrap().name, "id");
+}
+
+#[test]
+fn test_project_with_aliases() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("user_name", ColumnDataType::Text, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec![
+            "1".to_string(),
+            "Alice".to_string(),
+            "85.5".to_string(),
+        ])
+        .unwrap();
+    table
+        .insert(vec!["2".to_string(), "Bob".to_string(), "92.0".to_string()])
+        .unwrap();
+
+    // The returned table uses the aliases and keeps the requested order
+    let projected = table
+        .project_into_table_with_aliases(vec![
+            ("user_name".to_string(), Some("name".to_string())),
+            ("id".to_string(), None),
+        ])
+        .unwrap();
+    let names: Vec<&str> = projected.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["name", "id"]);
+    assert_eq!(
+        projected.columns[0].data,
+        vec![
+            Value::Text("Alice".to_string()),
+            Value::Text("Bob".to_string())
+        ]
+    );
+    assert_eq!(projected.primary_key_column.as_ref().unwrap().name, "id");
+
+    // Plain column names keep working
+    let projected = table.project_into_table(vec!["score".to_string()]).unwrap();
+    assert_eq!(projected.columns.len(), 1);
+    assert!(projected.primary_key_column.is_none());
+
+    let result = table.project_with_aliases(vec![
+        ("user_name".to_string(), Some("name".to_string())),
+        ("score".to_string(), Some("points".to_string())),
+    ]);
+    assert!(result.is_ok());
+
+    // Two columns with the same output name are rejected
+    let result = table.project_with_aliases(vec![
+        ("user_name".to_string(), Some("label".to_string())),
+        ("score".to_string(), Some("label".to_string())),
+    ]);
+    assert!(matches!(result, Err(Error::DuplicateOutputColumn(name)) if name == "label"));
+
+    let result = table.project_into_table_with_aliases(vec![
+        ("id".to_string(), None),
+        ("score".to_string(), Some("id".to_string())),
+    ]);
+    assert!(matches!(result, Err(Error::DuplicateOutputColumn(name)) if name == "id"));
+
+    // Aliases follow the same naming rules as table columns
+    let result = table
+        .project_into_table_with_aliases(vec![("score".to_string(), Some(" points".to_string()))]);
+    assert!(matches!(result, Err(Error::InvalidName(name, _)) if name == " points"));
+
+    // Unknown source columns are still reported
+    let result =
+        table.project_into_table_with_aliases(vec![("invalid".to_string(), Some("x".to_string()))]);
+    assert!(matches!(result, Err(Error::NonExistingColumns(_))));
+}
+
+#[test]
+fn test_display_and_description() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("score", ColumnDataType::Float, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec![
+            "1".to_string(),
+            "Alice".to_string(),
+            "85.5".to_string(),
+        ])
+        .unwrap();
+    table
+        .insert(vec!["2".to_string(), "Bob".to_string(), "92.0".to_string()])
+        .unwrap();
+
+    assert_eq!(
+        table.to_string(),
+        "id  name score \n\
+         --------------\n \
+         1 Alice 85.50 \n \
+         2   Bob 92.00 "
+    );
+
+    // Projections render through the same layout, using the output column names
+    let projected = table
+        .project_into_table_with_aliases(vec![
+            ("name".to_string(), Some("n".to_string())),
+            ("score".to_string(), None),
+        ])
+        .unwrap();
+    assert_eq!(
+        projected.to_string(),
+        "    n score \n-----------\nAlice 85.50 \n  Bob 92.00 "
+    );
+
+    assert_eq!(
+        table.render_description(),
+        "Table: test_table\n\n\
+         id    name  score \n\
+         -----------------\n\
+         Integer Text  Float \n\
+         prim_key nt_prim_key nt_prim_key "
+    );
+
+    // A table without rows renders only its header
+    table.truncate();
+    assert_eq!(table.to_string(), "id name score \n-------------");
+}
+
+#[test]
+fn test_render_with_options() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, name) in [("1", "Alice"), ("2", "Bartholomew"), ("3", "Bob")] {
+        table
+            .insert(vec![id.to_string(), name.to_string()])
+            .unwrap();
+    }
+
+    let options = ShowOptions {
+        max_rows: Some(2),
+        max_cell_width: Some(6),
+        show_row_numbers: true,
+    };
+    assert_eq!(
+        table.render_with_options(&options),
+        "# id   name\n-----------\n0  1  Alice\n1  2 Barth…\n... (1 more rows)"
+    );
+
+    // Without limits, widths follow the longest value
+    assert_eq!(
+        table.render_with_options(&ShowOptions::default()),
+        "id        name\n--------------\n 1       Alice\n 2 Bartholomew\n 3         Bob"
+    );
+}
+
+#[test]
+fn test_display_widths_follow_values() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "Bartholomew".to_string()])
+        .unwrap();
+    table
+        .insert(vec!["1000".to_string(), "null".to_string()])
+        .unwrap();
+
+    // Values longer than their header widen the column and the separator
+    assert_eq!(
+        table.to_string(),
+        "  id        name \n\
+         ----------------\n   \
+         1 Bartholomew \n\
+         1000        NULL "
+    );
+}
+
+#[test]
+fn test_validate() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    assert!(table.validate().is_ok());
+
+    table
+        .insert(vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+    assert!(table.validate().is_ok());
+
+    // A column left behind by a direct edit is reported against the longest column
+    table.columns[0].data.push(Value::Integer(2));
+    assert!(matches!(
+        table.validate(),
+        Err(Error::InconsistentColumnLengths(column_name, 1, 2)) if column_name == "name"
+    ));
+
+    table.columns[1].data.push(Value::Text("Bob".to_string()));
+    assert!(table.validate().is_ok());
+}
+
+#[test]
+fn test_name_validation() {
+    let column = |name: &str| Column::new(name, ColumnDataType::Integer, None, false, None);
+
+    let invalid_names = [
+        "",
+        "   ",
+        " id",
+        "id ",
+        "user\nname",
+        "a\tb",
+        &"x".repeat(256),
+    ];
+    for name in invalid_names {
+        let result = Table::new(name, vec![column("id")]);
+        assert!(
+            matches!(&result, Err(Error::InvalidName(invalid, _)) if invalid == name),
+            "table name {:?} was accepted",
+            name
+        );
+
+        let result = Table::new("test_table", vec![column("id"), column(name)]);
+        assert!(
+            matches!(&result, Err(Error::InvalidName(invalid, _)) if invalid == name),
+            "column name {:?} was accepted",
+            name
+        );
+    }
+
+    // Inner spaces, punctuation, non-ASCII letters and the longest allowed name are fine
+    for name in ["user name", "score_%", "città", &"x".repeat(255)] {
+        assert!(Table::new(name, vec![column(name)]).is_ok());
+    }
+
+    // The reason is part of the message
+    let error = Table::new("users\n", vec![column("id")]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid name \"users\\n\": it has leading or trailing whitespace"
+    );
+}
+
+#[test]
+fn test_case_insensitive_names() {
+    let mut table = Table::new(
+        "test_table",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("UserName", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "Alice".to_string(), "30".to_string()])
+        .unwrap();
+
+    // Names must match exactly by default
+    let condition = NestedCondition::parse("AGE > 25").unwrap();
+    assert!(matches!(
+        table.filter(&condition),
+        Err(Error::NonExistingColumn(column_name)) if column_name == "AGE"
+    ));
+    assert!(matches!(
+        table.insert_with_columns(vec!["ID".to_string()], vec!["2".to_string()]),
+        Err(Error::NonExistingColumns(_))
+    ));
+
+    table.set_case_insensitive_names(true);
+
+    // Conditions, inserts and projections resolve to the original columns
+    table
+        .insert_with_columns(
+            vec!["ID".to_string(), "username".to_string(), "Age".to_string()],
+            vec!["2".to_string(), "Bob".to_string(), "20".to_string()],
+        )
+        .unwrap();
+    assert_eq!(table.count_where(&condition).unwrap(), 1);
+    let projected = table
+        .project_into_table(vec!["USERNAME".to_string(), "Id".to_string()])
+        .unwrap();
+    let names: Vec<&str> = projected.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["UserName", "id"]);
+    assert_eq!(
+        table.order_by("AGE", false).unwrap().columns[0].data[0],
+        Value::Integer(2)
+    );
+
+    // The same column named twice with different casing is still a duplicate
+    let result = table.insert_with_columns(
+        vec!["id".to_string(), "ID".to_string()],
+        vec!["3".to_string(), "4".to_string()],
+    );
+    assert!(
+        matches!(result, Err(Error::DuplicateColumnInInsert(column_name)) if column_name == "id")
+    );
+
+    // Unknown columns are reported with the name as given
+    let result = table.filter(&NestedCondition::parse("Height > 1").unwrap());
+    assert!(
+        matches!(result, Err(Error::NonExistingColumn(column_name)) if column_name == "Height")
+    );
+
+    // Two columns that only differ by case make the name ambiguous
+    let mut ambiguous = Table::new(
+        "test_table",
+        vec![
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("Name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    ambiguous.set_case_insensitive_names(true);
+    let result = ambiguous.filter(&NestedCondition::parse("name = 'x'").unwrap());
+    assert!(
+        matches!(result, Err(Error::AmbiguousColumnName(column_name)) if column_name == "name")
+    );
+}
+
+#[test]
+fn test_add_column() {
+    let mut table = Table::new(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "Alice".to_string()])
+        .unwrap();
+    table
+        .insert(vec!["2".to_string(), "Bob".to_string()])
+        .unwrap();
+
+    // Existing records take the fill value, the default value or NULL
+    table
+        .add_column(
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+            Some("30".to_string()),
+        )
+        .unwrap();
+    table
+        .add_column(
+            Column::builder("country", ColumnDataType::Text)
+                .default_value(Value::Text("unknown".to_string()))
+                .build(),
+            None,
+        )
+        .unwrap();
+    table
+        .add_column(
+            Column::new("email", ColumnDataType::Text, None, false, None),
+            None,
+        )
+        .unwrap();
+    assert_eq!(table.columns[2].data, vec![Value::Integer(30); 2]);
+    assert_eq!(
+        table.columns[3].data,
+        vec![Value::Text("unknown".to_string()); 2]
+    );
+    assert_eq!(table.columns[4].data, vec![Value::Null; 2]);
+    assert!(table.validate().is_ok());
+
+    // New records fill every column
+    table
+        .insert(vec![
+            "3".to_string(),
+            "Carol".to_string(),
+            "41".to_string(),
+            "Norway".to_string(),
+            "carol@example.com".to_string(),
+        ])
+        .unwrap();
+    assert!(table.validate().is_ok());
+
+    // Columns that can't be added leave the table unchanged
+    let result = table.add_column(
+        Column::new("age", ColumnDataType::Integer, None, false, None),
+        None,
+    );
+    assert!(matches!(result, Err(Error::ColumnAlreadyExists(column_name)) if column_name == "age"));
+    let result = table.add_column(
+        Column::new("score", ColumnDataType::Integer, None, false, None),
+        Some("high".to_string()),
+    );
+    assert!(matches!(result, Err(Error::ParseError { .. })));
+    let result = table.add_column(
+        Column::builder("nickname", ColumnDataType::Text)
+            .not_null()
+            .build(),
+        None,
+    );
+    assert!(matches!(result, Err(Error::NullValue(column_name)) if column_name == "nickname"));
+    let result = table.add_column(
+        Column::builder("code", ColumnDataType::Text)
+            .unique()
+            .build(),
+        Some("x".to_string()),
+    );
+    assert!(matches!(result, Err(Error::DuplicateValue(..))));
+    let result = table.add_column(
+        Column::new("other_id", ColumnDataType::Integer, None, true, None),
+        Some("7".to_string()),
+    );
+    assert!(matches!(result, Err(Error::MultiplePrimaryKeys)));
+    let result = table.add_column(
+        Column::new(" padded", ColumnDataType::Integer, None, false, None),
+        None,
+    );
+    assert!(matches!(result, Err(Error::InvalidName(..))));
+    assert_eq!(table.columns.len(), 5);
+}
+
+#[test]
+fn test_drop_column() {
+    let mut table = Table::new(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "Alice".to_string(), "30".to_string()])
+        .unwrap();
+    table.create_index("age").unwrap();
+
+    table.drop_column("age").unwrap();
+    let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["id", "name"]);
+    assert!(!table.has_index("age"));
+    table
+        .insert(vec!["2".to_string(), "Bob".to_string()])
+        .unwrap();
+    assert!(table.validate().is_ok());
+
+    let result = table.drop_column("age");
+    assert!(matches!(result, Err(Error::NonExistingColumn(column_name)) if column_name == "age"));
+    let result = table.drop_column("id");
+    assert!(matches!(result, Err(Error::CannotDropPrimaryKey(column_name)) if column_name == "id"));
+
+    let mut single = Table::new(
+        "single",
+        vec![Column::new("name", ColumnDataType::Text, None, false, None)],
+    )
+    .unwrap();
+    assert!(matches!(single.drop_column("name"), Err(Error::NoColumns)));
+}
+
+#[test]
+fn test_rename_column() {
+    let mut table = Table::new(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "Alice".to_string(), "30".to_string()])
+        .unwrap();
+    table.create_index("name").unwrap();
+
+    table.rename_column("name", "user_name").unwrap();
+    table.rename_column("id", "user_id").unwrap();
+    assert_eq!(table.columns[1].name, "user_name");
+    assert_eq!(table.primary_key().unwrap().name, "user_id");
+    assert!(table.has_index("user_name"));
+    assert!(!table.has_index("name"));
+    let condition = NestedCondition::parse("user_name = 'Alice' AND user_id = 1").unwrap();
+    assert_eq!(table.count_where(&condition).unwrap(), 1);
+
+    // Renaming a column to its own name changes nothing
+    table.rename_column("age", "age").unwrap();
+
+    let result = table.rename_column("age", "user_name");
+    assert!(
+        matches!(result, Err(Error::ColumnAlreadyExists(column_name)) if column_name == "user_name")
+    );
+    let result = table.rename_column("name", "full_name");
+    assert!(matches!(result, Err(Error::NonExistingColumn(column_name)) if column_name == "name"));
+    let result = table.rename_column("age", "");
+    assert!(matches!(result, Err(Error::InvalidName(..))));
+    assert_eq!(table.columns[2].name, "age");
+}
+
+#[test]
+fn test_change_column_type() {
+    let mut table = Table::new(
+        "measurements",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("count", ColumnDataType::Integer, None, false, None),
+            Column::new("ratio", ColumnDataType::Float, None, false, None),
+            Column::new(
+                "price",
+                ColumnDataType::Decimal,
+                Value::parse_decimal("1.00"),
+                false,
+                None,
+            ),
+            Column::new("label", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for row in [
+        ["1", "10", "2.0", "3.50", "42"],
+        ["2", "null", "-4.0", "7.00", "null"],
+        ["3", "7", "0.5", "2.25", "x"],
+    ] {
+        table
+            .insert(row.iter().map(|value| value.to_string()).collect())
+            .unwrap();
+    }
+    table.create_index("count").unwrap();
+
+    // Integer to Float always works, and the index follows the new values
+    table
+        .change_column_type("count", ColumnDataType::Float)
+        .unwrap();
+    assert_eq!(table.columns[1].data_type, ColumnDataType::Float);
+    assert_eq!(
+        table.columns[1].data,
+        vec![Value::Float(10.0), Value::Null, Value::Float(7.0)]
+    );
+    let condition = NestedCondition::parse("count = 7").unwrap();
+    assert_eq!(table.filter_indexed(&condition).unwrap().row_count(), 1);
+
+    // Float to Integer only works if every value is whole
+    let result = table.change_column_type("ratio", ColumnDataType::Integer);
+    assert!(matches!(
+        result,
+        Err(Error::IncompatibleTypeConversion(column_name, ColumnDataType::Integer, value))
+            if column_name == "ratio" && value == "0.50"
+    ));
+    assert_eq!(table.columns[2].data_type, ColumnDataType::Float);
+    table
+        .change_column_type("count", ColumnDataType::Integer)
+        .unwrap();
+    assert_eq!(
+        table.columns[1].data,
+        vec![Value::Integer(10), Value::Null, Value::Integer(7)]
+    );
+
+    // Anything to Text through its display form, the default value included
+    table
+        .change_column_type("price", ColumnDataType::Text)
+        .unwrap();
+    assert_eq!(
+        table.columns[3].data,
+        vec![
+            Value::Text("3.50".to_string()),
+            Value::Text("7.00".to_string()),
+            Value::Text("2.25".to_string()),
+        ]
+    );
+    assert_eq!(
+        table.columns[3].default_value,
+        Some(Value::Text("1.00".to_string()))
+    );
+    table
+        .change_column_type("id", ColumnDataType::Text)
+        .unwrap();
+    assert_eq!(table.primary_key().unwrap().data_type, ColumnDataType::Text);
+    assert_eq!(table.columns[0].data[2], Value::Text("3".to_string()));
+
+    // Text back to numbers only if every value parses
+    table
+        .change_column_type("price", ColumnDataType::Decimal)
+        .unwrap();
+    assert_eq!(table.columns[3].data[0], Value::Decimal(350, 2));
+    let result = table.change_column_type("label", ColumnDataType::Integer);
+    assert!(matches!(
+        result,
+        Err(Error::IncompatibleTypeConversion(_, _, value)) if value == "x"
+    ));
+
+    // Decimal to Integer needs whole values, Decimal to Float always works
+    let result = table.change_column_type("price", ColumnDataType::Integer);
+    assert!(matches!(result, Err(Error::IncompatibleTypeConversion(..))));
+    table
+        .change_column_type("price", ColumnDataType::Float)
+        .unwrap();
+    assert_eq!(table.columns[3].data[2], Value::Float(2.25));
+    table
+        .change_column_type("ratio", ColumnDataType::Decimal)
+        .unwrap();
+    assert_eq!(table.columns[2].data[1], Value::Decimal(-4, 0));
+
+    assert!(matches!(
+        table.change_column_type("missing", ColumnDataType::Text),
+        Err(Error::NonExistingColumn(_))
+    ));
+}
+
+#[test]
+fn test_change_column_type_errors() {
+    let mut table = Table::new(
+        "orders",
+        vec![
+            Column::new("id", ColumnDataType::Float, None, true, None),
+            Column::new(
+                "user_id",
+                ColumnDataType::Integer,
+                None,
+                false,
+                ForeignKeyInfo::new("users", "id").into(),
+            ),
+        ],
+    )
+    .unwrap();
+    for row in [["1.001", "1"], ["1.002", "2"]] {
+        table
+            .insert(row.iter().map(|value| value.to_string()).collect())
+            .unwrap();
+    }
+
+    // Both sides of a foreign key would have to change together
+    let result = table.change_column_type("user_id", ColumnDataType::Float);
+    assert!(
+        matches!(result, Err(Error::ForeignKeyTypeChange(column_name)) if column_name == "user_id")
+    );
+
+    // Both keys display as "1.00"
+    let result = table.change_column_type("id", ColumnDataType::Text);
+    assert!(matches!(result, Err(Error::DuplicatePrimaryKey)));
+    assert_eq!(table.columns[0].data[0], Value::Float(1.001));
+}
+
+#[test]
+fn test_reorder_columns() {
+    let mut table = Table::new(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+            Column::new("age", ColumnDataType::Integer, None, false, None),
+        ],
+    )
+    .unwrap();
+    table
+        .insert(vec!["1".to_string(), "Alice".to_string(), "30".to_string()])
+        .unwrap();
+    table.create_index("name").unwrap();
+
+    table
+        .reorder_columns(vec![
+            "name".to_string(),
+            "age".to_string(),
+            "id".to_string(),
+        ])
+        .unwrap();
+    let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["name", "age", "id"]);
+    assert_eq!(
+        table.columns[0].data,
+        vec![Value::Text("Alice".to_string())]
+    );
+
+    // Positional inserts follow the new order
+    table
+        .insert(vec!["Bob".to_string(), "25".to_string(), "2".to_string()])
+        .unwrap();
+    assert_eq!(
+        table.columns[2].data,
+        vec![Value::Integer(1), Value::Integer(2)]
+    );
+    assert_eq!(table.primary_key().unwrap().name, "id");
+    let condition = NestedCondition::parse("name = 'Bob'").unwrap();
+    let filtered = table.filter_indexed(&condition).unwrap();
+    assert_eq!(filtered.columns[1].data, vec![Value::Integer(25)]);
+
+    let result = table.reorder_columns(vec!["name".to_string(), "age".to_string()]);
+    assert!(
+        matches!(result, Err(Error::InvalidColumnOrder(reason)) if reason == "column 'id' is missing")
+    );
+    let result = table.reorder_columns(vec![
+        "name".to_string(),
+        "name".to_string(),
+        "id".to_string(),
+    ]);
+    assert!(matches!(result, Err(Error::InvalidColumnOrder(_))));
+    let result = table.reorder_columns(vec![
+        "name".to_string(),
+        "age".to_string(),
+        "email".to_string(),
+    ]);
+    assert!(matches!(result, Err(Error::NonExistingColumn(column_name)) if column_name == "email"));
+    assert_eq!(table.columns[0].name, "name");
+}
+
+#[test]
+fn test_drop_primary_key() {
+    let mut table = Table::new(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("name", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, name) in [("1", "Alice"), ("2", "Bob")] {
+        table
+            .insert(vec![id.to_string(), name.to_string()])
+            .unwrap();
+    }
+    assert!(matches!(
+        table.update_column("id", "0"),
+        Err(Error::CannotBatchUpdatePrimaryKey)
+    ));
+
+    table.drop_primary_key().unwrap();
+    assert!(table.primary_key().is_none());
+    assert!(!table.columns[0].is_primary_key);
+
+    // The former key is an ordinary column now
+    table.update_column("id", "0").unwrap();
+    assert_eq!(
+        table.columns[0].data,
+        vec![Value::Integer(0), Value::Integer(0)]
+    );
+    table
+        .insert(vec!["0".to_string(), "Charlie".to_string()])
+        .unwrap();
+
+    assert!(matches!(table.drop_primary_key(), Err(Error::NoPrimaryKey)));
+}
+
+#[test]
+fn test_add_unique_constraint() {
+    let mut table = Table::new(
+        "users",
+        vec![
+            Column::new("id", ColumnDataType::Integer, None, true, None),
+            Column::new("email", ColumnDataType::Text, None, false, None),
+        ],
+    )
+    .unwrap();
+    for (id, email) in [
+        ("1", "a@x.org"),
+        ("2", "b@x.org"),
+        ("3", "a@x.org"),
+        ("4", "null"),
+        ("5", "b@x.org"),
+        ("6", "null"),
+        ("7", "a@x.org"),
+    ] {
+        table
+            .insert(vec![id.to_string(), email.to_string()])
+            .unwrap();
+    }
+
+    // Every duplicated value is reported once, NULLs are not duplicates
+    let result = table.add_unique_constraint("email");
+    assert!(matches!(
+        result,
+        Err(Error::DuplicateValues(column_name, values))
+            if column_name == "email" && values == vec!["a@x.org", "b@x.org"]
+    ));
+    assert!(!table.columns[1].unique);
+
+    table
+        .delete_with_nested_conditions(
+            &NestedCondition::parse("id >= 3 AND email IS NOT NULL").unwrap(),
+        )
+        .unwrap();
+    table.add_unique_constraint("email").unwrap();
+    assert!(table.columns[1].unique);
+    let result = table.insert(vec!["8".to_string(), "a@x.org".to_string()]);
+    assert!(matches!(result, Err(Error::DuplicateValue(..))));
+    table
+        .insert(vec!["8".to_string(), "null".to_string()])
+        .unwrap();
+
+    // describe shows the constraint
+    assert_eq!(
+        table.render_description(),
+        "Table: users\n\n\
+         id    email \n\
+         -----------\n\
+         Integer Text  \n\
+         prim_key nt_prim_key \n\
+         -     unique "
+    );
+
+    assert!(matches!(
+        table.add_unique_constraint("name"),
+        Err(Error::NonExistingColumn(_))
+    ));
+}